@@ -1,6 +1,12 @@
 use anyhow::{Context, Result};
 use std::env;
 
+/// Database file used when `DATABASE_PATH` isn't set
+pub const DEFAULT_DATABASE_PATH: &str = "./bot_history.db";
+
+/// Order form presets used when `ORDER_SIZE_PRESETS` isn't set
+const DEFAULT_ORDER_SIZE_PRESETS: [f64; 3] = [5.0, 25.0, 100.0];
+
 #[derive(Debug, Clone)]
 pub struct Config {
     // Authentication - only private key needed, CLOB auth is dynamic
@@ -9,18 +15,64 @@ pub struct Config {
     // Trading parameters
     pub max_order_size: f64,
     pub min_order_size: f64,
+    /// Sizes picked with keys 1-9 in the order form, instead of typing one
+    pub order_size_presets: Vec<f64>,
     pub volume_velocity_threshold: f64,
     pub obi_threshold: f64,
 
     // System
     pub database_path: String,
     pub rpc_url: Option<String>,
+    pub portfolio_snapshot_interval_secs: u64,
+    pub log_retention: usize,
+    pub log_to_file: bool,
+    pub log_file_path: String,
+    /// `tracing` filter; its default level is also the TUI log panel's minimum
+    pub log_level: String,
+    pub skip_balance_check: bool,
+    pub cancel_on_exit: bool,
+    pub data_retention_days: u32,
+    pub db_batch_size: usize,
+    pub db_flush_interval_secs: u64,
+    pub watch_max_markets: usize,
+    pub api_requests_per_second: f64,
+    pub paper_trading: bool,
+    pub connect_timeout_secs: u64,
+    pub request_timeout_secs: u64,
+    pub resolve_warning_hours: u64,
+    pub auto_remove_closed: bool,
+    pub alert_webhook_url: Option<String>,
+    pub max_spread: f64,
+    pub arb_fee_buffer: f64,
+    pub trading_fee_pct: f64,
+    pub min_trending_liquidity: f64,
+    pub order_poll_interval_secs: u64,
+    /// Poll active markets more often than quiet ones, between the min and
+    /// max intervals; otherwise every market is polled at the max interval
+    pub adaptive_polling: bool,
+    pub poll_interval_min_secs: u64,
+    pub poll_interval_max_secs: u64,
+    pub ui_theme: String,
 }
 
 impl Config {
+    /// `DATABASE_PATH`, or the default. Also used for onboarding, which runs
+    /// before the full configuration is loaded.
+    pub fn database_path_from_env() -> String {
+        env::var("DATABASE_PATH").unwrap_or_else(|_| DEFAULT_DATABASE_PATH.to_string())
+    }
+
     /// Load configuration from environment variables
     pub fn from_env() -> Result<Self> {
-        Ok(Config {
+        // A malformed list is an error rather than a silent fallback to the
+        // defaults, which the user never wrote
+        let explicit_presets = env::var("ORDER_SIZE_PRESETS")
+            .ok()
+            .map(|v| parse_order_size_presets(&v))
+            .transpose()?;
+        let presets_are_explicit = explicit_presets.is_some();
+
+        let mut config = Config {
             // Only private key is required - CLOB credentials are generated dynamically
             private_key: env::var("POLYMARKET_PK").context("POLYMARKET_PK not found")?,
 
@@ -33,6 +85,8 @@ impl Config {
                 .ok()
                 .and_then(|v| v.parse().ok())
                 .unwrap_or(1.0),
+            order_size_presets: explicit_presets
+                .unwrap_or_else(|| DEFAULT_ORDER_SIZE_PRESETS.to_vec()),
             volume_velocity_threshold: env::var("VOLUME_VELOCITY_THRESHOLD")
                 .ok()
                 .and_then(|v| v.parse().ok())
@@ -43,18 +97,120 @@ impl Config {
                 .unwrap_or(0.3),
 
             // System configuration
-            database_path: env::var("DATABASE_PATH")
-                .unwrap_or_else(|_| "./bot_history.db".to_string()),
+            database_path: Self::database_path_from_env(),
             rpc_url: env::var("RPC_URL").ok(),
-        })
+            portfolio_snapshot_interval_secs: env::var("PORTFOLIO_SNAPSHOT_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            log_retention: env::var("LOG_RETENTION")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000),
+            log_to_file: env::var("LOG_TO_FILE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(true),
+            log_file_path: env::var("LOG_FILE").unwrap_or_else(|_| "./bot.log".to_string()),
+            log_level: env::var("RUST_LOG").unwrap_or_else(|_| "info".to_string()),
+            skip_balance_check: env::var("SKIP_BALANCE_CHECK")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            cancel_on_exit: env::var("CANCEL_ON_EXIT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(true),
+            data_retention_days: env::var("DATA_RETENTION_DAYS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(30),
+            db_batch_size: env::var("DB_BATCH_SIZE")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(50),
+            db_flush_interval_secs: env::var("DB_FLUSH_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            watch_max_markets: env::var("WATCH_MAX_MARKETS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            api_requests_per_second: env::var("API_REQUESTS_PER_SECOND")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5.0),
+            paper_trading: env::var("PAPER_TRADING")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            connect_timeout_secs: env::var("CONNECT_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            request_timeout_secs: env::var("REQUEST_TIMEOUT_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(10),
+            resolve_warning_hours: env::var("RESOLVE_WARNING_HOURS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(24),
+            auto_remove_closed: env::var("AUTO_REMOVE_CLOSED")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(false),
+            alert_webhook_url: env::var("ALERT_WEBHOOK_URL").ok().filter(|v| !v.is_empty()),
+            max_spread: env::var("MAX_SPREAD")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.05),
+            arb_fee_buffer: env::var("ARB_FEE_BUFFER")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.02),
+            trading_fee_pct: env::var("TRADING_FEE_PCT")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(0.04),
+            min_trending_liquidity: env::var("MIN_TRENDING_LIQUIDITY")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(1000.0),
+            order_poll_interval_secs: env::var("ORDER_POLL_INTERVAL_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            adaptive_polling: env::var("ADAPTIVE_POLLING")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(true),
+            poll_interval_min_secs: env::var("POLL_INTERVAL_MIN_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(5),
+            poll_interval_max_secs: env::var("POLL_INTERVAL_MAX_SECS")
+                .ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60),
+            ui_theme: env::var("UI_THEME").unwrap_or_else(|_| "dark".to_string()),
+        };
+
+        // Only presets set explicitly are validated against the order size
+        // limits; defaults outside them are dropped
+        if !presets_are_explicit {
+            config.order_size_presets =
+                default_order_size_presets(config.min_order_size, config.max_order_size);
+        }
+
+        Ok(config)
     }
 
     /// Validate configuration values
     pub fn validate(&self) -> Result<()> {
         // Validate private key format
-        if !self.private_key.starts_with("0x") {
-            anyhow::bail!("Private key must start with '0x'");
-        }
+        validate_private_key(&self.private_key)?;
 
         // Validate order sizes
         if self.min_order_size <= 0.0 {
@@ -65,11 +221,204 @@ impl Config {
             anyhow::bail!("MAX_ORDER_SIZE must be greater than MIN_ORDER_SIZE");
         }
 
+        if self.order_size_presets.len() > 9 {
+            anyhow::bail!("ORDER_SIZE_PRESETS can have at most 9 sizes, one per key 1-9");
+        }
+
+        if let Some(size) = self
+            .order_size_presets
+            .iter()
+            .find(|&&size| !(self.min_order_size..=self.max_order_size).contains(&size))
+        {
+            anyhow::bail!(
+                "ORDER_SIZE_PRESETS size {} is outside MIN_ORDER_SIZE..=MAX_ORDER_SIZE ({}..={})",
+                size,
+                self.min_order_size,
+                self.max_order_size
+            );
+        }
+
+        if self.portfolio_snapshot_interval_secs == 0 {
+            anyhow::bail!("PORTFOLIO_SNAPSHOT_INTERVAL_SECS must be greater than 0");
+        }
+
+        if self.order_poll_interval_secs == 0 {
+            anyhow::bail!("ORDER_POLL_INTERVAL_SECS must be greater than 0");
+        }
+
+        if self.poll_interval_min_secs == 0 {
+            anyhow::bail!("POLL_INTERVAL_MIN_SECS must be greater than 0");
+        }
+
+        if self.poll_interval_max_secs < self.poll_interval_min_secs {
+            anyhow::bail!("POLL_INTERVAL_MAX_SECS must be at least POLL_INTERVAL_MIN_SECS");
+        }
+
+        if self.data_retention_days == 0 {
+            anyhow::bail!("DATA_RETENTION_DAYS must be greater than 0");
+        }
+
+        if self.db_batch_size == 0 {
+            anyhow::bail!("DB_BATCH_SIZE must be greater than 0");
+        }
+
+        if self.db_flush_interval_secs == 0 {
+            anyhow::bail!("DB_FLUSH_INTERVAL_SECS must be greater than 0");
+        }
+
+        if self.watch_max_markets == 0 {
+            anyhow::bail!("WATCH_MAX_MARKETS must be greater than 0");
+        }
+
+        if !(self.api_requests_per_second > 0.0 && self.api_requests_per_second.is_finite()) {
+            anyhow::bail!("API_REQUESTS_PER_SECOND must be greater than 0");
+        }
+
+        if self.connect_timeout_secs == 0 {
+            anyhow::bail!("CONNECT_TIMEOUT_SECS must be greater than 0");
+        }
+
+        if self.request_timeout_secs == 0 {
+            anyhow::bail!("REQUEST_TIMEOUT_SECS must be greater than 0");
+        }
+
+        if tracing_subscriber::EnvFilter::try_new(&self.log_level).is_err() {
+            anyhow::bail!("RUST_LOG is not a valid log filter: {}", self.log_level);
+        }
+
+        if self.log_retention == 0 {
+            anyhow::bail!("LOG_RETENTION must be greater than 0");
+        }
+
         // Validate OBI threshold
         if self.obi_threshold < -1.0 || self.obi_threshold > 1.0 {
             anyhow::bail!("OBI_THRESHOLD must be between -1.0 and 1.0");
         }
 
+        if crate::tui::Theme::from_name(&self.ui_theme).is_none() {
+            anyhow::bail!(
+                "UI_THEME must be one of: {}",
+                crate::tui::Theme::NAMES.join(", ")
+            );
+        }
+
+        if !(self.max_spread > 0.0 && self.max_spread <= 1.0) {
+            anyhow::bail!("MAX_SPREAD must be greater than 0 and at most 1.0");
+        }
+
+        if !(0.0..1.0).contains(&self.arb_fee_buffer) {
+            anyhow::bail!("ARB_FEE_BUFFER must be at least 0 and below 1.0");
+        }
+
+        if !(0.0..1.0).contains(&self.trading_fee_pct) {
+            anyhow::bail!("TRADING_FEE_PCT must be at least 0 and below 1.0");
+        }
+
+        if !(self.min_trending_liquidity >= 0.0 && self.min_trending_liquidity.is_finite()) {
+            anyhow::bail!("MIN_TRENDING_LIQUIDITY must be 0 or greater");
+        }
+
+        if let Some(ref url) = self.alert_webhook_url {
+            if !(url.starts_with("http://") || url.starts_with("https://")) {
+                anyhow::bail!("ALERT_WEBHOOK_URL must be an http(s) URL");
+            }
+        }
+
         Ok(())
     }
 }
+
+/// Parse `ORDER_SIZE_PRESETS`, failing on anything that isn't a list of numbers
+fn parse_order_size_presets(value: &str) -> Result<Vec<f64>> {
+    parse_list(value).with_context(|| {
+        format!(
+            "ORDER_SIZE_PRESETS must be comma-separated sizes, got '{}'",
+            value
+        )
+    })
+}
+
+/// The default presets within `min_order_size..=max_order_size`
+fn default_order_size_presets(min_order_size: f64, max_order_size: f64) -> Vec<f64> {
+    DEFAULT_ORDER_SIZE_PRESETS
+        .into_iter()
+        .filter(|size| (min_order_size..=max_order_size).contains(size))
+        .collect()
+}
+
+/// Parse a comma-separated list such as "5, 25, 100". An empty string is an
+/// empty list; any unparsable entry rejects the whole list.
+fn parse_list<T: std::str::FromStr>(value: &str) -> Option<Vec<T>> {
+    if value.trim().is_empty() {
+        return Some(Vec::new());
+    }
+    value.split(',').map(|v| v.trim().parse().ok()).collect()
+}
+
+/// Check that a private key is `0x` followed by exactly 64 hex digits and is a
+/// valid secp256k1 scalar (non-zero and below the curve order)
+pub fn validate_private_key(key: &str) -> Result<()> {
+    let Some(hex) = key.strip_prefix("0x") else {
+        anyhow::bail!("POLYMARKET_PK must start with '0x'");
+    };
+
+    if hex.len() != 64 {
+        anyhow::bail!(
+            "POLYMARKET_PK must have exactly 64 hex digits after '0x' (found {})",
+            hex.len()
+        );
+    }
+
+    if let Some(c) = hex.chars().find(|c| !c.is_ascii_hexdigit()) {
+        anyhow::bail!("POLYMARKET_PK contains a non-hex character: '{}'", c);
+    }
+
+    if key.parse::<ethers::signers::LocalWallet>().is_err() {
+        anyhow::bail!("POLYMARKET_PK is not a valid secp256k1 private key");
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_private_key() {
+        assert!(validate_private_key(&format!("0x{}", "1".repeat(64))).is_ok());
+        assert!(validate_private_key(&"1".repeat(64)).is_err());
+        assert!(validate_private_key(&format!("0x{}", "1".repeat(63))).is_err());
+        assert!(validate_private_key(&format!("0x{}g", "1".repeat(63))).is_err());
+        // Zero is not a valid scalar
+        assert!(validate_private_key(&format!("0x{}", "0".repeat(64))).is_err());
+    }
+
+    #[test]
+    fn test_parse_list() {
+        assert_eq!(parse_list::<f64>("5, 25,100"), Some(vec![5.0, 25.0, 100.0]));
+        assert_eq!(parse_list::<f64>(" "), Some(Vec::new()));
+        assert_eq!(parse_list::<f64>("5,,25"), None);
+        assert_eq!(parse_list::<f64>("5,lots"), None);
+    }
+
+    #[test]
+    fn test_malformed_presets_are_an_error() {
+        assert_eq!(
+            parse_order_size_presets("10, 20").unwrap(),
+            vec![10.0, 20.0]
+        );
+        let err = parse_order_size_presets("10,abc").unwrap_err();
+        assert!(err.to_string().contains("'10,abc'"), "{}", err);
+    }
+
+    #[test]
+    fn test_default_presets_fit_the_order_size_limits() {
+        assert_eq!(
+            default_order_size_presets(1.0, 100.0),
+            vec![5.0, 25.0, 100.0]
+        );
+        assert_eq!(default_order_size_presets(1.0, 50.0), vec![5.0, 25.0]);
+        assert_eq!(default_order_size_presets(10.0, 20.0), Vec::<f64>::new());
+    }
+}
@@ -1,9 +1,48 @@
+use crate::types::{Portfolio, TradeRecord, VolumeVelocityEvent};
 use anyhow::Result;
 use sqlx::sqlite::{SqliteConnectOptions, SqliteJournalMode, SqlitePoolOptions};
-use sqlx::{Pool, Sqlite};
+use sqlx::{Executor, Pool, Row, Sqlite};
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{watch, Notify};
+use tokio::task::JoinHandle;
 
 pub type DbPool = Pool<Sqlite>;
 
+/// Tables that grow continuously and are pruned by `prune_old_data`
+const PRUNED_TABLES: [&str; 3] = [
+    "orderbook_snapshots",
+    "volume_velocity_events",
+    "portfolio_snapshots",
+];
+
+/// Ordered schema migrations applied on top of `create_schema`. Entry `i` brings
+/// the database to version `i + 1`; append new steps, never edit or reorder old ones.
+const MIGRATIONS: &[&str] = &[
+    // 1: active-order lookups filter on status
+    "CREATE INDEX IF NOT EXISTS idx_orders_status ON orders(status)",
+    // 2: key/value store for TUI state restored on the next launch
+    "CREATE TABLE IF NOT EXISTS ui_state (key TEXT PRIMARY KEY, value TEXT NOT NULL)",
+    // 3, 4: per-market overrides of the spike detection thresholds
+    "ALTER TABLE watched_markets ADD COLUMN velocity_threshold REAL",
+    "ALTER TABLE watched_markets ADD COLUMN obi_threshold REAL",
+    // 5: favorites, the few watched markets actively traded
+    "ALTER TABLE watched_markets ADD COLUMN favorite BOOLEAN NOT NULL DEFAULT 0",
+    // 6: research notes, kept whether or not the market is watched
+    "CREATE TABLE IF NOT EXISTS market_notes (market_id TEXT PRIMARY KEY, note TEXT NOT NULL, updated_at INTEGER NOT NULL)",
+];
+
+/// How often the background retention task prunes old rows
+const PRUNE_INTERVAL: Duration = Duration::from_secs(60 * 60);
+
+/// `WriteBatcher` keeps at most this many batches queued; beyond that the
+/// oldest rows are dropped so an unwritable database can't exhaust memory
+const MAX_PENDING_BATCHES: usize = 100;
+
+/// Failed attempts after which `WriteBatcher` gives up on the queued rows
+const MAX_WRITE_ATTEMPTS: u32 = 3;
+
 /// Initialize the database with WAL mode for high concurrency
 pub async fn init_database(database_path: &str) -> Result<DbPool> {
     // Configure SQLite with WAL mode
@@ -20,6 +59,7 @@ pub async fn init_database(database_path: &str) -> Result<DbPool> {
 
     // Run migrations to create tables
     create_schema(&pool).await?;
+    run_migrations(&pool).await?;
 
     Ok(pool)
 }
@@ -168,5 +208,735 @@ async fn create_schema(pool: &DbPool) -> Result<()> {
     .execute(pool)
     .await?;
 
+    sqlx::query("CREATE INDEX IF NOT EXISTS idx_watched_markets_active ON watched_markets(active)")
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Apply any migrations newer than the version stored in `schema_version`
+async fn run_migrations(pool: &DbPool) -> Result<()> {
+    sqlx::query(
+        r#"
+        CREATE TABLE IF NOT EXISTS schema_version (
+            version INTEGER PRIMARY KEY,
+            applied_at INTEGER NOT NULL
+        )
+        "#,
+    )
+    .execute(pool)
+    .await?;
+
+    let current = schema_version(pool).await?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate() {
+        let version = index as i64 + 1;
+        if version <= current {
+            continue;
+        }
+
+        // Apply the step and record it atomically so a crash can't half-apply it
+        let mut tx = pool.begin().await?;
+        sqlx::query(migration).execute(&mut *tx).await?;
+        sqlx::query("INSERT INTO schema_version (version, applied_at) VALUES (?, ?)")
+            .bind(version)
+            .bind(chrono::Utc::now().timestamp())
+            .execute(&mut *tx)
+            .await?;
+        tx.commit().await?;
+
+        tracing::info!("Applied database migration {}", version);
+    }
+
+    Ok(())
+}
+
+/// Drop every table and recreate the schema from scratch.
+/// Returns the names of the tables that were dropped.
+pub async fn reset_database(pool: &DbPool) -> Result<Vec<String>> {
+    let tables: Vec<(String,)> = sqlx::query_as(
+        "SELECT name FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%' ORDER BY name",
+    )
+    .fetch_all(pool)
+    .await?;
+    let mut tables: Vec<String> = tables.into_iter().map(|(name,)| name).collect();
+
+    // Everything else references `markets`, so it has to go last
+    tables.sort_by_key(|name| name == "markets");
+
+    let mut tx = pool.begin().await?;
+    for table in &tables {
+        sqlx::query(&format!("DROP TABLE IF EXISTS \"{}\"", table))
+            .execute(&mut *tx)
+            .await?;
+    }
+    tx.commit().await?;
+
+    create_schema(pool).await?;
+    run_migrations(pool).await?;
+
+    Ok(tables)
+}
+
+/// Number of orders still `OPEN`
+pub async fn count_open_orders(pool: &DbPool) -> Result<i64> {
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM orders WHERE status = 'OPEN'")
+        .fetch_one(pool)
+        .await?;
+    Ok(count)
+}
+
+/// Number of tables in the database, excluding SQLite's internal ones
+pub async fn count_tables(pool: &DbPool) -> Result<i64> {
+    let (count,): (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM sqlite_master WHERE type = 'table' AND name NOT LIKE 'sqlite_%'",
+    )
+    .fetch_one(pool)
+    .await?;
+    Ok(count)
+}
+
+/// Highest migration version applied to the database (0 if none)
+pub async fn schema_version(pool: &DbPool) -> Result<i64> {
+    let (version,): (i64,) = sqlx::query_as("SELECT COALESCE(MAX(version), 0) FROM schema_version")
+        .fetch_one(pool)
+        .await?;
+    Ok(version)
+}
+
+/// Make sure a `markets` row exists so foreign keys from other tables resolve.
+/// Existing rows are left untouched. Takes any executor so it can run inside
+/// a transaction.
+pub async fn ensure_market<'e, E>(executor: E, market_id: &str) -> Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query(
+        r#"
+        INSERT OR IGNORE INTO markets (market_id, question, created_at)
+        VALUES (?, '', ?)
+        "#,
+    )
+    .bind(market_id)
+    .bind(chrono::Utc::now().timestamp())
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Record a fill in `trades`, timestamped now. Takes any executor so fills can be
+/// written in the same transaction as their order; the market row must exist.
+pub async fn record_trade<'e, E>(
+    executor: E,
+    market_id: &str,
+    trade_id: &str,
+    side: &str,
+    price: f64,
+    size: f64,
+) -> Result<()>
+where
+    E: Executor<'e, Database = Sqlite>,
+{
+    sqlx::query(
+        "INSERT INTO trades (market_id, trade_id, side, price, size, timestamp) VALUES (?, ?, ?, ?, ?, ?)",
+    )
+    .bind(market_id)
+    .bind(trade_id)
+    .bind(side)
+    .bind(price)
+    .bind(size)
+    .bind(chrono::Utc::now().timestamp())
+    .execute(executor)
+    .await?;
+
+    Ok(())
+}
+
+/// Load every recorded trade, oldest first
+pub async fn load_trades(pool: &DbPool) -> Result<Vec<TradeRecord>> {
+    let rows = sqlx::query(
+        "SELECT market_id, trade_id, side, price, size, timestamp FROM trades ORDER BY timestamp, id",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(|row| TradeRecord {
+            market_id: row.get(0),
+            trade_id: row.get(1),
+            side: row.get(2),
+            price: row.get(3),
+            size: row.get(4),
+            timestamp: row.get(5),
+        })
+        .collect())
+}
+
+/// Store a `ui_state` value, replacing any previous one
+pub async fn save_ui_state(pool: &DbPool, key: &str, value: &str) -> Result<()> {
+    sqlx::query("INSERT OR REPLACE INTO ui_state (key, value) VALUES (?, ?)")
+        .bind(key)
+        .bind(value)
+        .execute(pool)
+        .await?;
+    Ok(())
+}
+
+/// Read a `ui_state` value saved by an earlier session
+pub async fn load_ui_state(pool: &DbPool, key: &str) -> Result<Option<String>> {
+    let row: Option<(String,)> = sqlx::query_as("SELECT value FROM ui_state WHERE key = ?")
+        .bind(key)
+        .fetch_optional(pool)
+        .await?;
+    Ok(row.map(|(value,)| value))
+}
+
+/// Record a point on the equity curve in `portfolio_snapshots`
+pub async fn save_portfolio_snapshot(pool: &DbPool, portfolio: &Portfolio) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO portfolio_snapshots
+        (usdc_balance, total_value, realized_pnl, unrealized_pnl, timestamp)
+        VALUES (?, ?, ?, ?, ?)
+        "#,
+    )
+    .bind(portfolio.usdc_balance)
+    .bind(portfolio.total_value)
+    .bind(portfolio.realized_pnl)
+    .bind(portfolio.unrealized_pnl)
+    .bind(chrono::Utc::now().timestamp())
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
+
+/// Load the most recent `limit` portfolio snapshots as (timestamp, total_value),
+/// oldest first so they can be plotted directly
+pub async fn load_equity_curve(pool: &DbPool, limit: i64) -> Result<Vec<(i64, f64)>> {
+    let rows: Vec<(i64, f64)> = sqlx::query_as(
+        r#"
+        SELECT timestamp, total_value
+        FROM portfolio_snapshots
+        ORDER BY timestamp DESC, id DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().rev().collect())
+}
+
+/// Delete snapshot and event rows older than `retention_days`, then `VACUUM`
+/// to give the space back. Returns the number of rows deleted.
+pub async fn prune_old_data(pool: &DbPool, retention_days: u32) -> Result<u64> {
+    let cutoff = chrono::Utc::now().timestamp() - i64::from(retention_days) * 24 * 60 * 60;
+
+    let mut deleted = 0;
+    for table in PRUNED_TABLES {
+        let result = sqlx::query(&format!("DELETE FROM {} WHERE timestamp < ?", table))
+            .bind(cutoff)
+            .execute(pool)
+            .await?;
+        deleted += result.rows_affected();
+    }
+
+    // VACUUM rewrites the whole file, so skip it when nothing changed
+    if deleted > 0 {
+        sqlx::query("VACUUM").execute(pool).await?;
+    }
+
+    Ok(deleted)
+}
+
+/// A high-frequency row queued by `WriteBatcher`
+#[derive(Debug, Clone)]
+pub enum BatchedWrite {
+    OrderBookSnapshot {
+        market_id: String,
+        bids_volume: f64,
+        asks_volume: f64,
+        best_bid: Option<f64>,
+        best_ask: Option<f64>,
+        timestamp: i64,
+    },
+    VelocityEvent(VolumeVelocityEvent),
+}
+
+impl BatchedWrite {
+    fn market_id(&self) -> &str {
+        match self {
+            BatchedWrite::OrderBookSnapshot { market_id, .. } => market_id,
+            BatchedWrite::VelocityEvent(event) => &event.market_id,
+        }
+    }
+
+    /// Insert the row. Its market must already exist, see `ensure_market`.
+    pub async fn insert<'e, E>(&self, executor: E) -> Result<()>
+    where
+        E: Executor<'e, Database = Sqlite>,
+    {
+        match self {
+            BatchedWrite::OrderBookSnapshot {
+                market_id,
+                bids_volume,
+                asks_volume,
+                best_bid,
+                best_ask,
+                timestamp,
+            } => {
+                sqlx::query(
+                    "INSERT INTO orderbook_snapshots (market_id, bids_volume, asks_volume, best_bid, best_ask, timestamp) VALUES (?, ?, ?, ?, ?, ?)",
+                )
+                .bind(market_id)
+                .bind(bids_volume)
+                .bind(asks_volume)
+                .bind(best_bid)
+                .bind(best_ask)
+                .bind(timestamp)
+                .execute(executor)
+                .await?;
+            }
+            BatchedWrite::VelocityEvent(event) => {
+                sqlx::query(
+                    "INSERT INTO volume_velocity_events (market_id, velocity, volume_delta, time_delta, timestamp) VALUES (?, ?, ?, ?, ?)",
+                )
+                .bind(&event.market_id)
+                .bind(event.velocity)
+                .bind(event.volume_delta)
+                .bind(event.time_delta)
+                .bind(event.timestamp)
+                .execute(executor)
+                .await?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(Default)]
+struct PendingWrites {
+    rows: VecDeque<BatchedWrite>,
+    /// Rows dropped because the queue was full, not yet reported
+    dropped: usize,
+    /// Consecutive failed attempts to write `rows`
+    failed_attempts: u32,
+}
+
+/// Buffers order book snapshots and velocity events in memory and writes them
+/// in a single transaction from the task started by `spawn_flusher`, every
+/// flush interval or as soon as `batch_size` rows are queued. Many tiny
+/// transactions would otherwise each pay for an fsync, and callers never wait
+/// on the database.
+pub struct WriteBatcher {
+    pool: DbPool,
+    batch_size: usize,
+    pending: tokio::sync::Mutex<PendingWrites>,
+    batch_ready: Notify,
+}
+
+impl WriteBatcher {
+    pub fn new(pool: DbPool, batch_size: usize) -> Self {
+        Self {
+            pool,
+            batch_size: batch_size.max(1),
+            pending: tokio::sync::Mutex::new(PendingWrites::default()),
+            batch_ready: Notify::new(),
+        }
+    }
+
+    /// Most rows kept queued before the oldest are dropped
+    fn capacity(&self) -> usize {
+        self.batch_size.saturating_mul(MAX_PENDING_BATCHES)
+    }
+
+    /// Queue a row, waking the flusher once a full batch is waiting
+    pub async fn push(&self, write: BatchedWrite) {
+        let mut pending = self.pending.lock().await;
+        if pending.rows.len() >= self.capacity() {
+            pending.rows.pop_front();
+            pending.dropped += 1;
+        }
+        pending.rows.push_back(write);
+        if pending.rows.len() >= self.batch_size {
+            self.batch_ready.notify_one();
+        }
+    }
+
+    /// Rows queued but not yet written
+    pub async fn pending(&self) -> usize {
+        self.pending.lock().await.rows.len()
+    }
+
+    /// Write everything queued now, in one transaction. Returns how many rows
+    /// were written. Rows that fail stay queued for the next attempt, until
+    /// `MAX_WRITE_ATTEMPTS` failures in a row drop them.
+    pub async fn flush(&self) -> Result<usize> {
+        let rows = {
+            let mut pending = self.pending.lock().await;
+            if pending.dropped > 0 {
+                tracing::warn!(
+                    "Write queue full, dropped the {} oldest batched rows",
+                    pending.dropped
+                );
+                pending.dropped = 0;
+            }
+            std::mem::take(&mut pending.rows)
+        };
+        if rows.is_empty() {
+            return Ok(0);
+        }
+
+        // The queue lock isn't held while writing, so pushes never wait on it
+        let result = self.write_rows(&rows).await;
+
+        let mut pending = self.pending.lock().await;
+        match result {
+            Ok(()) => {
+                pending.failed_attempts = 0;
+                Ok(rows.len())
+            }
+            Err(e) => {
+                pending.failed_attempts += 1;
+                if pending.failed_attempts >= MAX_WRITE_ATTEMPTS {
+                    pending.failed_attempts = 0;
+                    tracing::error!(
+                        "Dropping {} batched rows after {} failed writes",
+                        rows.len(),
+                        MAX_WRITE_ATTEMPTS
+                    );
+                } else {
+                    // Ahead of anything queued meanwhile, within the cap
+                    let newer = std::mem::replace(&mut pending.rows, rows);
+                    pending.rows.extend(newer);
+                    let excess = pending.rows.len().saturating_sub(self.capacity());
+                    pending.rows.drain(..excess);
+                    pending.dropped += excess;
+                }
+                Err(e)
+            }
+        }
+    }
+
+    async fn write_rows(&self, rows: &VecDeque<BatchedWrite>) -> Result<()> {
+        let mut tx = self.pool.begin().await?;
+        let mut markets: Vec<&str> = rows.iter().map(BatchedWrite::market_id).collect();
+        markets.sort_unstable();
+        markets.dedup();
+        for market_id in markets {
+            ensure_market(&mut *tx, market_id).await?;
+        }
+        for row in rows {
+            row.insert(&mut *tx).await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    /// Flush every `interval`, and whenever a batch fills, until shutdown,
+    /// then once more so nothing queued is lost
+    pub fn spawn_flusher(
+        self: Arc<Self>,
+        interval: Duration,
+        mut shutdown: watch::Receiver<bool>,
+    ) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {}
+                    _ = self.batch_ready.notified() => {}
+                    _ = shutdown.changed() => break,
+                }
+                if *shutdown.borrow() {
+                    break;
+                }
+                if let Err(e) = self.flush().await {
+                    tracing::warn!("Failed to flush batched writes: {}", e);
+                }
+            }
+            match self.flush().await {
+                Ok(written) => tracing::info!("Flushed {} batched writes on shutdown", written),
+                Err(e) => tracing::error!("Failed to flush batched writes on shutdown: {}", e),
+            }
+        })
+    }
+}
+
+/// Prune old data on startup and then every hour until shutdown
+pub fn spawn_retention_task(
+    pool: DbPool,
+    retention_days: u32,
+    mut shutdown: watch::Receiver<bool>,
+) -> JoinHandle<()> {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(PRUNE_INTERVAL);
+        loop {
+            tokio::select! {
+                _ = ticker.tick() => {
+                    match prune_old_data(&pool, retention_days).await {
+                        Ok(0) => {}
+                        Ok(deleted) => tracing::info!(
+                            "Pruned {} rows older than {} days",
+                            deleted,
+                            retention_days
+                        ),
+                        Err(e) => tracing::warn!("Failed to prune old data: {}", e),
+                    }
+                }
+                _ = shutdown.changed() => break,
+            }
+            if *shutdown.borrow() {
+                break;
+            }
+        }
+        tracing::info!("Database retention task stopped");
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_migrations_are_idempotent() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let path = path.to_str().unwrap();
+
+        let pool = init_database(path).await.unwrap();
+        assert_eq!(
+            schema_version(&pool).await.unwrap(),
+            MIGRATIONS.len() as i64
+        );
+        pool.close().await;
+
+        // Reopening an up-to-date database applies nothing new
+        let pool = init_database(path).await.unwrap();
+        let (applied,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM schema_version")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(applied, MIGRATIONS.len() as i64);
+    }
+
+    #[tokio::test]
+    async fn test_reset_database_clears_all_tables() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let pool = init_database(path.to_str().unwrap()).await.unwrap();
+        ensure_market(&pool, "market_1").await.unwrap();
+        record_trade(&pool, "market_1", "t1", "BUY", 0.5, 1.0)
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO orders (order_id, market_id, side, order_type, price, size, status, created_at, updated_at) VALUES ('o1', 'market_1', 'BUY', 'LIMIT', 0.5, 1, 'OPEN', 0, 0)",
+        )
+        .execute(&pool)
+        .await
+        .unwrap();
+        assert_eq!(count_open_orders(&pool).await.unwrap(), 1);
+
+        let dropped = reset_database(&pool).await.unwrap();
+        assert_eq!(dropped.last().map(String::as_str), Some("markets"));
+        assert!(dropped.iter().any(|t| t == "trades"));
+
+        assert_eq!(count_open_orders(&pool).await.unwrap(), 0);
+        assert!(load_trades(&pool).await.unwrap().is_empty());
+        assert_eq!(
+            schema_version(&pool).await.unwrap(),
+            MIGRATIONS.len() as i64
+        );
+    }
+
+    #[tokio::test]
+    async fn test_ui_state_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let pool = init_database(path.to_str().unwrap()).await.unwrap();
+
+        assert_eq!(load_ui_state(&pool, "tab").await.unwrap(), None);
+        save_ui_state(&pool, "tab", "Markets").await.unwrap();
+        save_ui_state(&pool, "tab", "Logs").await.unwrap();
+        assert_eq!(
+            load_ui_state(&pool, "tab").await.unwrap().as_deref(),
+            Some("Logs")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recorded_trades_group_by_market() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let pool = init_database(path.to_str().unwrap()).await.unwrap();
+        ensure_market(&pool, "market_1").await.unwrap();
+        ensure_market(&pool, "market_2").await.unwrap();
+
+        record_trade(&pool, "market_1", "t1", "BUY", 0.40, 100.0)
+            .await
+            .unwrap();
+        record_trade(&pool, "market_2", "t2", "BUY", 0.70, 5.0)
+            .await
+            .unwrap();
+        record_trade(&pool, "market_1", "t3", "SELL", 0.55, 60.0)
+            .await
+            .unwrap();
+
+        let mut by_market: std::collections::BTreeMap<String, Vec<TradeRecord>> =
+            std::collections::BTreeMap::new();
+        for trade in load_trades(&pool).await.unwrap() {
+            by_market
+                .entry(trade.market_id.clone())
+                .or_default()
+                .push(trade);
+        }
+
+        assert_eq!(by_market.len(), 2);
+        let market_1: Vec<&str> = by_market["market_1"]
+            .iter()
+            .map(|t| t.trade_id.as_str())
+            .collect();
+        assert_eq!(market_1, vec!["t1", "t3"]);
+        assert_eq!(by_market["market_1"][1].side, "SELL");
+        assert_eq!(by_market["market_1"][1].price, 0.55);
+        assert_eq!(by_market["market_2"][0].size, 5.0);
+    }
+
+    fn batched_snapshot(market_id: &str, timestamp: i64) -> BatchedWrite {
+        BatchedWrite::OrderBookSnapshot {
+            market_id: market_id.to_string(),
+            bids_volume: 100.0,
+            asks_volume: 50.0,
+            best_bid: Some(0.49),
+            best_ask: None,
+            timestamp,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_write_batcher_queues_until_flushed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let pool = init_database(path.to_str().unwrap()).await.unwrap();
+        let batcher = WriteBatcher::new(pool.clone(), 3);
+
+        let count = |table: &'static str| {
+            let pool = pool.clone();
+            async move {
+                let (rows,): (i64,) = sqlx::query_as(&format!("SELECT COUNT(*) FROM {}", table))
+                    .fetch_one(&pool)
+                    .await
+                    .unwrap();
+                rows
+            }
+        };
+
+        batcher.push(batched_snapshot("market_1", 1_000)).await;
+        batcher
+            .push(BatchedWrite::VelocityEvent(VolumeVelocityEvent {
+                market_id: "market_2".to_string(),
+                velocity: 500.0,
+                volume_delta: 1_000.0,
+                time_delta: 2.0,
+                timestamp: 1_000,
+            }))
+            .await;
+        batcher.push(batched_snapshot("market_1", 1_001)).await;
+
+        // Pushing never writes; a full batch only wakes the flusher
+        assert_eq!(batcher.pending().await, 3);
+        assert_eq!(count("orderbook_snapshots").await, 0);
+        tokio::time::timeout(Duration::from_secs(1), batcher.batch_ready.notified())
+            .await
+            .expect("a full batch should wake the flusher");
+
+        // Markets are created on the fly, so unknown IDs don't trip foreign keys
+        assert_eq!(batcher.flush().await.unwrap(), 3);
+        assert_eq!(batcher.flush().await.unwrap(), 0);
+        assert_eq!(count("orderbook_snapshots").await, 2);
+        assert_eq!(count("volume_velocity_events").await, 1);
+        assert_eq!(count("markets").await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_write_batcher_bounds_the_queue() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let pool = init_database(path.to_str().unwrap()).await.unwrap();
+        let batcher = WriteBatcher::new(pool.clone(), 1);
+
+        // The oldest rows make room once the queue is full
+        let capacity = MAX_PENDING_BATCHES as i64;
+        for timestamp in 0..capacity + 5 {
+            batcher.push(batched_snapshot("market_1", timestamp)).await;
+        }
+        assert_eq!(batcher.pending().await, MAX_PENDING_BATCHES);
+        assert_eq!(batcher.flush().await.unwrap(), MAX_PENDING_BATCHES);
+        let (oldest,): (i64,) = sqlx::query_as("SELECT MIN(timestamp) FROM orderbook_snapshots")
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(oldest, 5);
+
+        // Rows that can't be written are retried, then given up on
+        batcher.push(batched_snapshot("market_1", 1_000)).await;
+        pool.close().await;
+        for _ in 1..MAX_WRITE_ATTEMPTS {
+            assert!(batcher.flush().await.is_err());
+            assert_eq!(batcher.pending().await, 1);
+        }
+        assert!(batcher.flush().await.is_err());
+        assert_eq!(batcher.pending().await, 0);
+    }
+
+    #[tokio::test]
+    async fn test_prune_old_data_keeps_recent_rows() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let pool = init_database(path.to_str().unwrap()).await.unwrap();
+        ensure_market(&pool, "market_1").await.unwrap();
+
+        let now = chrono::Utc::now().timestamp();
+        let old = now - 40 * 24 * 60 * 60;
+
+        for timestamp in [old, now] {
+            sqlx::query(
+                "INSERT INTO orderbook_snapshots (market_id, bids_volume, asks_volume, timestamp) VALUES ('market_1', 1, 1, ?)",
+            )
+            .bind(timestamp)
+            .execute(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO volume_velocity_events (market_id, velocity, volume_delta, time_delta, timestamp) VALUES ('market_1', 1, 1, 1, ?)",
+            )
+            .bind(timestamp)
+            .execute(&pool)
+            .await
+            .unwrap();
+            sqlx::query(
+                "INSERT INTO portfolio_snapshots (usdc_balance, total_value, timestamp) VALUES (0, 0, ?)",
+            )
+            .bind(timestamp)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        assert_eq!(prune_old_data(&pool, 30).await.unwrap(), 3);
+
+        for table in PRUNED_TABLES {
+            let remaining: Vec<(i64,)> =
+                sqlx::query_as(&format!("SELECT timestamp FROM {}", table))
+                    .fetch_all(&pool)
+                    .await
+                    .unwrap();
+            assert_eq!(remaining, vec![(now,)], "{}", table);
+        }
+    }
+}
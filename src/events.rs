@@ -0,0 +1,41 @@
+//! Internal event bus
+//!
+//! The spike detector and execution engine publish `BotEvent`s on a broadcast
+//! channel; the TUI and the webhook notifier subscribe to it. Producers don't
+//! know who is listening, and publishing with no subscribers is not an error.
+
+use crate::types::VolumeVelocityEvent;
+use tokio::sync::broadcast;
+
+/// Events buffered per subscriber before the slowest one starts missing some
+pub const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// Something that happened in the bot that other parts may want to react to
+#[derive(Debug, Clone)]
+pub enum BotEvent {
+    /// A volume velocity spike was detected
+    Spike(VolumeVelocityEvent),
+    /// An order was placed, with its ID
+    OrderPlaced(String),
+    /// An order filled in full at `price`
+    OrderFilled { order_id: String, price: f64 },
+    /// A resting order filled further but not completely
+    OrderPartiallyFilled {
+        order_id: String,
+        filled_size: f64,
+        size: f64,
+    },
+    /// An open order was cancelled, with its ID
+    OrderCancelled(String),
+    /// Panic mode cancelled this many open orders (and paused trading)
+    Panic(usize),
+    /// Trading was paused, by the user or by panic mode
+    Paused,
+    /// Trading was resumed
+    Resumed,
+}
+
+/// Create the sending half of a new event bus; subscribe with `Sender::subscribe`
+pub fn event_channel() -> broadcast::Sender<BotEvent> {
+    broadcast::channel(EVENT_CHANNEL_CAPACITY).0
+}
@@ -1,116 +1,1230 @@
-use crate::types::{BotState, OrderInfo, Portfolio};
+use crate::database::DbPool;
+use crate::events::{event_channel, BotEvent};
+use crate::markets::OrderBook;
+use crate::types::{BotState, OrderInfo, OrderType, Portfolio};
 use anyhow::Result;
+use sqlx::Row;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::{Duration, Instant};
+use tokio::sync::{broadcast, watch, RwLock};
+use tokio::task::JoinHandle;
+
+/// Disambiguates order IDs generated within the same millisecond
+static ORDER_SEQUENCE: AtomicU64 = AtomicU64::new(0);
 
 /// Execution engine for placing and managing orders
 /// This is a placeholder that will be integrated with polymarket-hft
 pub struct ExecutionEngine {
     state: Arc<RwLock<BotState>>,
     config: crate::config::Config,
+    db: DbPool,
+    started_at: Instant,
+    orders_placed: AtomicU64,
+    events: broadcast::Sender<BotEvent>,
 }
 
 impl ExecutionEngine {
-    pub fn new(config: crate::config::Config) -> Self {
+    pub fn new(config: crate::config::Config, db: DbPool) -> Self {
         Self {
             state: Arc::new(RwLock::new(BotState::default())),
             config,
+            db,
+            started_at: Instant::now(),
+            orders_placed: AtomicU64::new(0),
+            events: event_channel(),
         }
     }
-    
-    /// Place a market order
+
+    /// The event bus orders and pause changes are published on. Other
+    /// producers publish on it too, and consumers `subscribe` to it.
+    pub fn events(&self) -> broadcast::Sender<BotEvent> {
+        self.events.clone()
+    }
+
+    /// Publish an event; having no subscribers is fine
+    fn publish(&self, event: BotEvent) {
+        let _ = self.events.send(event);
+    }
+
+    /// Configuration the engine validates orders against
+    pub fn config(&self) -> &crate::config::Config {
+        &self.config
+    }
+
+    /// Time since the engine was created at launch
+    pub fn uptime(&self) -> Duration {
+        self.started_at.elapsed()
+    }
+
+    /// Number of orders placed since launch
+    pub fn orders_placed(&self) -> u64 {
+        self.orders_placed.load(Ordering::Relaxed)
+    }
+
+    /// Whether orders are simulated locally instead of sent to the CLOB
+    pub fn is_paper_trading(&self) -> bool {
+        self.config.paper_trading
+    }
+
+    /// Place an order and record it in the `orders` table as OPEN
+    ///
+    /// Limit orders require a price. For market orders the price is the worst
+    /// acceptable fill; when omitted the order accepts any price.
+    ///
+    /// Orders are only accepted on markets in the monitored list, which the
+    /// interfaces keep in sync with the watchlist.
+    ///
+    /// In paper trading mode the order is never submitted. Market orders fill
+    /// in full immediately at the latest known price; limit orders rest until
+    /// `poll_open_orders` sees the book cross them. Fills are recorded in `trades`.
     pub async fn place_order(
         &self,
         market_id: &str,
         side: &str,
+        order_type: OrderType,
         size: f64,
-        price: f64,
+        price: Option<f64>,
     ) -> Result<String> {
         // Check if bot is paused
         let state = self.state.read().await;
         if state.is_paused {
             anyhow::bail!("Bot is paused - order rejected");
         }
+        if !state.monitored_markets.iter().any(|m| m == market_id) {
+            anyhow::bail!(
+                "Market {} is not being monitored - join it first",
+                market_id
+            );
+        }
         drop(state);
-        
+
         // Validate order size
         if size < self.config.min_order_size {
             anyhow::bail!("Order size below minimum: {}", self.config.min_order_size);
         }
-        
+
         if size > self.config.max_order_size {
             anyhow::bail!("Order size exceeds maximum: {}", self.config.max_order_size);
         }
-        
+
+        // Resolve the price to store for this order type
+        let price = match (order_type, price) {
+            (OrderType::Limit, None) => anyhow::bail!("Limit orders require a price"),
+            (_, Some(p)) => p,
+            // No bound on a market order: accept the whole 0-1 range
+            (OrderType::Market, None) => {
+                if side.eq_ignore_ascii_case("SELL") {
+                    0.0
+                } else {
+                    1.0
+                }
+            }
+        };
+
+        if !(0.0..=1.0).contains(&price) {
+            anyhow::bail!("Price must be between 0.00 and 1.00, got {}", price);
+        }
+
+        // Paper market orders fill at once; paper limit orders rest until the
+        // order poller sees the book cross them
+        let fill_price = match (self.config.paper_trading, order_type) {
+            (true, OrderType::Market) => {
+                let Some(latest) = self.latest_price(market_id).await? else {
+                    anyhow::bail!(
+                        "No recent price for market {} - can't fill a paper market order",
+                        market_id
+                    );
+                };
+                // Never fill past the worst price the order accepts
+                Some(if side.eq_ignore_ascii_case("SELL") {
+                    latest.max(price)
+                } else {
+                    latest.min(price)
+                })
+            }
+            _ => None,
+        };
+
         // TODO: Integrate with polymarket-hft::client::clob
-        // For now, return a mock order ID
-        let order_id = format!("order_{}", chrono::Utc::now().timestamp_millis());
-        
+        // For now, generate a mock order ID and persist the order locally
+        let prefix = if self.config.paper_trading {
+            "paper"
+        } else {
+            "order"
+        };
+        let order_id = format!(
+            "{}_{}_{}",
+            prefix,
+            chrono::Utc::now().timestamp_millis(),
+            ORDER_SEQUENCE.fetch_add(1, Ordering::Relaxed)
+        );
+        let side = side.to_uppercase();
+        let now = chrono::Utc::now().timestamp();
+        let (status, filled_size) = match fill_price {
+            Some(_) => ("FILLED", size),
+            None => ("OPEN", 0.0),
+        };
+
+        crate::database::ensure_market(&self.db, market_id).await?;
+
+        let mut tx = self.db.begin().await?;
+        sqlx::query(
+            r#"
+            INSERT INTO orders
+            (order_id, market_id, side, order_type, price, size, filled_size, status, created_at, updated_at)
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(&order_id)
+        .bind(market_id)
+        .bind(&side)
+        .bind(order_type.as_str())
+        .bind(price)
+        .bind(size)
+        .bind(filled_size)
+        .bind(status)
+        .bind(now)
+        .bind(now)
+        .execute(&mut *tx)
+        .await?;
+
+        if let Some(fill_price) = fill_price {
+            crate::database::record_trade(&mut *tx, market_id, &order_id, &side, fill_price, size)
+                .await?;
+
+            tracing::info!("📄 Paper fill: {} {} @ {}", side, size, fill_price);
+        }
+        tx.commit().await?;
+        self.orders_placed.fetch_add(1, Ordering::Relaxed);
+
         // Update state
         let mut state = self.state.write().await;
         state.last_order_id = Some(order_id.clone());
-        
+
         tracing::info!(
-            "📝 Placed {} order on market {} - Size: {} @ Price: {}",
+            "📝 Placed {} {} order on market {} - Size: {} @ Price: {}",
+            order_type,
             side,
             market_id,
             size,
             price
         );
-        
+        self.publish(BotEvent::OrderPlaced(order_id.clone()));
+        if let Some(price) = fill_price {
+            self.publish(BotEvent::OrderFilled {
+                order_id: order_id.clone(),
+                price,
+            });
+        }
+
         Ok(order_id)
     }
-    
+
+    /// Liquidity pre-check against `MAX_SPREAD`, run before placing an order.
+    ///
+    /// Market orders are rejected when the spread is wider than the limit or one
+    /// side of the book is empty; the error includes the best bid/ask. Limit
+    /// orders rest at their own price, so they only get a warning (`Ok(Some)`).
+    pub fn check_spread(&self, order_type: OrderType, book: &OrderBook) -> Result<Option<String>> {
+        let quote = |p: Option<f64>| p.map_or("none".to_string(), |p| format!("{:.3}", p));
+        let (bid, ask) = (quote(book.best_bid()), quote(book.best_ask()));
+        let max_spread = self.config.max_spread;
+
+        let problem = match book.spread() {
+            Some(spread) if spread <= max_spread => return Ok(None),
+            Some(spread) => format!(
+                "spread {:.3} exceeds MAX_SPREAD {:.3} (best bid {}, best ask {})",
+                spread, max_spread, bid, ask
+            ),
+            None => format!(
+                "order book is one-sided (best bid {}, best ask {})",
+                bid, ask
+            ),
+        };
+
+        match order_type {
+            OrderType::Market => anyhow::bail!("Thin market: {}", problem),
+            OrderType::Limit => Ok(Some(format!("Wide market: {}", problem))),
+        }
+    }
+
     /// Cancel all open orders (PANIC mode)
     pub async fn cancel_all_orders(&self) -> Result<usize> {
         // TODO: Integrate with polymarket-hft to cancel all orders
         tracing::warn!("🚨 PANIC: Cancelling all orders");
-        
+
         // Pause the bot
         let mut state = self.state.write().await;
         state.is_paused = true;
-        
-        Ok(0) // Return number of cancelled orders
+        self.publish(BotEvent::Paused);
+
+        let result = sqlx::query(
+            "UPDATE orders SET status = 'CANCELLED', updated_at = ? WHERE status = 'OPEN'",
+        )
+        .bind(chrono::Utc::now().timestamp())
+        .execute(&self.db)
+        .await?;
+
+        let cancelled = result.rows_affected() as usize;
+        self.publish(BotEvent::Panic(cancelled));
+        Ok(cancelled)
+    }
+
+    /// Cancel a single open order. Returns `false` if no open order has that ID.
+    pub async fn cancel_order(&self, order_id: &str) -> Result<bool> {
+        // TODO: Integrate with polymarket-hft to cancel the order on the exchange
+        let result = sqlx::query(
+            "UPDATE orders SET status = 'CANCELLED', updated_at = ? WHERE order_id = ? AND status = 'OPEN'",
+        )
+        .bind(chrono::Utc::now().timestamp())
+        .bind(order_id)
+        .execute(&self.db)
+        .await?;
+
+        if result.rows_affected() == 0 {
+            tracing::warn!("Cancel requested for unknown order {}", order_id);
+            return Ok(false);
+        }
+
+        tracing::info!("Cancelled order {}", order_id);
+        self.publish(BotEvent::OrderCancelled(order_id.to_string()));
+        Ok(true)
     }
-    
-    /// Get list of active orders
+
+    /// Get list of active orders, newest first
     pub async fn get_active_orders(&self) -> Result<Vec<OrderInfo>> {
         // TODO: Integrate with polymarket-hft to fetch active orders
-        Ok(Vec::new())
+        let rows = sqlx::query(
+            r#"
+            SELECT order_id, market_id, side, price, size, filled_size, status, created_at
+            FROM orders
+            WHERE status = 'OPEN'
+            ORDER BY created_at DESC, id DESC
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let orders = rows
+            .into_iter()
+            .map(|row| OrderInfo {
+                order_id: row.get(0),
+                market_id: row.get(1),
+                side: row.get(2),
+                price: row.get(3),
+                size: row.get(4),
+                filled_size: row.get(5),
+                status: row.get(6),
+                created_at: row.get(7),
+            })
+            .collect();
+
+        Ok(orders)
     }
-    
+
     /// Get current portfolio state
+    ///
+    /// P&L is computed from the `trades` table using average cost basis per market.
+    /// Realized P&L is net of `trading_fee_pct`, charged on both legs of a trade
+    /// once the position is closed; unrealized P&L is before fees.
+    /// Open positions are valued at the latest order book mid price, falling back
+    /// to the last traded price when no snapshot exists.
     pub async fn get_portfolio(&self) -> Result<Portfolio> {
-        // TODO: Integrate with polymarket-hft::client::data
+        // TODO: Integrate with polymarket-hft::client::data for the USDC balance
+        let usdc_balance = 0.0;
+
+        let rows = sqlx::query(
+            r#"
+            SELECT market_id, side, price, size
+            FROM trades
+            ORDER BY timestamp ASC, id ASC
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let fee_pct = self.config.trading_fee_pct;
+        let mut positions: HashMap<String, Position> = HashMap::new();
+        for row in rows {
+            let market_id: String = row.get(0);
+            let side: String = row.get(1);
+            let price: f64 = row.get(2);
+            let size: f64 = row.get(3);
+            positions
+                .entry(market_id)
+                .or_default()
+                .apply_fill(&side, price, size, fee_pct);
+        }
+
+        let mut realized_pnl = 0.0;
+        let mut fees_paid = 0.0;
+        let mut unrealized_pnl = 0.0;
+        let mut positions_value = 0.0;
+
+        for (market_id, position) in &positions {
+            realized_pnl += position.realized_pnl;
+            fees_paid += position.fees_paid;
+
+            if position.quantity > 0.0 {
+                let mark = self
+                    .latest_price(market_id)
+                    .await?
+                    .unwrap_or(position.last_price);
+                unrealized_pnl += position.unrealized_pnl(mark);
+                positions_value += position.quantity * mark;
+            }
+        }
+
         Ok(Portfolio {
-            usdc_balance: 0.0,
-            total_value: 0.0,
-            realized_pnl: 0.0,
-            unrealized_pnl: 0.0,
+            usdc_balance,
+            total_value: usdc_balance + positions_value,
+            realized_pnl,
+            unrealized_pnl,
+            fees_paid,
+        })
+    }
+
+    /// Spawn a background task that records a portfolio snapshot every
+    /// `portfolio_snapshot_interval_secs`. The task exits when `shutdown` flips to true.
+    pub fn spawn_snapshot_writer(
+        self: &Arc<Self>,
+        mut shutdown: watch::Receiver<bool>,
+    ) -> JoinHandle<()> {
+        let engine = Arc::clone(self);
+        let period = Duration::from_secs(engine.config.portfolio_snapshot_interval_secs);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        if let Err(e) = engine.write_portfolio_snapshot().await {
+                            tracing::warn!("Failed to write portfolio snapshot: {}", e);
+                        }
+                    }
+                    _ = shutdown.changed() => break,
+                }
+                if *shutdown.borrow() {
+                    break;
+                }
+            }
+            tracing::info!("Portfolio snapshot writer stopped");
         })
     }
-    
+
+    /// Spawn a background task that polls open orders for fills every
+    /// `order_poll_interval_secs`. The task exits when `shutdown` flips to true.
+    pub fn spawn_order_poller(
+        self: &Arc<Self>,
+        mut shutdown: watch::Receiver<bool>,
+    ) -> JoinHandle<()> {
+        let engine = Arc::clone(self);
+        let period = Duration::from_secs(engine.config.order_poll_interval_secs);
+
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                tokio::select! {
+                    _ = ticker.tick() => {
+                        if let Err(e) = engine.poll_open_orders().await {
+                            tracing::warn!("Failed to poll order status: {}", e);
+                        }
+                    }
+                    _ = shutdown.changed() => break,
+                }
+                if *shutdown.borrow() {
+                    break;
+                }
+            }
+            tracing::info!("Order status poller stopped");
+        })
+    }
+
+    /// Bring every open order's `filled_size` and `status` up to date,
+    /// recording the new fills in `trades`. Filled and cancelled orders are
+    /// terminal and never polled again. Returns the number of orders updated.
+    ///
+    /// Fills are only simulated in paper trading; live orders are left as they are.
+    pub async fn poll_open_orders(&self) -> Result<usize> {
+        // TODO: Query live order status from polymarket-hft::client::clob
+        if !self.config.paper_trading {
+            return Ok(0);
+        }
+
+        let rows = sqlx::query(
+            r#"
+            SELECT order_id, market_id, side, price, size, filled_size, updated_at
+            FROM orders
+            WHERE status = 'OPEN'
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        let mut updated = 0;
+        for row in rows {
+            let order = RestingOrder {
+                order_id: row.get(0),
+                market_id: row.get(1),
+                side: row.get(2),
+                price: row.get(3),
+                size: row.get(4),
+                filled_size: row.get(5),
+                updated_at: row.get(6),
+            };
+            let filled_size = self.fetch_filled_size(&order).await?.min(order.size);
+            if filled_size > order.filled_size {
+                self.record_fill(&order, filled_size).await?;
+                updated += 1;
+            }
+        }
+        Ok(updated)
+    }
+
+    /// Simulated total filled size of a resting paper order.
+    ///
+    /// The order is matched against the newest order book snapshot taken since
+    /// it last changed: a buy fills once the best ask is at or below its price,
+    /// a sell once the best bid is at or above it, up to the volume on the
+    /// other side of the book less what the order already filled.
+    async fn fetch_filled_size(&self, order: &RestingOrder) -> Result<f64> {
+        let row = sqlx::query(
+            r#"
+            SELECT best_bid, best_ask, bids_volume, asks_volume
+            FROM orderbook_snapshots
+            WHERE market_id = ? AND timestamp > ?
+            ORDER BY timestamp DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(&order.market_id)
+        .bind(order.updated_at)
+        .fetch_optional(&self.db)
+        .await?;
+        let Some(row) = row else {
+            return Ok(order.filled_size);
+        };
+
+        let best_bid: Option<f64> = row.get(0);
+        let best_ask: Option<f64> = row.get(1);
+        let available = if order.side.eq_ignore_ascii_case("BUY") {
+            match best_ask {
+                Some(ask) if ask <= order.price => row.get::<f64, _>(3),
+                _ => 0.0,
+            }
+        } else {
+            match best_bid {
+                Some(bid) if bid >= order.price => row.get::<f64, _>(2),
+                _ => 0.0,
+            }
+        };
+        Ok(order.filled_size + (available - order.filled_size).max(0.0))
+    }
+
+    /// Record an order's progress to `filled_size`: the new fill goes into
+    /// `trades` at the order's price, and the order is FILLED once complete
+    async fn record_fill(&self, order: &RestingOrder, filled_size: f64) -> Result<()> {
+        let complete = filled_size >= order.size;
+        let status = if complete { "FILLED" } else { "OPEN" };
+
+        let mut tx = self.db.begin().await?;
+        let result = sqlx::query(
+            "UPDATE orders SET filled_size = ?, status = ?, updated_at = ? WHERE order_id = ? AND status = 'OPEN'",
+        )
+        .bind(filled_size)
+        .bind(status)
+        .bind(chrono::Utc::now().timestamp())
+        .bind(&order.order_id)
+        .execute(&mut *tx)
+        .await?;
+        // Cancelled while we were polling
+        if result.rows_affected() == 0 {
+            return Ok(());
+        }
+
+        crate::database::record_trade(
+            &mut *tx,
+            &order.market_id,
+            &order.order_id,
+            &order.side,
+            order.price,
+            filled_size - order.filled_size,
+        )
+        .await?;
+        tx.commit().await?;
+
+        tracing::info!(
+            "Order {} filled {}/{} @ {}",
+            order.order_id,
+            filled_size,
+            order.size,
+            order.price
+        );
+        self.publish(if complete {
+            BotEvent::OrderFilled {
+                order_id: order.order_id.clone(),
+                price: order.price,
+            }
+        } else {
+            BotEvent::OrderPartiallyFilled {
+                order_id: order.order_id.clone(),
+                filled_size,
+                size: order.size,
+            }
+        });
+        Ok(())
+    }
+
+    /// Compute the current portfolio and store it in `portfolio_snapshots`
+    pub async fn write_portfolio_snapshot(&self) -> Result<()> {
+        let portfolio = self.get_portfolio().await?;
+        crate::database::save_portfolio_snapshot(&self.db, &portfolio).await
+    }
+
+    /// Latest mid price for a market from the order book snapshots
+    async fn latest_price(&self, market_id: &str) -> Result<Option<f64>> {
+        let row = sqlx::query(
+            r#"
+            SELECT best_bid, best_ask
+            FROM orderbook_snapshots
+            WHERE market_id = ?
+            ORDER BY timestamp DESC, id DESC
+            LIMIT 1
+            "#,
+        )
+        .bind(market_id)
+        .fetch_optional(&self.db)
+        .await?;
+
+        Ok(row.and_then(|row| {
+            let best_bid: Option<f64> = row.get(0);
+            let best_ask: Option<f64> = row.get(1);
+            match (best_bid, best_ask) {
+                (Some(bid), Some(ask)) => Some((bid + ask) / 2.0),
+                (Some(price), None) | (None, Some(price)) => Some(price),
+                (None, None) => None,
+            }
+        }))
+    }
+
     /// Pause the bot (cancel-only mode)
     pub async fn pause(&self) {
         let mut state = self.state.write().await;
         state.is_paused = true;
         tracing::info!("⏸️  Bot paused - entering cancel-only mode");
+        self.publish(BotEvent::Paused);
     }
-    
+
     /// Resume normal trading
     pub async fn resume(&self) {
         let mut state = self.state.write().await;
         state.is_paused = false;
         tracing::info!("▶️  Bot resumed - trading enabled");
+        self.publish(BotEvent::Resumed);
     }
-    
+
     /// Check if bot is paused
     pub async fn is_paused(&self) -> bool {
         self.state.read().await.is_paused
     }
-    
+
     /// Get the last order ID
     pub async fn get_last_order_id(&self) -> Option<String> {
         self.state.read().await.last_order_id.clone()
     }
+
+    /// Markets orders may be placed on, in the order they were joined
+    pub async fn monitored_markets(&self) -> Vec<String> {
+        self.state.read().await.monitored_markets.clone()
+    }
+
+    /// Replace the monitored list, e.g. with the watchlist loaded at startup
+    pub async fn set_monitored_markets(&self, market_ids: Vec<String>) {
+        self.state.write().await.monitored_markets = market_ids;
+    }
+
+    /// Start monitoring a market; already monitored markets are left as-is
+    pub async fn add_monitored_market(&self, market_id: &str) {
+        let mut state = self.state.write().await;
+        if !state.monitored_markets.iter().any(|m| m == market_id) {
+            state.monitored_markets.push(market_id.to_string());
+        }
+    }
+
+    /// Stop monitoring a market
+    pub async fn remove_monitored_market(&self, market_id: &str) {
+        self.state
+            .write()
+            .await
+            .monitored_markets
+            .retain(|m| m != market_id);
+    }
+}
+
+/// An OPEN order as the status poller sees it
+struct RestingOrder {
+    order_id: String,
+    market_id: String,
+    side: String,
+    price: f64,
+    size: f64,
+    filled_size: f64,
+    updated_at: i64,
+}
+
+/// Fee charged on a fill of `size` shares at `price`, as a fraction of its notional
+pub fn trading_fee(price: f64, size: f64, fee_pct: f64) -> f64 {
+    price * size * fee_pct
+}
+
+/// Running position in a single market, tracked with average cost basis
+#[derive(Debug, Clone, Default)]
+struct Position {
+    quantity: f64,
+    average_cost: f64,
+    /// Net of fees
+    realized_pnl: f64,
+    /// Fees of closed trades, both legs
+    fees_paid: f64,
+    /// Buy fees of the shares still held, realized when they are sold
+    open_fees: f64,
+    last_price: f64,
+}
+
+impl Position {
+    fn apply_fill(&mut self, side: &str, price: f64, size: f64, fee_pct: f64) {
+        self.last_price = price;
+
+        if side.eq_ignore_ascii_case("BUY") {
+            let cost = self.quantity * self.average_cost + size * price;
+            self.quantity += size;
+            self.average_cost = cost / self.quantity;
+            self.open_fees += trading_fee(price, size, fee_pct);
+        } else {
+            // Shares can't be sold short, so only the held quantity realizes P&L
+            let closed = size.min(self.quantity);
+            if closed <= 0.0 {
+                return;
+            }
+            let fees =
+                self.open_fees * closed / self.quantity + trading_fee(price, closed, fee_pct);
+            self.open_fees -= self.open_fees * closed / self.quantity;
+            self.fees_paid += fees;
+            self.realized_pnl += (price - self.average_cost) * closed - fees;
+            self.quantity -= closed;
+            if self.quantity <= 0.0 {
+                self.quantity = 0.0;
+                self.average_cost = 0.0;
+                self.open_fees = 0.0;
+            }
+        }
+    }
+
+    fn unrealized_pnl(&self, mark_price: f64) -> f64 {
+        (mark_price - self.average_cost) * self.quantity
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    pub(crate) fn test_config() -> Config {
+        Config {
+            private_key: format!("0x{}", "1".repeat(64)),
+            max_order_size: 100.0,
+            min_order_size: 1.0,
+            order_size_presets: vec![5.0, 25.0, 100.0],
+            volume_velocity_threshold: 1000.0,
+            obi_threshold: 0.3,
+            database_path: String::new(),
+            rpc_url: None,
+            portfolio_snapshot_interval_secs: 60,
+            log_retention: 1000,
+            log_to_file: false,
+            log_file_path: String::new(),
+            log_level: "info".to_string(),
+            skip_balance_check: true,
+            cancel_on_exit: true,
+            data_retention_days: 30,
+            db_batch_size: 50,
+            db_flush_interval_secs: 5,
+            watch_max_markets: 5,
+            api_requests_per_second: 5.0,
+            paper_trading: false,
+            connect_timeout_secs: 10,
+            request_timeout_secs: 10,
+            resolve_warning_hours: 24,
+            auto_remove_closed: false,
+            alert_webhook_url: None,
+            max_spread: 0.05,
+            arb_fee_buffer: 0.02,
+            trading_fee_pct: 0.0,
+            min_trending_liquidity: 0.0,
+            order_poll_interval_secs: 5,
+            adaptive_polling: true,
+            poll_interval_min_secs: 5,
+            poll_interval_max_secs: 60,
+            ui_theme: "dark".to_string(),
+        }
+    }
+
+    async fn test_engine() -> (ExecutionEngine, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let db = crate::database::init_database(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let engine = ExecutionEngine::new(test_config(), db);
+        engine.add_monitored_market("market_1").await;
+        (engine, dir)
+    }
+
+    #[tokio::test]
+    async fn test_placed_order_is_active() {
+        let (engine, _dir) = test_engine().await;
+
+        let order_id = engine
+            .place_order("market_1", "buy", OrderType::Limit, 10.0, Some(0.45))
+            .await
+            .unwrap();
+
+        let orders = engine.get_active_orders().await.unwrap();
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_id, order_id);
+        assert_eq!(orders[0].market_id, "market_1");
+        assert_eq!(orders[0].side, "BUY");
+        assert_eq!(orders[0].status, "OPEN");
+        assert_eq!(orders[0].price, 0.45);
+        assert_eq!(engine.orders_placed(), 1);
+
+        // Orders placed back to back within a millisecond still get unique IDs
+        let second = engine
+            .place_order("market_1", "buy", OrderType::Limit, 10.0, Some(0.45))
+            .await
+            .unwrap();
+        assert_ne!(second, order_id);
+        assert_eq!(engine.get_active_orders().await.unwrap().len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_engine_publishes_events() {
+        let (engine, _dir) = test_engine().await;
+        let mut events = engine.events().subscribe();
+
+        let order_id = engine
+            .place_order("market_1", "BUY", OrderType::Limit, 10.0, Some(0.45))
+            .await
+            .unwrap();
+        engine.cancel_order(&order_id).await.unwrap();
+        engine.pause().await;
+        engine.resume().await;
+
+        assert!(matches!(events.try_recv(), Ok(BotEvent::OrderPlaced(id)) if id == order_id));
+        assert!(matches!(events.try_recv(), Ok(BotEvent::OrderCancelled(id)) if id == order_id));
+        assert!(matches!(events.try_recv(), Ok(BotEvent::Paused)));
+        assert!(matches!(events.try_recv(), Ok(BotEvent::Resumed)));
+        assert!(events.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_order_rejected_on_unmonitored_market() {
+        let (engine, _dir) = test_engine().await;
+
+        let result = engine
+            .place_order("market_2", "BUY", OrderType::Limit, 10.0, Some(0.45))
+            .await;
+        assert!(result.is_err());
+
+        engine.add_monitored_market("market_2").await;
+        engine.add_monitored_market("market_2").await;
+        assert_eq!(
+            engine.monitored_markets().await,
+            vec!["market_1", "market_2"]
+        );
+        assert!(engine
+            .place_order("market_2", "BUY", OrderType::Limit, 10.0, Some(0.45))
+            .await
+            .is_ok());
+
+        engine.remove_monitored_market("market_1").await;
+        assert!(engine
+            .place_order("market_1", "BUY", OrderType::Limit, 10.0, Some(0.45))
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_orders() {
+        let (engine, _dir) = test_engine().await;
+
+        engine
+            .place_order("market_1", "BUY", OrderType::Limit, 10.0, Some(0.45))
+            .await
+            .unwrap();
+
+        assert_eq!(engine.cancel_all_orders().await.unwrap(), 1);
+        assert!(engine.get_active_orders().await.unwrap().is_empty());
+        assert!(engine.is_paused().await);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_order() {
+        let (engine, _dir) = test_engine().await;
+
+        let order_id = engine
+            .place_order("market_1", "BUY", OrderType::Limit, 10.0, Some(0.45))
+            .await
+            .unwrap();
+
+        assert!(!engine.cancel_order("order_unknown").await.unwrap());
+        assert!(engine.cancel_order(&order_id).await.unwrap());
+        assert!(engine.get_active_orders().await.unwrap().is_empty());
+        // Already cancelled
+        assert!(!engine.cancel_order(&order_id).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_poller_fills_crossed_orders() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let db = crate::database::init_database(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let engine = ExecutionEngine::new(
+            Config {
+                paper_trading: true,
+                ..test_config()
+            },
+            db,
+        );
+        engine.add_monitored_market("market_1").await;
+        let mut events = engine.events().subscribe();
+        let order_id = engine
+            .place_order("market_1", "BUY", OrderType::Limit, 10.0, Some(0.45))
+            .await
+            .unwrap();
+        sqlx::query("UPDATE orders SET updated_at = 0")
+            .execute(&engine.db)
+            .await
+            .unwrap();
+
+        let snapshot = |ask: f64, asks_volume: f64, ts: i64| {
+            sqlx::query(
+                "INSERT INTO orderbook_snapshots (market_id, bids_volume, asks_volume, best_bid, best_ask, timestamp) VALUES ('market_1', 0, ?, 0.40, ?, ?)",
+            )
+            .bind(asks_volume)
+            .bind(ask)
+            .bind(ts)
+        };
+
+        // Not crossed yet: nothing fills
+        snapshot(0.50, 100.0, 1).execute(&engine.db).await.unwrap();
+        assert_eq!(engine.poll_open_orders().await.unwrap(), 0);
+
+        // Crossed with only 4 shares offered: a partial fill
+        snapshot(0.44, 4.0, 2).execute(&engine.db).await.unwrap();
+        assert_eq!(engine.poll_open_orders().await.unwrap(), 1);
+        let order = &engine.get_active_orders().await.unwrap()[0];
+        assert_eq!(order.filled_size, 4.0);
+
+        // The same 4 shares on a newer snapshot were already taken
+        sqlx::query("UPDATE orders SET updated_at = 2")
+            .execute(&engine.db)
+            .await
+            .unwrap();
+        snapshot(0.44, 4.0, 3).execute(&engine.db).await.unwrap();
+        assert_eq!(engine.poll_open_orders().await.unwrap(), 0);
+
+        // The rest fills once a newer snapshot has enough volume
+        snapshot(0.45, 100.0, 4).execute(&engine.db).await.unwrap();
+        assert_eq!(engine.poll_open_orders().await.unwrap(), 1);
+        assert!(engine.get_active_orders().await.unwrap().is_empty());
+        // Terminal orders aren't polled again
+        assert_eq!(engine.poll_open_orders().await.unwrap(), 0);
+
+        let (trades, shares): (i64, f64) =
+            sqlx::query_as("SELECT COUNT(*), SUM(size) FROM trades WHERE trade_id = ?")
+                .bind(&order_id)
+                .fetch_one(&engine.db)
+                .await
+                .unwrap();
+        assert_eq!((trades, shares), (2, 10.0));
+
+        assert!(matches!(events.try_recv(), Ok(BotEvent::OrderPlaced(_))));
+        assert!(matches!(
+            events.try_recv(),
+            Ok(BotEvent::OrderPartiallyFilled { filled_size, .. }) if filled_size == 4.0
+        ));
+        assert!(matches!(
+            events.try_recv(),
+            Ok(BotEvent::OrderFilled { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_poller_leaves_live_orders_alone() {
+        let (engine, _dir) = test_engine().await;
+        engine
+            .place_order("market_1", "BUY", OrderType::Limit, 10.0, Some(0.45))
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO orderbook_snapshots (market_id, bids_volume, asks_volume, best_bid, best_ask, timestamp) VALUES ('market_1', 0, 100, 0.40, 0.44, ?)",
+        )
+        .bind(chrono::Utc::now().timestamp() + 1)
+        .execute(&engine.db)
+        .await
+        .unwrap();
+
+        // Live fills aren't queried yet, so crossing the book does nothing
+        assert_eq!(engine.poll_open_orders().await.unwrap(), 0);
+        assert_eq!(
+            engine.get_active_orders().await.unwrap()[0].filled_size,
+            0.0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_paper_limit_order_rests_and_market_order_fills() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let db = crate::database::init_database(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let config = Config {
+            paper_trading: true,
+            ..test_config()
+        };
+        let engine = ExecutionEngine::new(config, db.clone());
+        engine.add_monitored_market("market_1").await;
+
+        // A limit order waits for the book to cross it
+        let order_id = engine
+            .place_order("market_1", "BUY", OrderType::Limit, 10.0, Some(0.40))
+            .await
+            .unwrap();
+        assert!(order_id.starts_with("paper_"));
+        assert_eq!(engine.get_active_orders().await.unwrap().len(), 1);
+        assert_eq!(engine.get_portfolio().await.unwrap().total_value, 0.0);
+
+        // A market order fills at once, shown as a position marked at the fill price
+        sqlx::query(
+            "INSERT INTO orderbook_snapshots (market_id, bids_volume, asks_volume, best_bid, best_ask, timestamp) VALUES ('market_1', 1, 1, 0.39, 0.41, 0)",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        engine
+            .place_order("market_1", "BUY", OrderType::Market, 10.0, None)
+            .await
+            .unwrap();
+        let portfolio = engine.get_portfolio().await.unwrap();
+        assert!((portfolio.total_value - 4.0).abs() < 1e-9);
+        assert_eq!(portfolio.unrealized_pnl, 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_paper_market_order_respects_price_bound() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let db = crate::database::init_database(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let config = Config {
+            paper_trading: true,
+            ..test_config()
+        };
+        let engine = ExecutionEngine::new(config, db.clone());
+        engine.add_monitored_market("market_1").await;
+
+        // Without a recent price there is nothing to fill against
+        assert!(engine
+            .place_order("market_1", "BUY", OrderType::Market, 10.0, None)
+            .await
+            .is_err());
+
+        crate::database::ensure_market(&db, "market_1")
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO orderbook_snapshots (market_id, bids_volume, asks_volume, best_bid, best_ask, timestamp) VALUES ('market_1', 1, 1, 0.58, 0.62, 0)",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+
+        engine
+            .place_order("market_1", "BUY", OrderType::Market, 10.0, None)
+            .await
+            .unwrap();
+        engine
+            .place_order("market_1", "BUY", OrderType::Market, 10.0, Some(0.55))
+            .await
+            .unwrap();
+        engine
+            .place_order("market_1", "SELL", OrderType::Market, 10.0, Some(0.65))
+            .await
+            .unwrap();
+
+        let prices: Vec<f64> = crate::database::load_trades(&db)
+            .await
+            .unwrap()
+            .iter()
+            .map(|t| t.price)
+            .collect();
+        assert_eq!(prices.len(), 3);
+        assert!((prices[0] - 0.60).abs() < 1e-9);
+        assert_eq!(prices[1], 0.55);
+        assert_eq!(prices[2], 0.65);
+    }
+
+    #[tokio::test]
+    async fn test_spread_check() {
+        use crate::markets::OrderBookLevel;
+
+        let (engine, _dir) = test_engine().await;
+        let book = |bid: f64, ask: f64| OrderBook {
+            bids: vec![OrderBookLevel {
+                price: bid,
+                size: 10.0,
+            }],
+            asks: vec![OrderBookLevel {
+                price: ask,
+                size: 10.0,
+            }],
+        };
+
+        // Within MAX_SPREAD (0.05): no complaint for either order type
+        let tight = book(0.48, 0.52);
+        assert!(engine
+            .check_spread(OrderType::Market, &tight)
+            .unwrap()
+            .is_none());
+        assert!(engine
+            .check_spread(OrderType::Limit, &tight)
+            .unwrap()
+            .is_none());
+
+        // Too wide: market orders are rejected with the book state, limits warned
+        let wide = book(0.40, 0.60);
+        let err = engine
+            .check_spread(OrderType::Market, &wide)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("best bid 0.400"));
+        assert!(err.contains("best ask 0.600"));
+        assert!(engine
+            .check_spread(OrderType::Limit, &wide)
+            .unwrap()
+            .is_some());
+
+        let empty = OrderBook {
+            bids: vec![],
+            asks: vec![],
+        };
+        assert!(engine.check_spread(OrderType::Market, &empty).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_limit_order_requires_price() {
+        let (engine, _dir) = test_engine().await;
+
+        let result = engine
+            .place_order("market_1", "BUY", OrderType::Limit, 10.0, None)
+            .await;
+        assert!(result.is_err());
+        assert!(engine.get_active_orders().await.unwrap().is_empty());
+    }
+
+    async fn insert_trade(engine: &ExecutionEngine, side: &str, price: f64, size: f64, ts: i64) {
+        crate::database::ensure_market(&engine.db, "market_1")
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO trades (market_id, trade_id, side, price, size, timestamp) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind("market_1")
+        .bind(format!("trade_{}", ts))
+        .bind(side)
+        .bind(price)
+        .bind(size)
+        .bind(ts)
+        .execute(&engine.db)
+        .await
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_realized_pnl() {
+        let (engine, _dir) = test_engine().await;
+
+        // Buy 100 @ 0.40, sell 100 @ 0.55 => +15.00
+        insert_trade(&engine, "BUY", 0.40, 100.0, 1).await;
+        insert_trade(&engine, "SELL", 0.55, 100.0, 2).await;
+
+        let portfolio = engine.get_portfolio().await.unwrap();
+        assert!((portfolio.realized_pnl - 15.0).abs() < 1e-9);
+        assert_eq!(portfolio.unrealized_pnl, 0.0);
+
+        // A losing round trip reduces realized P&L: buy @ 0.60, sell @ 0.50 => -5.00
+        insert_trade(&engine, "BUY", 0.60, 50.0, 3).await;
+        insert_trade(&engine, "SELL", 0.50, 50.0, 4).await;
+
+        let portfolio = engine.get_portfolio().await.unwrap();
+        assert!((portfolio.realized_pnl - 10.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_round_trip_pnl_is_net_of_fees() {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let pool = crate::database::init_database(db_path.to_str().unwrap())
+            .await
+            .unwrap();
+        let engine = ExecutionEngine::new(
+            Config {
+                trading_fee_pct: 0.04,
+                ..test_config()
+            },
+            pool,
+        );
+
+        // Buy 100 @ 0.40, sell 100 @ 0.55 => +15.00 gross; fees 1.60 + 2.20
+        insert_trade(&engine, "BUY", 0.40, 100.0, 1).await;
+        insert_trade(&engine, "SELL", 0.55, 100.0, 2).await;
+
+        let portfolio = engine.get_portfolio().await.unwrap();
+        assert!((portfolio.fees_paid - 3.8).abs() < 1e-9);
+        assert!((portfolio.realized_pnl - 11.2).abs() < 1e-9);
+        assert!((portfolio.realized_pnl + portfolio.fees_paid - 15.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_open_position_unrealized_pnl() {
+        let (engine, _dir) = test_engine().await;
+
+        // Average cost of (10 @ 0.20, 10 @ 0.40) is 0.30
+        insert_trade(&engine, "BUY", 0.20, 10.0, 1).await;
+        insert_trade(&engine, "BUY", 0.40, 10.0, 2).await;
+
+        // Without a snapshot the last trade price is the mark
+        let portfolio = engine.get_portfolio().await.unwrap();
+        assert!((portfolio.unrealized_pnl - 2.0).abs() < 1e-9);
+
+        sqlx::query(
+            "INSERT INTO orderbook_snapshots (market_id, bids_volume, asks_volume, best_bid, best_ask, timestamp) VALUES ('market_1', 0, 0, 0.49, 0.51, 3)",
+        )
+        .execute(&engine.db)
+        .await
+        .unwrap();
+
+        let portfolio = engine.get_portfolio().await.unwrap();
+        assert!((portfolio.unrealized_pnl - 4.0).abs() < 1e-9);
+        assert!((portfolio.total_value - 10.0).abs() < 1e-9);
+        assert_eq!(portfolio.realized_pnl, 0.0);
+    }
 }
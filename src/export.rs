@@ -0,0 +1,273 @@
+//! Export of orders, trades and spike events for offline analysis
+//!
+//! `/export` writes one file per dataset (`orders`, `trades`, `events`) into a
+//! directory, either as CSV or as a JSON array of records.
+
+use crate::database::DbPool;
+use crate::types::{OrderInfo, TradeRecord, VolumeVelocityEvent};
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use sqlx::Row;
+use std::path::{Path, PathBuf};
+
+/// File format written by `/export`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Parse a `--format` value, case-insensitively
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.to_lowercase().as_str() {
+            "csv" => Some(ExportFormat::Csv),
+            "json" => Some(ExportFormat::Json),
+            _ => None,
+        }
+    }
+
+    fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// Everything `/export` writes, oldest rows first
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportData {
+    pub orders: Vec<OrderInfo>,
+    pub trades: Vec<TradeRecord>,
+    pub events: Vec<VolumeVelocityEvent>,
+}
+
+/// A row type that can be written as one CSV line
+trait CsvRecord {
+    const HEADER: &'static str;
+    fn csv_row(&self) -> String;
+}
+
+impl CsvRecord for OrderInfo {
+    const HEADER: &'static str = "order_id,market_id,side,price,size,filled_size,status,created_at";
+
+    fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{},{},{}",
+            csv_field(&self.order_id),
+            csv_field(&self.market_id),
+            csv_field(&self.side),
+            self.price,
+            self.size,
+            self.filled_size,
+            csv_field(&self.status),
+            self.created_at
+        )
+    }
+}
+
+impl CsvRecord for TradeRecord {
+    const HEADER: &'static str = "market_id,trade_id,side,price,size,timestamp";
+
+    fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{},{}",
+            csv_field(&self.market_id),
+            csv_field(&self.trade_id),
+            csv_field(&self.side),
+            self.price,
+            self.size,
+            self.timestamp
+        )
+    }
+}
+
+impl CsvRecord for VolumeVelocityEvent {
+    const HEADER: &'static str = "market_id,velocity,volume_delta,time_delta,timestamp";
+
+    fn csv_row(&self) -> String {
+        format!(
+            "{},{},{},{},{}",
+            csv_field(&self.market_id),
+            self.velocity,
+            self.volume_delta,
+            self.time_delta,
+            self.timestamp
+        )
+    }
+}
+
+/// Quote a CSV field if it contains a delimiter, quote or line break
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn to_csv<T: CsvRecord>(rows: &[T]) -> String {
+    let mut out = String::from(T::HEADER);
+    out.push('\n');
+    for row in rows {
+        out.push_str(&row.csv_row());
+        out.push('\n');
+    }
+    out
+}
+
+/// Load every order, trade and velocity event from the database
+pub async fn load_export_data(pool: &DbPool) -> Result<ExportData> {
+    let orders = sqlx::query(
+        r#"
+        SELECT order_id, market_id, side, price, size, filled_size, status, created_at
+        FROM orders
+        ORDER BY created_at, id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| OrderInfo {
+        order_id: row.get(0),
+        market_id: row.get(1),
+        side: row.get(2),
+        price: row.get(3),
+        size: row.get(4),
+        filled_size: row.get(5),
+        status: row.get(6),
+        created_at: row.get(7),
+    })
+    .collect();
+
+    let trades = crate::database::load_trades(pool).await?;
+
+    let events = sqlx::query(
+        r#"
+        SELECT market_id, velocity, volume_delta, time_delta, timestamp
+        FROM volume_velocity_events
+        ORDER BY timestamp, id
+        "#,
+    )
+    .fetch_all(pool)
+    .await?
+    .into_iter()
+    .map(|row| VolumeVelocityEvent {
+        market_id: row.get(0),
+        velocity: row.get(1),
+        volume_delta: row.get(2),
+        time_delta: row.get(3),
+        timestamp: row.get(4),
+    })
+    .collect();
+
+    Ok(ExportData {
+        orders,
+        trades,
+        events,
+    })
+}
+
+/// Write `orders`, `trades` and `events` files into `dir`, creating it if needed.
+/// Returns the paths written.
+pub fn write_export(data: &ExportData, dir: &Path, format: ExportFormat) -> Result<Vec<PathBuf>> {
+    std::fs::create_dir_all(dir).with_context(|| format!("failed to create {}", dir.display()))?;
+
+    let contents = match format {
+        ExportFormat::Csv => [
+            ("orders", to_csv(&data.orders)),
+            ("trades", to_csv(&data.trades)),
+            ("events", to_csv(&data.events)),
+        ],
+        ExportFormat::Json => [
+            ("orders", serde_json::to_string_pretty(&data.orders)?),
+            ("trades", serde_json::to_string_pretty(&data.trades)?),
+            ("events", serde_json::to_string_pretty(&data.events)?),
+        ],
+    };
+
+    let mut paths = Vec::with_capacity(contents.len());
+    for (name, content) in contents {
+        let path = dir.join(format!("{}.{}", name, format.extension()));
+        std::fs::write(&path, content)
+            .with_context(|| format!("failed to write {}", path.display()))?;
+        paths.push(path);
+    }
+    Ok(paths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_json_export_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let db = crate::database::init_database(dir.path().join("test.db").to_str().unwrap())
+            .await
+            .unwrap();
+        crate::database::ensure_market(&db, "market_1")
+            .await
+            .unwrap();
+
+        sqlx::query(
+            r#"
+            INSERT INTO orders (order_id, market_id, side, order_type, price, size, filled_size, status, created_at, updated_at)
+            VALUES ('order_1', 'market_1', 'BUY', 'LIMIT', 0.42, 10.0, 10.0, 'FILLED', 100, 100)
+            "#,
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO trades (market_id, trade_id, side, price, size, timestamp) VALUES ('market_1', 'order_1', 'BUY', 0.42, 10.0, 100)",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+        sqlx::query(
+            "INSERT INTO volume_velocity_events (market_id, velocity, volume_delta, time_delta, timestamp) VALUES ('market_1', 1500.0, 3000.0, 2.0, 101)",
+        )
+        .execute(&db)
+        .await
+        .unwrap();
+
+        let data = load_export_data(&db).await.unwrap();
+        let out = dir.path().join("export");
+        let paths = write_export(&data, &out, ExportFormat::Json).unwrap();
+        assert_eq!(paths.len(), 3);
+
+        let read = |name: &str| std::fs::read_to_string(out.join(name)).unwrap();
+        let orders: Vec<OrderInfo> = serde_json::from_str(&read("orders.json")).unwrap();
+        let trades: Vec<TradeRecord> = serde_json::from_str(&read("trades.json")).unwrap();
+        let events: Vec<VolumeVelocityEvent> = serde_json::from_str(&read("events.json")).unwrap();
+
+        assert_eq!(orders.len(), 1);
+        assert_eq!(orders[0].order_id, "order_1");
+        assert_eq!(orders[0].status, "FILLED");
+        assert_eq!(orders[0].price, 0.42);
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].trade_id, "order_1");
+        assert_eq!(trades[0].size, 10.0);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].velocity, 1500.0);
+        assert_eq!(events[0].timestamp, 101);
+    }
+
+    #[test]
+    fn test_csv_quotes_special_fields() {
+        let trades = vec![TradeRecord {
+            market_id: "a,b".to_string(),
+            trade_id: "say \"hi\"".to_string(),
+            side: "SELL".to_string(),
+            price: 0.5,
+            size: 2.0,
+            timestamp: 7,
+        }];
+        assert_eq!(
+            to_csv(&trades),
+            "market_id,trade_id,side,price,size,timestamp\n\"a,b\",\"say \"\"hi\"\"\",SELL,0.5,2,7\n"
+        );
+    }
+}
@@ -0,0 +1,221 @@
+//! Headless daemon mode
+//!
+//! Runs the bot without any interactive interface: watched markets are polled at
+//! the configured intervals, fed through spike detection, and results are reported via
+//! `tracing`. Intended for running under systemd or similar supervisors.
+
+use crate::config::Config;
+use crate::database::DbPool;
+use crate::markets::{load_watched_markets, parse_volume, MarketService};
+use crate::spike_detection::{
+    adaptive_poll_interval, AlertDebouncer, SpikeDetector, ALERT_DEBOUNCE,
+};
+use anyhow::Result;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{watch, Mutex};
+
+/// When each watched market is next due, following `POLL_INTERVAL_MIN_SECS`,
+/// `POLL_INTERVAL_MAX_SECS` and `ADAPTIVE_POLLING` like the TUI does
+struct PollSchedule {
+    adaptive: bool,
+    min: Duration,
+    max: Duration,
+    next_poll: HashMap<String, Instant>,
+}
+
+impl PollSchedule {
+    fn new(config: &Config) -> Self {
+        Self {
+            adaptive: config.adaptive_polling,
+            min: Duration::from_secs(config.poll_interval_min_secs),
+            max: Duration::from_secs(config.poll_interval_max_secs),
+            next_poll: HashMap::new(),
+        }
+    }
+
+    /// How often to wake up: often enough for the busiest market
+    fn tick(&self) -> Duration {
+        if self.adaptive {
+            self.min
+        } else {
+            self.max
+        }
+    }
+
+    /// Markets never polled are due right away
+    fn is_due(&self, market_id: &str, now: Instant) -> bool {
+        self.next_poll.get(market_id).is_none_or(|due| *due <= now)
+    }
+
+    /// Set the next poll of `market_id` from its latest velocity
+    fn schedule(&mut self, market_id: &str, velocity: Option<f64>, threshold: f64, now: Instant) {
+        let interval = if self.adaptive {
+            adaptive_poll_interval(velocity, threshold, self.min, self.max)
+        } else {
+            self.max
+        };
+        self.next_poll.insert(market_id.to_string(), now + interval);
+    }
+}
+
+/// Poll watched markets and run spike detection until `shutdown` fires
+pub async fn run_headless(
+    config: &Config,
+    db: DbPool,
+    spike_detector: Arc<Mutex<SpikeDetector>>,
+    market_service: MarketService,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<()> {
+    let mut schedule = PollSchedule::new(config);
+    let mut ticker = tokio::time::interval(schedule.tick());
+    let mut alerts = AlertDebouncer::new(ALERT_DEBOUNCE);
+
+    if schedule.adaptive {
+        tracing::info!(
+            "Running headless - polling watched markets every {}-{}s, busiest first",
+            schedule.min.as_secs(),
+            schedule.max.as_secs()
+        );
+    } else {
+        tracing::info!(
+            "Running headless - polling watched markets every {}s",
+            schedule.max.as_secs()
+        );
+    }
+
+    loop {
+        tokio::select! {
+            _ = ticker.tick() => {
+                poll_watched_markets(&db, &market_service, &spike_detector, &mut schedule, &mut alerts).await;
+            }
+            _ = shutdown.changed() => break,
+        }
+        if *shutdown.borrow() {
+            break;
+        }
+    }
+
+    tracing::info!("Headless loop stopped");
+    Ok(())
+}
+
+/// Fetch the latest volume for every watched market that is due concurrently,
+/// then check each for spikes. The detector is only locked for the checks, not
+/// the network calls. Errors are logged per market so one bad market doesn't
+/// stop the others.
+async fn poll_watched_markets(
+    db: &DbPool,
+    market_service: &MarketService,
+    spike_detector: &Mutex<SpikeDetector>,
+    schedule: &mut PollSchedule,
+    alerts: &mut AlertDebouncer,
+) {
+    let markets = match load_watched_markets(db).await {
+        Ok(markets) => markets,
+        Err(e) => {
+            tracing::error!("Failed to load watched markets: {}", e);
+            return;
+        }
+    };
+
+    let now = Instant::now();
+    let markets: Vec<_> = markets
+        .into_iter()
+        .filter(|m| schedule.is_due(&m.id, now))
+        .collect();
+    if markets.is_empty() {
+        tracing::debug!("No watched markets due for polling");
+        return;
+    }
+
+    let ids: Vec<String> = markets.iter().map(|m| m.id.clone()).collect();
+    let results = market_service.get_markets(&ids).await;
+
+    let mut spike_detector = spike_detector.lock().await;
+    let now = Instant::now();
+    for (market, result) in markets.into_iter().zip(results) {
+        match result {
+            Ok(Some(latest)) => {
+                check_market(
+                    &mut spike_detector,
+                    alerts,
+                    &market.question,
+                    &market.id,
+                    &latest.volume,
+                )
+                .await
+            }
+            Ok(None) => tracing::warn!("Market {} not found", market.id),
+            Err(e) => tracing::warn!("Failed to fetch market {}: {}", market.id, e),
+        }
+
+        // Failures wait a full interval too, so a broken market isn't hammered
+        schedule.schedule(
+            &market.id,
+            spike_detector.current_velocity(&market.id),
+            spike_detector.velocity_threshold_for(&market.id),
+            now,
+        );
+    }
+}
+
+/// Run a market's latest volume through spike detection and log the outcome
+async fn check_market(
+    spike_detector: &mut SpikeDetector,
+    alerts: &mut AlertDebouncer,
+    question: &str,
+    market_id: &str,
+    volume: &str,
+) {
+    let volume = parse_volume(volume);
+    match spike_detector
+        .check_volume_velocity(market_id, volume)
+        .await
+    {
+        Ok(Some(_)) if !alerts.should_alert(market_id, Instant::now()) => {
+            tracing::debug!("{}: spike alert debounced", question)
+        }
+        Ok(Some(event)) => tracing::warn!(
+            "🚨 Volume spike on {}: {:+.2} vol/sec ({:+.2} over {:.0}s)",
+            question,
+            event.velocity,
+            event.volume_delta,
+            event.time_delta
+        ),
+        Ok(None) => tracing::debug!("{}: volume {:.2}", question, volume),
+        Err(e) => tracing::warn!("Spike detection failed for {}: {}", market_id, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_poll_schedule_follows_config() {
+        let mut config = crate::execution::tests::test_config();
+        config.poll_interval_min_secs = 5;
+        config.poll_interval_max_secs = 60;
+        let now = Instant::now();
+
+        let mut schedule = PollSchedule::new(&config);
+        assert_eq!(schedule.tick(), Duration::from_secs(5));
+        assert!(schedule.is_due("market_1", now));
+
+        // A market at the spike threshold comes back after the minimum
+        schedule.schedule("market_1", Some(1000.0), 1000.0, now);
+        schedule.schedule("market_2", None, 1000.0, now);
+        assert!(!schedule.is_due("market_1", now));
+        assert!(schedule.is_due("market_1", now + Duration::from_secs(5)));
+        assert!(!schedule.is_due("market_2", now + Duration::from_secs(59)));
+        assert!(schedule.is_due("market_2", now + Duration::from_secs(60)));
+
+        config.adaptive_polling = false;
+        let mut schedule = PollSchedule::new(&config);
+        assert_eq!(schedule.tick(), Duration::from_secs(60));
+        schedule.schedule("market_1", Some(1000.0), 1000.0, now);
+        assert!(!schedule.is_due("market_1", now + Duration::from_secs(5)));
+    }
+}
@@ -1,17 +1,29 @@
 pub mod clob_auth;
 pub mod config;
 pub mod database;
+pub mod events;
 pub mod execution;
+pub mod export;
+pub mod headless;
+pub mod logging;
 pub mod markets;
+pub mod notify;
 pub mod onboarding;
+pub mod recording;
+pub mod repl;
+pub mod setup;
 pub mod spike_detection;
 pub mod tui;
 pub mod types;
 
 pub use clob_auth::{authenticate, AuthenticatedClient};
 pub use config::Config;
-pub use database::{init_database, DbPool};
+pub use database::{init_database, spawn_retention_task, DbPool};
 pub use execution::ExecutionEngine;
-pub use onboarding::run_onboarding_checks;
+pub use headless::run_headless;
+pub use logging::init_logging;
+pub use markets::{build_http_client, MarketService};
+pub use onboarding::{check_network_connectivity, check_usdc_balance, run_onboarding_checks};
+pub use repl::run_repl;
 pub use spike_detection::SpikeDetector;
 pub use tui::run_tui;
@@ -0,0 +1,56 @@
+//! Logging setup
+//!
+//! Routes `tracing` output to a log file. The terminal is only written to in headless
+//! mode, since output there would corrupt the TUI. The file is appended to across
+//! sessions and rotated once it grows past a size limit.
+
+use crate::config::Config;
+use std::fs::{self, File, OpenOptions};
+use std::path::Path;
+use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+
+/// Rotate the log file once it reaches this size (10 MB)
+const MAX_LOG_FILE_BYTES: u64 = 10 * 1024 * 1024;
+
+/// Initialize the global `tracing` subscriber from the configuration.
+/// `console` also writes to stderr, for headless runs with no TUI.
+pub fn init_logging(config: &Config, console: bool) {
+    let log_file = if config.log_to_file {
+        open_log_file(&config.log_file_path)
+    } else {
+        None
+    };
+
+    let file_layer = log_file.map(|file| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::sync::Mutex::new(file))
+            .with_ansi(false)
+    });
+
+    let console_layer =
+        console.then(|| tracing_subscriber::fmt::layer().with_writer(std::io::stderr));
+
+    tracing_subscriber::registry()
+        .with(
+            tracing_subscriber::EnvFilter::try_new(&config.log_level)
+                .unwrap_or_else(|_| "info".into()),
+        )
+        .with(file_layer)
+        .with(console_layer)
+        .init();
+}
+
+/// Open the log file for appending, rotating it first if it is too large.
+/// Returns None if the file can't be opened so the bot still starts.
+fn open_log_file(path: &str) -> Option<File> {
+    let path = Path::new(path);
+
+    if let Ok(metadata) = fs::metadata(path) {
+        if metadata.len() >= MAX_LOG_FILE_BYTES {
+            let rotated = path.with_extension("log.1");
+            let _ = fs::rename(path, rotated);
+        }
+    }
+
+    OpenOptions::new().create(true).append(true).open(path).ok()
+}
@@ -1,85 +1,433 @@
 use anyhow::Result;
+use clap::Parser;
+use polymarket_bot_summer::database::{count_open_orders, reset_database, WriteBatcher};
+use polymarket_bot_summer::notify::WebhookNotifier;
+use polymarket_bot_summer::onboarding::OnboardingError;
+use polymarket_bot_summer::recording::{read_samples, replay_samples, SessionRecorder};
+use polymarket_bot_summer::setup::run_setup_wizard;
 use polymarket_bot_summer::{
-    authenticate, init_database, run_onboarding_checks, run_tui, Config, ExecutionEngine,
-    SpikeDetector,
+    authenticate, build_http_client, check_network_connectivity, check_usdc_balance, init_database,
+    init_logging, run_headless, run_onboarding_checks, run_repl, run_tui, spawn_retention_task,
+    Config, DbPool, ExecutionEngine, MarketService, SpikeDetector,
 };
-use std::fs::File;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use std::time::Duration;
+use tokio::sync::{watch, Mutex};
+
+/// High-frequency trading bot for Polymarket using activity spike exploitation
+#[derive(Parser, Debug)]
+#[command(version, about)]
+struct Args {
+    /// Load environment variables from this file instead of ./.env
+    #[arg(long, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// SQLite database file (overrides DATABASE_PATH)
+    #[arg(long, value_name = "PATH")]
+    database: Option<String>,
+
+    /// Log filter, e.g. "debug" or "polymarket_bot_summer=trace" (overrides RUST_LOG)
+    #[arg(long, value_name = "LEVEL")]
+    log_level: Option<String>,
+
+    /// Shorthand for --log-level debug
+    #[arg(short, long, conflicts_with = "log_level")]
+    verbose: bool,
+
+    /// Use the full-screen terminal UI (default when stdout is a terminal)
+    #[arg(long, conflicts_with_all = ["repl", "headless"])]
+    tui: bool,
+
+    /// Use the line-based command prompt (default when stdout is not a terminal)
+    #[arg(long, conflicts_with = "headless")]
+    repl: bool,
+
+    /// Run without any interface, logging to the console (e.g. under systemd)
+    #[arg(long)]
+    headless: bool,
+
+    /// Skip authentication and network checks (development/demo)
+    #[arg(long)]
+    offline: bool,
+
+    /// Drop all tables and recreate the database (asks for confirmation)
+    #[arg(long)]
+    reset_db: bool,
+
+    /// With --reset-db, reset even if there are open orders
+    #[arg(long, requires = "reset_db")]
+    force: bool,
+
+    /// Append all fetched market data (volumes, order books) to this JSONL file
+    #[arg(long, value_name = "FILE", conflicts_with = "replay")]
+    record: Option<PathBuf>,
+
+    /// Run a recorded session through spike detection instead of the API, then exit
+    #[arg(long, value_name = "FILE")]
+    replay: Option<PathBuf>,
+
+    /// With --replay, process samples as fast as possible instead of at the recorded pace
+    #[arg(long, requires = "replay")]
+    replay_fast: bool,
+
+    /// Interactively write the .env file (or the --config file), then exit
+    #[arg(long)]
+    setup: bool,
+}
+
+/// Which interface drives the bot after startup
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Interface {
+    Tui,
+    Repl,
+    Headless,
+}
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Initialize logging to file (not terminal, to avoid corrupting TUI)
-    let log_file = File::create("bot.log").unwrap_or_else(|_| {
-        // Fallback: if we can't create file, just disable file logging
-        File::open(if cfg!(windows) { "NUL" } else { "/dev/null" }).unwrap()
-    });
+    let args = Args::parse();
 
-    tracing_subscriber::registry()
-        .with(
-            tracing_subscriber::EnvFilter::try_from_default_env().unwrap_or_else(|_| "info".into()),
-        )
-        .with(
-            tracing_subscriber::fmt::layer()
-                .with_writer(std::sync::Mutex::new(log_file))
-                .with_ansi(false),
-        )
-        .init();
+    if args.setup {
+        let path = args.config.clone().unwrap_or_else(|| PathBuf::from(".env"));
+        return run_setup_wizard(&path);
+    }
 
     // Load environment variables
-    dotenvy::dotenv().ok();
+    match args.config {
+        Some(ref path) => {
+            if let Err(e) = dotenvy::from_path(path) {
+                eprintln!("Failed to load config file {}: {}", path.display(), e);
+                std::process::exit(1);
+            }
+        }
+        None => {
+            dotenvy::dotenv().ok();
+        }
+    }
+
+    // Command-line flags override the environment. They are applied to the
+    // loaded Config rather than the process environment, which isn't safe to
+    // modify once the runtime's worker threads are running.
+    let database_path = args
+        .database
+        .clone()
+        .unwrap_or_else(Config::database_path_from_env);
+
+    // Flags win over UI_MODE; with neither, use the TUI only on a real terminal
+    let interface = if args.headless {
+        Interface::Headless
+    } else if args.repl {
+        Interface::Repl
+    } else if args.tui {
+        Interface::Tui
+    } else {
+        match std::env::var("UI_MODE").ok().as_deref() {
+            Some("tui") => Interface::Tui,
+            Some("repl") => Interface::Repl,
+            Some(other) => {
+                eprintln!("Invalid UI_MODE '{}': expected 'tui' or 'repl'", other);
+                std::process::exit(1);
+            }
+            None if std::io::stdout().is_terminal() => Interface::Tui,
+            None => Interface::Repl,
+        }
+    };
+    // A replay never needs the network
+    let offline = args.offline || args.replay.is_some();
 
     // Run onboarding checks (validates private key and database)
-    if let Err(e) = run_onboarding_checks() {
+    if let Err(e) = run_onboarding_checks(&database_path) {
         eprintln!("{}", e);
         std::process::exit(1);
     }
 
     // Load and validate configuration
-    let config = match Config::from_env() {
+    let mut config = match Config::from_env() {
         Ok(c) => c,
         Err(e) => {
             eprintln!("Configuration error: {}", e);
             std::process::exit(1);
         }
     };
+    config.database_path = database_path;
+    if let Some(ref log_level) = args.log_level {
+        config.log_level = log_level.clone();
+    } else if args.verbose {
+        config.log_level = "debug".to_string();
+    }
 
     if let Err(e) = config.validate() {
         eprintln!("Configuration validation failed: {}", e);
         std::process::exit(1);
     }
 
+    if !offline {
+        // Warn (but keep going) if the Polymarket APIs can't be reached
+        if let Err(e) = check_network_connectivity().await {
+            eprintln!("{}", e);
+        }
+
+        // Warn early if the wallet can't fund the smallest order
+        check_usdc_balance(&config).await;
+    }
+
+    // Initialize logging to file (and the terminal only when there is no TUI to corrupt)
+    init_logging(
+        &config,
+        interface == Interface::Headless || args.replay.is_some(),
+    );
+
     // Authenticate with CLOB API (dynamic, no stored credentials needed)
-    let auth_client = match authenticate(&config.private_key).await {
-        Ok(client) => client,
-        Err(e) => {
-            eprintln!("CLOB authentication failed: {}", e);
-            std::process::exit(1);
+    let auth_client = if offline {
+        tracing::warn!("⚠ Offline mode - skipping CLOB authentication");
+        None
+    } else {
+        match authenticate(&config.private_key).await {
+            Ok(client) => {
+                tracing::info!("✓ Authenticated as {}", client.wallet_address);
+                Some(client)
+            }
+            Err(e) => {
+                eprintln!(
+                    "{}",
+                    OnboardingError::AuthenticationFailed(format!("{:#}", e))
+                );
+                std::process::exit(1);
+            }
         }
     };
-    tracing::info!("✓ Authenticated as {}", auth_client.wallet_address);
 
     // Initialize database
     let db = init_database(&config.database_path).await?;
     tracing::info!("✓ Database initialized at {}", config.database_path);
 
-    // Initialize spike detector
-    let _spike_detector = SpikeDetector::new(
+    if args.reset_db {
+        reset_database_interactive(&db, &config.database_path, args.force).await?;
+    }
+
+    // One HTTP client for all market data and alert requests so connections are reused
+    let http_client = build_http_client(
+        Duration::from_secs(config.connect_timeout_secs),
+        Duration::from_secs(config.request_timeout_secs),
+    )?;
+
+    // Initialize spike detector, shared with whichever interface runs
+    let mut spike_detector = SpikeDetector::new(
         db.clone(),
         config.volume_velocity_threshold,
         config.obi_threshold,
     );
-    tracing::info!("✓ Spike detector initialized");
+    let overrides = spike_detector.load_market_thresholds().await?;
+    if overrides > 0 {
+        tracing::info!("✓ Loaded threshold overrides for {} market(s)", overrides);
+    }
 
-    // Initialize execution engine
-    let execution_engine = Arc::new(ExecutionEngine::new(config.clone()));
+    // Replays skip the webhook so old spikes aren't re-announced. Unless
+    // --database names a file for them, the spikes they find go to a scratch
+    // database so they don't mix with the live history.
+    if let Some(ref path) = args.replay {
+        if args.database.is_some() {
+            return replay_session(path, &mut spike_detector, !args.replay_fast).await;
+        }
+        let scratch_path =
+            std::env::temp_dir().join(format!("polymarket-replay-{}.db", std::process::id()));
+        let scratch = init_database(&scratch_path.to_string_lossy()).await?;
+        let mut detector = spike_detector.with_database(scratch.clone());
+        let result = replay_session(path, &mut detector, !args.replay_fast).await;
+        scratch.close().await;
+        remove_database_files(&scratch_path);
+        return result;
+    }
+
+    // Initialize execution engine; it owns the event bus the detector publishes to
+    let execution_engine = Arc::new(ExecutionEngine::new(config.clone(), db.clone()));
     tracing::info!("✓ Execution engine initialized");
 
+    // Snapshots and velocity events are written in batches, flushed on shutdown
+    let write_batcher = Arc::new(WriteBatcher::new(db.clone(), config.db_batch_size));
+    let spike_detector = Arc::new(Mutex::new(
+        spike_detector
+            .with_events(execution_engine.events())
+            .with_write_batcher(write_batcher.clone()),
+    ));
+    tracing::info!("✓ Spike detector initialized");
+
+    // The webhook is just another subscriber to spike events
+    if let Some(ref url) = config.alert_webhook_url {
+        WebhookNotifier::new(http_client.clone(), url.clone())
+            .spawn(execution_engine.events().subscribe());
+        tracing::info!("✓ Spike alerts will be posted to the configured webhook");
+    }
+
+    let mut market_service =
+        MarketService::with_client(http_client).with_rate_limit(config.api_requests_per_second);
+    if let Some(ref path) = args.record {
+        market_service = market_service.with_recorder(SessionRecorder::create(path)?);
+        tracing::info!("✓ Recording market data to {}", path.display());
+    }
+
     // TODO: Integrate auth_client with polymarket-hft for actual trading
     tracing::info!("⚠ Trading integration pending - running in demo mode");
 
-    // Start TUI
-    run_tui(db, execution_engine).await?;
+    // Record portfolio snapshots in the background for the equity curve
+    let (shutdown_tx, shutdown_rx) = watch::channel(false);
+    let shutdown_tx = Arc::new(shutdown_tx);
+    let snapshot_writer = execution_engine.spawn_snapshot_writer(shutdown_rx.clone());
+
+    // Keep open orders' fill progress up to date
+    let order_poller = execution_engine.spawn_order_poller(shutdown_rx.clone());
+
+    // Prune old snapshots/events now and periodically to bound database growth
+    let retention_task =
+        spawn_retention_task(db.clone(), config.data_retention_days, shutdown_rx.clone());
+
+    let write_flusher = write_batcher.spawn_flusher(
+        Duration::from_secs(config.db_flush_interval_secs),
+        shutdown_rx.clone(),
+    );
+
+    // Ctrl-C / SIGTERM take the same shutdown path as quitting from the TUI
+    let signal_tx = shutdown_tx.clone();
+    tokio::spawn(async move {
+        shutdown_signal().await;
+        tracing::warn!("Received shutdown signal, shutting down");
+        let _ = signal_tx.send(true);
+    });
+
+    let wallet_address = auth_client.map(|client| client.wallet_address);
+    let result = match interface {
+        Interface::Tui => {
+            run_tui(
+                db,
+                execution_engine.clone(),
+                spike_detector,
+                market_service,
+                wallet_address,
+                shutdown_rx,
+            )
+            .await
+        }
+        Interface::Repl => {
+            run_repl(
+                db,
+                execution_engine.clone(),
+                spike_detector,
+                market_service,
+                wallet_address,
+                shutdown_rx,
+            )
+            .await
+        }
+        Interface::Headless => {
+            run_headless(&config, db, spike_detector, market_service, shutdown_rx).await
+        }
+    };
+
+    // Stop background tasks before exiting
+    let _ = shutdown_tx.send(true);
+    let _ = snapshot_writer.await;
+    let _ = order_poller.await;
+    let _ = retention_task.await;
+    let _ = write_flusher.await;
+
+    // Don't leave orders live on the exchange after we exit
+    if config.cancel_on_exit {
+        match execution_engine.cancel_all_orders().await {
+            Ok(count) => {
+                tracing::info!("Cancelled {} open order(s) on exit", count);
+                println!("Cancelled {} open order(s) on exit", count);
+            }
+            Err(e) => {
+                tracing::error!("Failed to cancel orders on exit: {}", e);
+                eprintln!("Failed to cancel orders on exit: {}", e);
+            }
+        }
+    }
+
+    result
+}
+
+/// Wait for Ctrl-C, or SIGTERM on Unix (what `systemctl stop` sends)
+async fn shutdown_signal() {
+    #[cfg(unix)]
+    {
+        use tokio::signal::unix::{signal, SignalKind};
+
+        match signal(SignalKind::terminate()) {
+            Ok(mut sigterm) => {
+                tokio::select! {
+                    _ = tokio::signal::ctrl_c() => {}
+                    _ = sigterm.recv() => {}
+                }
+            }
+            Err(_) => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = tokio::signal::ctrl_c().await;
+    }
+}
+
+/// Handle `--replay`: run every sample in the recording through spike detection
+/// and print what was found
+async fn replay_session(path: &Path, detector: &mut SpikeDetector, realtime: bool) -> Result<()> {
+    let samples = read_samples(path)?;
+    println!(
+        "Replaying {} sample(s) from {}{}",
+        samples.len(),
+        path.display(),
+        if realtime { "" } else { " (fast)" }
+    );
+
+    let summary = replay_samples(&samples, detector, realtime).await?;
+    println!(
+        "Replay finished: {} sample(s), {} volume spike(s), {} order book imbalance(s)",
+        summary.samples, summary.spikes, summary.imbalances
+    );
+    Ok(())
+}
+
+/// Delete a SQLite database along with its WAL and shared-memory files
+fn remove_database_files(path: &Path) {
+    for suffix in ["", "-wal", "-shm"] {
+        let mut file = path.as_os_str().to_owned();
+        file.push(suffix);
+        let _ = std::fs::remove_file(file);
+    }
+}
+
+/// Handle `--reset-db`: refuse while orders are open (unless forced), confirm,
+/// then wipe and recreate every table. Exits if the user backs out.
+async fn reset_database_interactive(db: &DbPool, path: &str, force: bool) -> Result<()> {
+    let open_orders = count_open_orders(db).await?;
+    if open_orders > 0 && !force {
+        eprintln!(
+            "Refusing to reset: {} open order(s) in {}. Cancel them first or pass --force.",
+            open_orders, path
+        );
+        std::process::exit(1);
+    }
+
+    print!(
+        "This permanently deletes all data in {}. Type 'yes' to continue: ",
+        path
+    );
+    std::io::stdout().flush()?;
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    if answer.trim() != "yes" {
+        println!("Database reset cancelled");
+        std::process::exit(0);
+    }
 
+    let tables = reset_database(db).await?;
+    println!("Cleared tables: {}", tables.join(", "));
+    tracing::warn!("Database reset, cleared tables: {}", tables.join(", "));
     Ok(())
 }
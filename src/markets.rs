@@ -1,8 +1,15 @@
+use crate::recording::{Sample, SessionRecorder};
 use anyhow::Result;
+use chrono::{DateTime, NaiveDate};
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Deserializer, Serialize};
 use sqlx::Row; // For .get() method on database rows
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
 
 const GAMMA_API_BASE: &str = "https://gamma-api.polymarket.com";
+const CLOB_API_BASE: &str = "https://clob.polymarket.com";
 
 /// Custom deserializer that handles both JSON arrays and JSON strings containing arrays
 fn deserialize_string_or_vec<'de, D>(deserializer: D) -> Result<Vec<String>, D::Error>
@@ -78,6 +85,61 @@ pub struct GammaMarket {
         deserialize_with = "deserialize_string_or_vec"
     )]
     pub outcome_prices: Vec<String>,
+    #[serde(
+        rename = "clobTokenIds",
+        default,
+        deserialize_with = "deserialize_string_or_vec"
+    )]
+    pub clob_token_ids: Vec<String>,
+}
+
+/// A Gamma `/markets` response normalized to a list. The endpoint normally
+/// returns an array, but some lookups answer with a single market object or
+/// an object wrapping the array in `data`; all three are accepted. Any other
+/// object, such as an error body, is rejected.
+#[derive(Debug, Clone)]
+struct GammaMarkets(Vec<GammaMarket>);
+
+impl<'de> Deserialize<'de> for GammaMarkets {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        use serde::de::Error;
+        use serde_json::Value;
+
+        let markets = match Value::deserialize(deserializer)? {
+            Value::Array(arr) => serde_json::from_value(Value::Array(arr)),
+            Value::Object(mut obj) => match obj.remove("data") {
+                Some(Value::Array(arr)) => serde_json::from_value(Value::Array(arr)),
+                Some(Value::Null) => Ok(Vec::new()),
+                Some(other) => {
+                    return Err(D::Error::custom(format!(
+                        "expected `data` to be an array of markets, found: {}",
+                        other
+                    )))
+                }
+                None if obj.contains_key("id") || obj.contains_key("conditionId") => {
+                    serde_json::from_value(Value::Object(obj)).map(|m| vec![m])
+                }
+                // e.g. `{"error": "..."}`, which would otherwise parse as an empty market
+                None => {
+                    return Err(D::Error::custom(format!(
+                        "expected a market with an `id` or `conditionId`, found: {}",
+                        Value::Object(obj)
+                    )))
+                }
+            },
+            Value::Null => Ok(Vec::new()),
+            other => {
+                return Err(D::Error::custom(format!(
+                    "expected a market, an array of markets or an object with a `data` array, found: {}",
+                    other
+                )))
+            }
+        };
+        markets.map(GammaMarkets).map_err(D::Error::custom)
+    }
 }
 
 /// Helper structs for /public-search response
@@ -85,12 +147,6 @@ pub struct GammaMarket {
 struct PublicSearchResponse {
     #[serde(default)]
     events: Vec<PublicSearchEvent>,
-    #[serde(default)]
-    tags: serde_json::Value,
-    #[serde(default)]
-    profiles: serde_json::Value,
-    #[serde(default)]
-    pagination: serde_json::Value,
 }
 
 #[derive(Debug, Deserialize)]
@@ -112,6 +168,127 @@ pub struct PublicSearchMarket {
     pub enable_order_book: bool,
 }
 
+/// Parse a volume string such as "1234567", "1,234.5" or "1.2M" into dollars.
+///
+/// Commas and a leading `$` are ignored and a trailing K/M/B (either case)
+/// scales the number. Anything unparseable, including an empty string, is 0.
+pub fn parse_volume(volume: &str) -> f64 {
+    let cleaned: String = volume
+        .trim()
+        .trim_start_matches('$')
+        .chars()
+        .filter(|&c| c != ',')
+        .collect();
+
+    let (number, multiplier) = match cleaned.chars().last() {
+        Some('k' | 'K') => (&cleaned[..cleaned.len() - 1], 1e3),
+        Some('m' | 'M') => (&cleaned[..cleaned.len() - 1], 1e6),
+        Some('b' | 'B') => (&cleaned[..cleaned.len() - 1], 1e9),
+        _ => (cleaned.as_str(), 1.0),
+    };
+
+    number
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| v.is_finite())
+        .map_or(0.0, |v| v * multiplier)
+}
+
+/// Client-side filters applied to `search_markets` results
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct SearchFilter {
+    /// Drop markets that have traded less than this
+    pub min_volume: Option<f64>,
+    /// Order by volume, ascending when `true`; `None` keeps the API order
+    pub volume_ascending: Option<bool>,
+}
+
+impl SearchFilter {
+    pub fn apply(&self, mut markets: Vec<MarketInfo>) -> Vec<MarketInfo> {
+        let volume = |m: &MarketInfo| parse_volume(&m.volume);
+
+        if let Some(min_volume) = self.min_volume {
+            markets.retain(|m| volume(m) >= min_volume);
+        }
+        if let Some(ascending) = self.volume_ascending {
+            markets.sort_by(|a, b| {
+                let ordering = volume(a).total_cmp(&volume(b));
+                if ascending {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            });
+        }
+        markets
+    }
+}
+
+/// A binary market whose two outcome prices don't sum to 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArbitrageSignal {
+    pub market_id: String,
+    pub question: String,
+    pub prices: [f64; 2],
+    /// `1 - sum`: positive when buying both outcomes costs less than the $1
+    /// payout, negative when selling both collects more
+    pub edge: f64,
+}
+
+/// Open binary markets whose prices sum below `1 - fee_buffer` or above
+/// `1 + fee_buffer`, largest edge first. Markets without exactly two prices
+/// are skipped.
+pub fn find_arbitrage(markets: &[MarketInfo], fee_buffer: f64) -> Vec<ArbitrageSignal> {
+    let mut signals: Vec<ArbitrageSignal> = markets
+        .iter()
+        .filter(|m| m.active)
+        .filter_map(|m| {
+            let [yes, no] = m.prices[..] else {
+                return None;
+            };
+            let edge = 1.0 - (yes + no);
+            (edge.abs() > fee_buffer).then(|| ArbitrageSignal {
+                market_id: m.id.clone(),
+                question: m.question.clone(),
+                prices: [yes, no],
+                edge,
+            })
+        })
+        .collect();
+
+    signals.sort_by(|a, b| b.edge.abs().total_cmp(&a.edge.abs()));
+    signals
+}
+
+/// Extract the market slug from a Polymarket URL.
+///
+/// Accepts `polymarket.com/event/<event>/<market>`, `polymarket.com/market/<market>`
+/// and `polymarket.com/event/<event>`; for the last form the event slug is
+/// returned, which single-market events share with their market. The scheme
+/// and `www.` are optional, query strings and fragments are ignored.
+pub fn parse_market_url(url: &str) -> Option<String> {
+    let rest = url
+        .trim()
+        .trim_start_matches("https://")
+        .trim_start_matches("http://");
+    let rest = rest.strip_prefix("www.").unwrap_or(rest);
+    let path = rest.strip_prefix("polymarket.com/")?;
+    let path = path.split(['?', '#']).next().unwrap_or_default();
+
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+    let slug = match segments.as_slice() {
+        ["event", _, market] => *market,
+        ["event", event] | ["market", event] => *event,
+        _ => return None,
+    };
+
+    // Slugs go into a query string, so only accept what they're made of
+    slug.chars()
+        .all(|c| c.is_ascii_alphanumeric() || c == '-')
+        .then(|| slug.to_string())
+}
+
 /// Simplified market info for display
 #[derive(Debug, Clone)]
 pub struct MarketInfo {
@@ -122,14 +299,34 @@ pub struct MarketInfo {
     pub volume: String,
     pub outcomes: Vec<String>,
     pub prices: Vec<f64>,
+    /// CLOB token ID for each outcome, empty when the source doesn't provide them
+    pub token_ids: Vec<String>,
+    /// Scheduled resolution date as sent by the API (RFC 3339 or a bare date)
+    pub end_date: Option<String>,
+}
+
+impl MarketInfo {
+    /// Parsed `end_date`; a bare date is taken as midnight UTC
+    pub fn end_time(&self) -> Option<DateTime<Utc>> {
+        let end_date = self.end_date.as_deref()?;
+        if let Ok(time) = DateTime::parse_from_rfc3339(end_date) {
+            return Some(time.with_timezone(&Utc));
+        }
+        NaiveDate::parse_from_str(end_date, "%Y-%m-%d")
+            .ok()
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+            .map(|time| time.and_utc())
+    }
 }
 
 impl From<GammaMarket> for MarketInfo {
     fn from(m: GammaMarket) -> Self {
+        // Keep prices index-aligned with outcomes: a malformed price becomes
+        // 0.0 in place rather than shifting every later price down a slot
         let prices: Vec<f64> = m
             .outcome_prices
             .iter()
-            .filter_map(|p| p.parse::<f64>().ok())
+            .map(|p| p.parse::<f64>().unwrap_or(0.0))
             .collect();
 
         // Use id if condition_id is empty
@@ -147,6 +344,8 @@ impl From<GammaMarket> for MarketInfo {
             volume: m.volume,
             outcomes: m.outcomes,
             prices,
+            token_ids: m.clob_token_ids,
+            end_date: m.end_date,
         }
     }
 }
@@ -161,36 +360,408 @@ impl From<PublicSearchMarket> for MarketInfo {
             volume: m.volume.unwrap_or_else(|| "0".to_string()),
             outcomes: Vec::new(), // public-search doesn't provide outcomes
             prices: Vec::new(),   // public-search doesn't provide prices
+            token_ids: Vec::new(),
+            end_date: None,
+        }
+    }
+}
+
+/// A single price level in an order book
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct OrderBookLevel {
+    pub price: f64,
+    pub size: f64,
+}
+
+/// Order book for one outcome token, bids best-first (highest) and asks
+/// best-first (lowest)
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OrderBook {
+    pub bids: Vec<OrderBookLevel>,
+    pub asks: Vec<OrderBookLevel>,
+}
+
+impl OrderBook {
+    pub fn best_bid(&self) -> Option<f64> {
+        self.bids.first().map(|l| l.price)
+    }
+
+    pub fn best_ask(&self) -> Option<f64> {
+        self.asks.first().map(|l| l.price)
+    }
+
+    pub fn spread(&self) -> Option<f64> {
+        Some(self.best_ask()? - self.best_bid()?)
+    }
+
+    pub fn mid_price(&self) -> Option<f64> {
+        Some((self.best_ask()? + self.best_bid()?) / 2.0)
+    }
+
+    /// Horizontal ASCII histogram of cumulative depth, asks above bids, each
+    /// side split into `bins` price steps walking away from the touch. Bars are
+    /// scaled so the deepest row is `width` characters; empty for an empty book.
+    pub fn depth_chart(&self, bins: usize, width: usize) -> Vec<String> {
+        let asks = cumulative_depth(&self.asks, bins);
+        let bids = cumulative_depth(&self.bids, bins);
+        let max_depth = asks
+            .iter()
+            .chain(&bids)
+            .map(|&(_, d)| d)
+            .fold(0.0, f64::max);
+        if max_depth <= 0.0 {
+            return Vec::new();
         }
+
+        let row = |side: &str, (price, depth): (f64, f64)| {
+            let bar = ((depth / max_depth) * width as f64).round() as usize;
+            format!("{} {:.3} │{} {:.0}", side, price, "█".repeat(bar), depth)
+        };
+        let mut lines: Vec<String> = asks.into_iter().rev().map(|r| row("ask", r)).collect();
+        if let Some(spread) = self.spread() {
+            lines.push(format!("    ───── spread {:.3}", spread));
+        }
+        lines.extend(bids.into_iter().map(|r| row("bid", r)));
+        lines
     }
 }
 
+/// Cumulative size at `bins` evenly spaced prices from the best level (first)
+/// to the worst (last), as (price, depth) pairs nearest the touch first.
+/// Steps that land on the same price are merged.
+fn cumulative_depth(levels: &[OrderBookLevel], bins: usize) -> Vec<(f64, f64)> {
+    let (Some(best), Some(worst)) = (levels.first(), levels.last()) else {
+        return Vec::new();
+    };
+    let bins = bins.max(1);
+    let step = (worst.price - best.price) / bins as f64;
+
+    let mut rows: Vec<(f64, f64)> = (1..=bins)
+        .map(|i| {
+            let edge = if i == bins {
+                worst.price
+            } else {
+                best.price + step * i as f64
+            };
+            let reach = (edge - best.price).abs() + 1e-9;
+            let depth = levels
+                .iter()
+                .filter(|l| (l.price - best.price).abs() <= reach)
+                .map(|l| l.size)
+                .sum();
+            (edge, depth)
+        })
+        .collect();
+    rows.dedup_by(|a, b| (a.0 - b.0).abs() < 1e-9);
+    rows
+}
+
+/// Order book response from the CLOB `/book` endpoint, prices and sizes as strings
+#[derive(Debug, Deserialize)]
+struct ClobOrderBook {
+    #[serde(default)]
+    bids: Vec<ClobOrderBookLevel>,
+    #[serde(default)]
+    asks: Vec<ClobOrderBookLevel>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ClobOrderBookLevel {
+    price: String,
+    size: String,
+}
+
+impl From<ClobOrderBook> for OrderBook {
+    fn from(book: ClobOrderBook) -> Self {
+        fn levels(raw: Vec<ClobOrderBookLevel>) -> Vec<OrderBookLevel> {
+            raw.into_iter()
+                .filter_map(|l| {
+                    Some(OrderBookLevel {
+                        price: l.price.parse().ok()?,
+                        size: l.size.parse().ok()?,
+                    })
+                })
+                .collect()
+        }
+
+        // The CLOB doesn't promise an order, so sort best-first explicitly
+        let mut bids = levels(book.bids);
+        let mut asks = levels(book.asks);
+        bids.sort_by(|a, b| b.price.total_cmp(&a.price));
+        asks.sort_by(|a, b| a.price.total_cmp(&b.price));
+
+        Self { bids, asks }
+    }
+}
+
+/// Errors returned by `MarketService` requests
+#[derive(Debug, thiserror::Error)]
+pub enum MarketServiceError {
+    #[error("HTTP request failed: {0}")]
+    Http(reqwest::Error),
+    #[error("request timed out")]
+    Timeout,
+    #[error("rate limited by the Polymarket API")]
+    RateLimited,
+    #[error("not found")]
+    NotFound,
+    #[error("failed to parse API response: {0}")]
+    Parse(#[from] serde_json::Error),
+    #[error("unexpected HTTP status {0}")]
+    Status(u16),
+}
+
+impl From<reqwest::Error> for MarketServiceError {
+    fn from(e: reqwest::Error) -> Self {
+        if e.is_timeout() {
+            Self::Timeout
+        } else {
+            Self::Http(e)
+        }
+    }
+}
+
+/// Consecutive failed API calls before the connection is reported as down
+const DISCONNECTED_AFTER_FAILURES: u32 = 3;
+
+/// API connection state derived from recent calls
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConnectionState {
+    /// No API call has been made yet
+    Idle,
+    Connected,
+    /// Recent calls failed, but not enough to give up on the connection
+    Reconnecting,
+    Disconnected,
+}
+
+/// Outcome of recent API calls, for status displays
+#[derive(Debug, Clone, Copy)]
+pub struct ConnectionStatus {
+    pub state: ConnectionState,
+    /// Round-trip time of the last successful call
+    pub latency: Option<Duration>,
+}
+
+/// Result of pinging one API endpoint with `check_connectivity`
+#[derive(Debug, Clone)]
+pub struct EndpointCheck {
+    pub name: &'static str,
+    pub url: String,
+    /// HTTP status of the response, or why no response arrived
+    pub result: Result<u16, String>,
+    pub latency: Duration,
+}
+
+#[derive(Debug, Default)]
+struct CallHealth {
+    calls: u64,
+    consecutive_failures: u32,
+    last_latency: Option<Duration>,
+}
+
+/// Connect and request timeout used when none is configured
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Request rate used when none is configured
+const DEFAULT_REQUESTS_PER_SECOND: f64 = 5.0;
+
+/// Requests `get_markets` keeps in flight at once. The rate limiter still
+/// spaces their start times; this only bounds how many wait on responses.
+const MAX_CONCURRENT_REQUESTS: usize = 4;
+
+/// Spaces requests evenly at a fixed rate. Callers over the limit wait for
+/// their slot instead of failing; the async mutex hands slots out in order.
+#[derive(Debug)]
+struct RateLimiter {
+    interval: Duration,
+    next_slot: tokio::sync::Mutex<Instant>,
+}
+
+impl RateLimiter {
+    fn new(requests_per_second: f64) -> Self {
+        Self {
+            interval: Duration::from_secs_f64(1.0 / requests_per_second),
+            next_slot: tokio::sync::Mutex::new(Instant::now()),
+        }
+    }
+
+    /// Wait until the next request is allowed to fire
+    async fn acquire(&self) {
+        let wait = {
+            let mut next_slot = self.next_slot.lock().await;
+            let now = Instant::now();
+            let slot = (*next_slot).max(now);
+            *next_slot = slot + self.interval;
+            slot - now
+        };
+
+        if !wait.is_zero() {
+            tokio::time::sleep(wait).await;
+        }
+    }
+}
+
+/// Build the HTTP client shared by every Polymarket API caller. Reusing one
+/// client keeps connections alive between requests instead of opening (and
+/// TLS-handshaking) a new one each time. Both timeouts are required so a
+/// stalled connection fails instead of hanging its caller.
+pub fn build_http_client(
+    connect_timeout: Duration,
+    request_timeout: Duration,
+) -> reqwest::Result<reqwest::Client> {
+    reqwest::Client::builder()
+        .user_agent(concat!(
+            env!("CARGO_PKG_NAME"),
+            "/",
+            env!("CARGO_PKG_VERSION")
+        ))
+        .connect_timeout(connect_timeout)
+        .timeout(request_timeout)
+        .build()
+}
+
 /// Market service for fetching markets from Polymarket
 pub struct MarketService {
     client: reqwest::Client,
+    health: Mutex<CallHealth>,
+    limiter: RateLimiter,
+    recorder: Option<Arc<SessionRecorder>>,
+    gamma_base: String,
+    clob_base: String,
 }
 
 impl MarketService {
     pub fn new() -> Self {
+        let client = build_http_client(DEFAULT_TIMEOUT, DEFAULT_TIMEOUT).unwrap_or_default();
+        Self::with_client(client)
+    }
+
+    /// Create a service on top of an existing (usually shared) HTTP client
+    pub fn with_client(client: reqwest::Client) -> Self {
         Self {
-            client: reqwest::Client::new(),
+            client,
+            health: Mutex::new(CallHealth::default()),
+            limiter: RateLimiter::new(DEFAULT_REQUESTS_PER_SECOND),
+            recorder: None,
+            gamma_base: GAMMA_API_BASE.to_string(),
+            clob_base: CLOB_API_BASE.to_string(),
+        }
+    }
+
+    /// Talk to other Gamma and CLOB servers, e.g. a local mock in tests
+    #[cfg(test)]
+    pub(crate) fn with_base_urls(mut self, gamma_base: &str, clob_base: &str) -> Self {
+        self.gamma_base = gamma_base.to_string();
+        self.clob_base = clob_base.to_string();
+        self
+    }
+
+    /// Send at most `requests_per_second` API requests
+    pub fn with_rate_limit(mut self, requests_per_second: f64) -> Self {
+        self.limiter = RateLimiter::new(requests_per_second);
+        self
+    }
+
+    /// Append every market and order book fetched by ID to `recorder`
+    pub fn with_recorder(mut self, recorder: SessionRecorder) -> Self {
+        self.recorder = Some(Arc::new(recorder));
+        self
+    }
+
+    /// Total API calls made, successful or not
+    pub fn api_calls(&self) -> u64 {
+        self.health.lock().unwrap_or_else(|e| e.into_inner()).calls
+    }
+
+    /// Connection state and latency based on the most recent API calls
+    pub fn connection_status(&self) -> ConnectionStatus {
+        let health = self.health.lock().unwrap_or_else(|e| e.into_inner());
+        let state = match health.consecutive_failures {
+            _ if health.calls == 0 => ConnectionState::Idle,
+            0 => ConnectionState::Connected,
+            n if n < DISCONNECTED_AFTER_FAILURES => ConnectionState::Reconnecting,
+            _ => ConnectionState::Disconnected,
+        };
+        ConnectionStatus {
+            state,
+            latency: health.last_latency,
+        }
+    }
+
+    /// Ping the Gamma and CLOB APIs concurrently and time each round trip.
+    /// Any HTTP response counts as reachable, as in the onboarding check.
+    /// Bypasses the rate limiter and doesn't touch `connection_status`.
+    pub async fn check_connectivity(&self) -> Vec<EndpointCheck> {
+        let endpoints = [
+            ("Gamma API", &self.gamma_base),
+            ("CLOB API", &self.clob_base),
+        ];
+        futures::future::join_all(endpoints.into_iter().map(|(name, url)| async move {
+            let started = Instant::now();
+            let result = self.client.get(url).send().await;
+            EndpointCheck {
+                name,
+                url: url.clone(),
+                result: result
+                    .map(|response| response.status().as_u16())
+                    .map_err(|e| e.to_string()),
+                latency: started.elapsed(),
+            }
+        }))
+        .await
+    }
+
+    /// GET a URL, recording whether it succeeded and how long it took
+    async fn get(&self, url: &str) -> reqwest::Result<reqwest::Response> {
+        self.limiter.acquire().await;
+
+        let started = Instant::now();
+        let result = self.client.get(url).send().await;
+
+        let mut health = self.health.lock().unwrap_or_else(|e| e.into_inner());
+        health.calls += 1;
+        match result {
+            Ok(ref response) if response.status().is_success() => {
+                health.consecutive_failures = 0;
+                health.last_latency = Some(started.elapsed());
+            }
+            _ => health.consecutive_failures += 1,
         }
+
+        result
+    }
+
+    /// GET a URL and decode its JSON body, mapping failures to typed errors
+    async fn get_json<T: DeserializeOwned>(&self, url: &str) -> Result<T, MarketServiceError> {
+        let response = self.get(url).await?;
+
+        match response.status() {
+            reqwest::StatusCode::TOO_MANY_REQUESTS => return Err(MarketServiceError::RateLimited),
+            reqwest::StatusCode::NOT_FOUND => return Err(MarketServiceError::NotFound),
+            status if !status.is_success() => {
+                return Err(MarketServiceError::Status(status.as_u16()))
+            }
+            _ => {}
+        }
+
+        let body = response.bytes().await?;
+        Ok(serde_json::from_slice(&body)?)
     }
 
     /// Search markets by keyword using /public-search
-    pub async fn search_markets(&self, keyword: &str, _limit: usize) -> Result<Vec<MarketInfo>> {
+    pub async fn search_markets(
+        &self,
+        keyword: &str,
+        _limit: usize,
+        filter: &SearchFilter,
+    ) -> Result<Vec<MarketInfo>, MarketServiceError> {
         let url = format!(
             "{}/public-search?q={}&search_profiles=false",
-            GAMMA_API_BASE, keyword
+            self.gamma_base, keyword
         );
 
-        let response = self.client.get(&url).send().await?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch markets: {}", response.status());
-        }
-
-        let search_response: PublicSearchResponse = response.json().await?;
+        let search_response: PublicSearchResponse = self.get_json(&url).await?;
 
         // Flatten events -> markets
         let markets: Vec<PublicSearchMarket> = search_response
@@ -200,53 +771,119 @@ impl MarketService {
             .collect();
 
         // Filter valid CLOB markets that are open and convert
-        let filtered: Vec<MarketInfo> = markets
+        let open: Vec<MarketInfo> = markets
             .into_iter()
             .filter(|m| m.enable_order_book && !m.closed)
             .map(|m| m.into())
-            .take(20)
             .collect();
 
-        Ok(filtered)
+        // Filter and sort before capping so the cap keeps the best matches
+        Ok(filter.apply(open).into_iter().take(20).collect())
     }
 
-    /// Fetch featured/trending markets
-    pub async fn get_trending_markets(&self, limit: usize) -> Result<Vec<MarketInfo>> {
-        let url = format!(
-            "{}/markets?limit={}&closed=false&active=true&order=volume&ascending=false",
-            GAMMA_API_BASE, limit
-        );
-
-        let response = self.client.get(&url).send().await?;
-
-        if !response.status().is_success() {
-            anyhow::bail!("Failed to fetch markets: {}", response.status());
+    /// Top `limit` open markets by volume. With `min_liquidity`, only those
+    /// with at least that much USDC of liquidity, so they can actually be
+    /// traded; Gamma applies the floor before the limit.
+    pub async fn get_trending_markets(
+        &self,
+        limit: usize,
+        min_liquidity: Option<f64>,
+    ) -> Result<Vec<MarketInfo>, MarketServiceError> {
+        let mut url = format!("{}/markets?limit={}&", self.gamma_base, limit);
+        if let Some(floor) = min_liquidity {
+            url.push_str(&format!("liquidity_num_min={}&", floor));
         }
+        url.push_str("closed=false&active=true&order=volume&ascending=false");
 
-        let markets: Vec<GammaMarket> = response.json().await?;
+        let GammaMarkets(markets) = self.get_json(&url).await?;
 
+        // Gamma already filtered by liquidity; checked again in case it didn't
+        let floor = min_liquidity.unwrap_or(0.0);
         let filtered: Vec<MarketInfo> = markets
             .into_iter()
-            .filter(|m| m.enable_order_book)
+            .filter(|m| m.enable_order_book && parse_volume(&m.liquidity) >= floor)
+            .take(limit)
             .map(|m| m.into())
             .collect();
 
         Ok(filtered)
     }
 
-    /// Get market by ID
-    pub async fn get_market(&self, condition_id: &str) -> Result<Option<MarketInfo>> {
-        let url = format!("{}/markets?id={}", GAMMA_API_BASE, condition_id);
+    /// Get market by ID, `None` if the API doesn't know it
+    pub async fn get_market(
+        &self,
+        condition_id: &str,
+    ) -> Result<Option<MarketInfo>, MarketServiceError> {
+        let url = format!("{}/markets?id={}", self.gamma_base, condition_id);
 
-        let response = self.client.get(&url).send().await?;
+        let markets = match self.get_json(&url).await {
+            Ok(GammaMarkets(markets)) => markets,
+            Err(MarketServiceError::NotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        };
+        let market: Option<MarketInfo> = markets.into_iter().next().map(|m| m.into());
 
-        if !response.status().is_success() {
-            return Ok(None);
+        if let (Some(recorder), Some(market)) = (&self.recorder, &market) {
+            recorder.record(&Sample::Volume {
+                timestamp_ms: Utc::now().timestamp_millis(),
+                market_id: market.id.clone(),
+                volume: parse_volume(&market.volume),
+            });
         }
+        Ok(market)
+    }
 
-        let markets: Vec<GammaMarket> = response.json().await?;
+    /// Look a market up by its URL slug. Returns `Ok(None)` if no market has it.
+    pub async fn get_market_by_slug(
+        &self,
+        slug: &str,
+    ) -> Result<Option<MarketInfo>, MarketServiceError> {
+        let url = format!("{}/markets?slug={}", self.gamma_base, slug);
+
+        let markets = match self.get_json(&url).await {
+            Ok(GammaMarkets(markets)) => markets,
+            Err(MarketServiceError::NotFound) => return Ok(None),
+            Err(e) => return Err(e),
+        };
         Ok(markets.into_iter().next().map(|m| m.into()))
     }
+
+    /// Fetch several markets concurrently, at most `MAX_CONCURRENT_REQUESTS` at a
+    /// time. Results come back in input order, one per ID, so a failing market
+    /// doesn't affect the others.
+    pub async fn get_markets(
+        &self,
+        condition_ids: &[String],
+    ) -> Vec<Result<Option<MarketInfo>, MarketServiceError>> {
+        let permits = tokio::sync::Semaphore::new(MAX_CONCURRENT_REQUESTS);
+        let requests = condition_ids.iter().map(|id| async {
+            // The semaphore is never closed, so acquiring can't fail
+            let _permit = permits.acquire().await.expect("semaphore closed");
+            self.get_market(id).await
+        });
+        futures::future::join_all(requests).await
+    }
+
+    /// Get the CLOB order book for an outcome token of `market_id`. The market
+    /// is only used to label the recorded sample.
+    pub async fn get_order_book(
+        &self,
+        market_id: &str,
+        token_id: &str,
+    ) -> Result<OrderBook, MarketServiceError> {
+        let url = format!("{}/book?token_id={}", self.clob_base, token_id);
+        let book: OrderBook = self.get_json::<ClobOrderBook>(&url).await?.into();
+
+        if let Some(ref recorder) = self.recorder {
+            recorder.record(&Sample::OrderBook {
+                timestamp_ms: Utc::now().timestamp_millis(),
+                market_id: market_id.to_string(),
+                token_id: token_id.to_string(),
+                book: book.clone(),
+            });
+        }
+        Ok(book)
+    }
 }
 
 impl Default for MarketService {
@@ -270,9 +907,16 @@ pub async fn save_watched_market(pool: &DbPool, market: &MarketInfo) -> Result<(
 
     sqlx::query(
         r#"
-        INSERT OR REPLACE INTO watched_markets 
+        INSERT INTO watched_markets 
         (id, question, volume, outcomes, prices, joined_at, active)
         VALUES (?, ?, ?, ?, ?, ?, 1)
+        ON CONFLICT(id) DO UPDATE SET
+            question = excluded.question,
+            volume = excluded.volume,
+            outcomes = excluded.outcomes,
+            prices = excluded.prices,
+            joined_at = excluded.joined_at,
+            active = 1
         "#,
     )
     .bind(&market.id)
@@ -319,6 +963,8 @@ pub async fn load_watched_markets(pool: &DbPool) -> Result<Vec<MarketInfo>> {
             volume,
             outcomes,
             prices,
+            token_ids: Vec::new(),
+            end_date: None,
         });
     }
 
@@ -340,3 +986,542 @@ pub async fn remove_watched_market(pool: &DbPool, id: &str) -> Result<()> {
 
     Ok(())
 }
+
+/// Re-activate a market removed with `remove_watched_market`, keeping its
+/// saved data. Returns false if the market was never watched.
+pub async fn restore_watched_market(pool: &DbPool, id: &str) -> Result<bool> {
+    let result = sqlx::query("UPDATE watched_markets SET active = 1 WHERE id = ?")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Mark a watched market as a favorite or not. Favorites survive leaving and
+/// rejoining the market.
+pub async fn set_favorite_market(pool: &DbPool, id: &str, favorite: bool) -> Result<()> {
+    sqlx::query("UPDATE watched_markets SET favorite = ? WHERE id = ?")
+        .bind(favorite)
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// IDs of the active watched markets marked as favorites
+pub async fn load_favorite_markets(pool: &DbPool) -> Result<HashSet<String>> {
+    let ids: Vec<String> =
+        sqlx::query_scalar("SELECT id FROM watched_markets WHERE active = 1 AND favorite = 1")
+            .fetch_all(pool)
+            .await?;
+
+    Ok(ids.into_iter().collect())
+}
+
+/// Set the note on a market, or clear it when `note` is blank
+pub async fn save_market_note(pool: &DbPool, market_id: &str, note: &str) -> Result<()> {
+    let note = note.trim();
+    if note.is_empty() {
+        sqlx::query("DELETE FROM market_notes WHERE market_id = ?")
+            .bind(market_id)
+            .execute(pool)
+            .await?;
+        return Ok(());
+    }
+
+    sqlx::query(
+        r#"
+        INSERT INTO market_notes (market_id, note, updated_at)
+        VALUES (?, ?, ?)
+        ON CONFLICT(market_id) DO UPDATE SET
+            note = excluded.note,
+            updated_at = excluded.updated_at
+        "#,
+    )
+    .bind(market_id)
+    .bind(note)
+    .bind(Utc::now().timestamp())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Every saved note, keyed by market ID
+pub async fn load_market_notes(pool: &DbPool) -> Result<HashMap<String, String>> {
+    let notes: Vec<(String, String)> = sqlx::query_as("SELECT market_id, note FROM market_notes")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(notes.into_iter().collect())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::database::init_database;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+    /// Serve canned JSON bodies on a local port, picking the first route whose
+    /// prefix matches the request path (404 otherwise). Returns the base URL.
+    async fn mock_server(routes: Vec<(&'static str, String)>) -> String {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let base = format!("http://{}", listener.local_addr().unwrap());
+
+        tokio::spawn(async move {
+            while let Ok((mut socket, _)) = listener.accept().await {
+                let mut request = vec![0; 8192];
+                let read = socket.read(&mut request).await.unwrap_or(0);
+                let request = String::from_utf8_lossy(&request[..read]);
+                let path = request.split_whitespace().nth(1).unwrap_or("/");
+
+                let (status, body) = routes
+                    .iter()
+                    .find(|(prefix, _)| path.starts_with(prefix))
+                    .map_or(("404 Not Found", ""), |(_, body)| ("200 OK", body));
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                let _ = socket.write_all(response.as_bytes()).await;
+            }
+        });
+        base
+    }
+
+    pub(crate) async fn mock_service(routes: Vec<(&'static str, String)>) -> MarketService {
+        let base = mock_server(routes).await;
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        MarketService::with_client(client).with_base_urls(&base, &base)
+    }
+
+    #[tokio::test]
+    async fn test_search_keeps_open_clob_markets() {
+        let body = serde_json::json!({
+            "events": [
+                {"markets": [
+                    {"id": "1", "question": "Open CLOB market", "volume": "1500", "closed": false, "enableOrderBook": true},
+                    {"id": "2", "question": "Closed market", "volume": "9000", "closed": true, "enableOrderBook": true},
+                ]},
+                {"markets": [
+                    {"id": "3", "question": "AMM-only market", "volume": "9000", "closed": false, "enableOrderBook": false},
+                    {"id": "4", "question": "No volume field", "enableOrderBook": true},
+                ]},
+            ]
+        });
+        let service = mock_service(vec![("/public-search", body.to_string())]).await;
+
+        let markets = service
+            .search_markets("election", 20, &SearchFilter::default())
+            .await
+            .unwrap();
+        let ids: Vec<&str> = markets.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["1", "4"]);
+        assert_eq!(markets[1].volume, "0");
+        // Search results carry no prices until enriched with get_market
+        assert!(markets[0].outcomes.is_empty() && markets[0].prices.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_gamma_markets_parse_encoded_and_plain_arrays() {
+        // Gamma sends these arrays as JSON-encoded strings
+        let encoded = serde_json::json!([{
+            "id": "10",
+            "conditionId": "0xabc",
+            "question": "Encoded?",
+            "active": true,
+            "enableOrderBook": true,
+            "volume": "2500.5",
+            "liquidity": "5000",
+            "outcomes": "[\"Yes\", \"No\"]",
+            "outcomePrices": "[\"0.65\", \"0.35\"]",
+            "clobTokenIds": "[\"111\", \"222\"]",
+        }]);
+        let plain = serde_json::json!([
+            {
+                "id": "20",
+                "question": "Plain arrays, liquid",
+                "active": true,
+                "enableOrderBook": true,
+                "volume": "9000",
+                "liquidity": "2000",
+                "outcomes": ["Yes", "No"],
+                "outcomePrices": [0.2, "bad"],
+            },
+            {
+                "id": "21",
+                "question": "Illiquid",
+                "active": true,
+                "enableOrderBook": true,
+                "volume": "8000",
+                "liquidity": "10",
+            },
+        ]);
+        let service = mock_service(vec![
+            ("/markets?id=", encoded.to_string()),
+            // Only answers when the floor is passed on to Gamma
+            (
+                "/markets?limit=10&liquidity_num_min=1000&",
+                plain.to_string(),
+            ),
+        ])
+        .await;
+
+        let market = service.get_market("0xabc").await.unwrap().unwrap();
+        assert_eq!(market.id, "0xabc");
+        assert_eq!(market.outcomes, vec!["Yes", "No"]);
+        assert_eq!(market.prices, vec![0.65, 0.35]);
+        assert_eq!(market.token_ids, vec!["111", "222"]);
+
+        let trending = service
+            .get_trending_markets(10, Some(1000.0))
+            .await
+            .unwrap();
+        assert_eq!(trending.len(), 1);
+        assert_eq!(trending[0].id, "20");
+        // A bad price stays in its slot as 0.0
+        assert_eq!(trending[0].prices, vec![0.2, 0.0]);
+    }
+
+    #[test]
+    fn test_gamma_markets_accept_every_response_shape() {
+        let ids = |json: &str| -> Vec<String> {
+            let GammaMarkets(markets) = serde_json::from_str(json).unwrap();
+            markets.into_iter().map(|m| m.id).collect()
+        };
+        assert_eq!(ids(r#"[{"id": "1"}, {"id": "2"}]"#), vec!["1", "2"]);
+        assert_eq!(ids(r#"{"id": "1", "question": "Single?"}"#), vec!["1"]);
+        assert_eq!(
+            ids(r#"{"data": [{"id": "1"}, {"id": "2"}]}"#),
+            vec!["1", "2"]
+        );
+        assert!(ids(r#"{"data": null}"#).is_empty());
+        assert!(ids("[]").is_empty());
+
+        let err = serde_json::from_str::<GammaMarkets>(r#""oops""#).unwrap_err();
+        assert!(err.to_string().contains("expected a market"), "{}", err);
+        let err = serde_json::from_str::<GammaMarkets>(r#"{"data": 5}"#).unwrap_err();
+        assert!(err.to_string().contains("`data`"), "{}", err);
+        let err = serde_json::from_str::<GammaMarkets>(r#"{"error": "rate limited"}"#).unwrap_err();
+        assert!(err.to_string().contains("rate limited"), "{}", err);
+    }
+
+    #[tokio::test]
+    async fn test_market_lookups_handle_single_and_wrapped_responses() {
+        let single = serde_json::json!({
+            "id": "30",
+            "conditionId": "0xsingle",
+            "question": "Single object?",
+            "outcomes": "[\"Yes\", \"No\"]",
+            "outcomePrices": "[\"0.4\", \"0.6\"]",
+        });
+        let wrapped = serde_json::json!({
+            "data": [
+                {"id": "40", "question": "Wrapped", "enableOrderBook": true, "liquidity": "5000"},
+                {"id": "41", "question": "Wrapped too", "enableOrderBook": true, "liquidity": "5000"},
+            ]
+        });
+        let service = mock_service(vec![
+            ("/markets?id=", single.to_string()),
+            ("/markets?limit=", wrapped.to_string()),
+        ])
+        .await;
+
+        let market = service.get_market("0xsingle").await.unwrap().unwrap();
+        assert_eq!(market.id, "0xsingle");
+        assert_eq!(market.prices, vec![0.4, 0.6]);
+
+        let trending = service.get_trending_markets(10, None).await.unwrap();
+        let ids: Vec<&str> = trending.iter().map(|m| m.id.as_str()).collect();
+        assert_eq!(ids, vec!["40", "41"]);
+        // Never more than asked for
+        let trending = service.get_trending_markets(1, None).await.unwrap();
+        assert_eq!(trending.len(), 1);
+    }
+
+    #[test]
+    fn test_find_arbitrage_flags_mispriced_pairs() {
+        let market = |id: &str, prices: Vec<f64>| MarketInfo {
+            id: id.to_string(),
+            question: format!("Question {}", id),
+            active: true,
+            order_book_enabled: true,
+            volume: "0".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            prices,
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+        let markets = vec![
+            market("fair", vec![0.6, 0.41]),
+            market("cheap", vec![0.45, 0.5]),
+            market("rich", vec![0.7, 0.4]),
+            market("multi", vec![0.2, 0.2, 0.2]),
+        ];
+
+        let signals = find_arbitrage(&markets, 0.02);
+        let ids: Vec<&str> = signals.iter().map(|s| s.market_id.as_str()).collect();
+        assert_eq!(ids, vec!["rich", "cheap"]);
+        assert!((signals[0].edge + 0.1).abs() < 1e-9);
+        assert!((signals[1].edge - 0.05).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_depth_chart_is_cumulative_and_scaled() {
+        let level = |price, size| OrderBookLevel { price, size };
+        let book = OrderBook {
+            bids: vec![level(0.50, 100.0), level(0.48, 100.0), level(0.46, 200.0)],
+            asks: vec![level(0.52, 50.0), level(0.56, 150.0)],
+        };
+
+        let chart = book.depth_chart(2, 20);
+        assert_eq!(
+            chart,
+            vec![
+                format!("ask 0.560 │{} 200", "█".repeat(10)),
+                format!("ask 0.540 │{} 50", "█".repeat(3)),
+                "    ───── spread 0.020".to_string(),
+                format!("bid 0.480 │{} 200", "█".repeat(10)),
+                format!("bid 0.460 │{} 400", "█".repeat(20)),
+            ]
+        );
+        assert!(OrderBook::default().depth_chart(5, 20).is_empty());
+    }
+
+    #[test]
+    fn test_parse_volume() {
+        assert_eq!(parse_volume("1.2M"), 1_200_000.0);
+        assert_eq!(parse_volume("1,234"), 1_234.0);
+        assert_eq!(parse_volume("0"), 0.0);
+        assert_eq!(parse_volume(""), 0.0);
+        assert_eq!(parse_volume("1234567.89"), 1_234_567.89);
+        assert_eq!(parse_volume("$3.5k"), 3_500.0);
+        assert_eq!(parse_volume("2B"), 2_000_000_000.0);
+        assert_eq!(parse_volume("n/a"), 0.0);
+    }
+
+    #[tokio::test]
+    async fn test_watched_market_round_trip_on_fresh_db() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let pool = init_database(path.to_str().unwrap()).await.unwrap();
+
+        let market = MarketInfo {
+            id: "market_1".to_string(),
+            question: "Will it rain tomorrow?".to_string(),
+            active: true,
+            order_book_enabled: true,
+            volume: "1234.5".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            prices: vec![0.6, 0.4],
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+
+        save_watched_market(&pool, &market).await.unwrap();
+        let loaded = load_watched_markets(&pool).await.unwrap();
+        assert_eq!(loaded.len(), 1);
+        assert_eq!(loaded[0].id, market.id);
+        assert_eq!(loaded[0].question, market.question);
+        assert_eq!(loaded[0].outcomes, market.outcomes);
+        assert_eq!(loaded[0].prices, market.prices);
+
+        set_favorite_market(&pool, &market.id, true).await.unwrap();
+        let favorites = load_favorite_markets(&pool).await.unwrap();
+        assert!(favorites.contains(&market.id));
+
+        remove_watched_market(&pool, &market.id).await.unwrap();
+        assert!(load_watched_markets(&pool).await.unwrap().is_empty());
+        assert!(load_favorite_markets(&pool).await.unwrap().is_empty());
+
+        // Rejoining keeps the favorite flag
+        save_watched_market(&pool, &market).await.unwrap();
+        assert!(load_favorite_markets(&pool)
+            .await
+            .unwrap()
+            .contains(&market.id));
+    }
+
+    #[tokio::test]
+    async fn test_market_notes_set_replace_and_clear() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let pool = init_database(path.to_str().unwrap()).await.unwrap();
+
+        save_market_note(&pool, "market_1", "Watching for the debate")
+            .await
+            .unwrap();
+        save_market_note(&pool, "market_1", "  Polls moved after the debate ")
+            .await
+            .unwrap();
+        save_market_note(&pool, "market_2", "Thin book")
+            .await
+            .unwrap();
+
+        let notes = load_market_notes(&pool).await.unwrap();
+        assert_eq!(notes.len(), 2);
+        assert_eq!(notes["market_1"], "Polls moved after the debate");
+
+        save_market_note(&pool, "market_1", " ").await.unwrap();
+        let notes = load_market_notes(&pool).await.unwrap();
+        assert_eq!(notes.keys().collect::<Vec<_>>(), vec!["market_2"]);
+    }
+
+    #[tokio::test]
+    async fn test_rate_limiter_spaces_requests() {
+        let limiter = RateLimiter::new(20.0);
+        let started = Instant::now();
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        // First request fires immediately, the next two wait 50ms each
+        assert!(started.elapsed() >= Duration::from_millis(100));
+    }
+
+    #[tokio::test]
+    async fn test_stalled_request_times_out() {
+        // A server that accepts connections but never responds
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            let mut connections = Vec::new();
+            while let Ok((socket, _)) = listener.accept().await {
+                connections.push(socket);
+            }
+        });
+
+        let client = build_http_client(Duration::from_secs(1), Duration::from_millis(200)).unwrap();
+        let service = MarketService::with_client(client);
+
+        let started = Instant::now();
+        let result = service
+            .get_json::<serde_json::Value>(&format!("http://{}/markets", addr))
+            .await;
+
+        assert!(matches!(result, Err(MarketServiceError::Timeout)));
+        assert!(started.elapsed() < Duration::from_secs(2));
+    }
+
+    #[test]
+    fn test_order_book_sorted_best_first() {
+        let raw: ClobOrderBook = serde_json::from_str(
+            r#"{
+                "bids": [{"price": "0.45", "size": "10"}, {"price": "0.48", "size": "5"}],
+                "asks": [{"price": "0.55", "size": "7"}, {"price": "0.52", "size": "3"}]
+            }"#,
+        )
+        .unwrap();
+
+        let book = OrderBook::from(raw);
+        assert_eq!(book.best_bid(), Some(0.48));
+        assert_eq!(book.best_ask(), Some(0.52));
+        assert!((book.spread().unwrap() - 0.04).abs() < 1e-9);
+        assert!((book.mid_price().unwrap() - 0.50).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_search_filter() {
+        let market = |id: &str, volume: &str| MarketInfo {
+            id: id.to_string(),
+            question: String::new(),
+            active: true,
+            order_book_enabled: true,
+            volume: volume.to_string(),
+            outcomes: Vec::new(),
+            prices: Vec::new(),
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+        let markets = vec![
+            market("small", "500"),
+            market("big", "25000.5"),
+            market("unknown", ""),
+            market("mid", "12000"),
+        ];
+        let ids = |markets: Vec<MarketInfo>| -> Vec<String> {
+            markets.into_iter().map(|m| m.id).collect()
+        };
+
+        assert_eq!(
+            ids(SearchFilter::default().apply(markets.clone())),
+            vec!["small", "big", "unknown", "mid"]
+        );
+        assert_eq!(
+            ids(SearchFilter {
+                min_volume: Some(10000.0),
+                volume_ascending: Some(false),
+            }
+            .apply(markets.clone())),
+            vec!["big", "mid"]
+        );
+        assert_eq!(
+            ids(SearchFilter {
+                min_volume: None,
+                volume_ascending: Some(true),
+            }
+            .apply(markets)),
+            vec!["unknown", "small", "mid", "big"]
+        );
+    }
+
+    #[test]
+    fn test_parse_market_url() {
+        assert_eq!(
+            parse_market_url("https://polymarket.com/event/fed-decision/will-the-fed-cut?tid=1"),
+            Some("will-the-fed-cut".to_string())
+        );
+        assert_eq!(
+            parse_market_url("polymarket.com/event/btc-100k-2025"),
+            Some("btc-100k-2025".to_string())
+        );
+        assert_eq!(
+            parse_market_url("https://www.polymarket.com/market/btc-100k-2025/#comments"),
+            Some("btc-100k-2025".to_string())
+        );
+        assert_eq!(parse_market_url("https://example.com/event/btc"), None);
+        assert_eq!(parse_market_url("https://polymarket.com/profile/abc"), None);
+        assert_eq!(
+            parse_market_url("https://polymarket.com/event/a&limit=5"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_end_time_parsing() {
+        let mut market = MarketInfo::from(serde_json::from_str::<GammaMarket>("{}").unwrap());
+        assert_eq!(market.end_time(), None);
+
+        market.end_date = Some("2024-11-05T12:00:00Z".to_string());
+        assert_eq!(
+            market.end_time().unwrap().to_rfc3339(),
+            "2024-11-05T12:00:00+00:00"
+        );
+
+        market.end_date = Some("2024-11-05".to_string());
+        assert_eq!(
+            market.end_time().unwrap().to_rfc3339(),
+            "2024-11-05T00:00:00+00:00"
+        );
+    }
+
+    #[test]
+    fn test_malformed_price_keeps_outcomes_aligned() {
+        let gamma: GammaMarket = serde_json::from_str(
+            r#"{
+                "id": "1",
+                "question": "Who wins?",
+                "outcomes": "[\"A\", \"B\", \"C\"]",
+                "outcomePrices": "[\"0.2\", \"n/a\", \"0.5\"]"
+            }"#,
+        )
+        .unwrap();
+
+        let market = MarketInfo::from(gamma);
+        assert_eq!(market.outcomes, vec!["A", "B", "C"]);
+        assert_eq!(market.prices, vec![0.2, 0.0, 0.5]);
+    }
+}
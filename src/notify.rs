@@ -0,0 +1,108 @@
+//! Outbound alert notifications
+//!
+//! Spike events from the event bus are POSTed to an optional webhook so alerts
+//! reach you away from the terminal. The body carries both `content` (Discord) and `text` (Slack)
+//! alongside the raw event, so either service accepts it as-is.
+
+use crate::events::BotEvent;
+use crate::spike_detection::{AlertDebouncer, ALERT_DEBOUNCE};
+use crate::types::VolumeVelocityEvent;
+use reqwest::Client;
+use serde_json::{json, Value};
+use std::time::Instant;
+use tokio::sync::broadcast::{self, error::RecvError};
+use tokio::task::JoinHandle;
+
+/// Sends spike alerts to `ALERT_WEBHOOK_URL`
+#[derive(Clone)]
+pub struct WebhookNotifier {
+    client: Client,
+    url: String,
+}
+
+impl WebhookNotifier {
+    pub fn new(client: Client, url: String) -> Self {
+        Self { client, url }
+    }
+
+    /// Forward spikes published on `events` until the bus closes, at most one
+    /// per market every `ALERT_DEBOUNCE`
+    pub fn spawn(self, mut events: broadcast::Receiver<BotEvent>) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut alerts = AlertDebouncer::new(ALERT_DEBOUNCE);
+            loop {
+                match events.recv().await {
+                    Ok(BotEvent::Spike(event)) => {
+                        if alerts.should_alert(&event.market_id, Instant::now()) {
+                            self.notify_spike(&event);
+                        }
+                    }
+                    Ok(_) => {}
+                    Err(RecvError::Lagged(missed)) => {
+                        tracing::warn!("Spike webhook fell behind, skipped {} event(s)", missed)
+                    }
+                    Err(RecvError::Closed) => break,
+                }
+            }
+        })
+    }
+
+    /// POST the event in a background task. Never blocks the caller; failures
+    /// are only logged.
+    pub fn notify_spike(&self, event: &VolumeVelocityEvent) {
+        let client = self.client.clone();
+        let url = self.url.clone();
+        let body = spike_payload(event);
+        let market_id = event.market_id.clone();
+
+        tokio::spawn(async move {
+            let result = client
+                .post(&url)
+                .json(&body)
+                .send()
+                .await
+                .and_then(|response| response.error_for_status());
+            if let Err(e) = result {
+                tracing::warn!("Spike webhook for {} failed: {}", market_id, e);
+            }
+        });
+    }
+}
+
+/// Discord/Slack-compatible message with the event attached
+fn spike_payload(event: &VolumeVelocityEvent) -> Value {
+    let message = format!(
+        "🚨 Volume spike on {}: {:+.2} vol/sec ({:+.2} over {:.0}s)",
+        event.market_id, event.velocity, event.volume_delta, event.time_delta
+    );
+    json!({
+        "content": message,
+        "text": message,
+        "event": event,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_spike_payload_is_chat_compatible() {
+        let event = VolumeVelocityEvent {
+            market_id: "market_1".to_string(),
+            velocity: 1500.0,
+            volume_delta: 3000.0,
+            time_delta: 2.0,
+            timestamp: 1_700_000_000,
+        };
+        let payload = spike_payload(&event);
+
+        assert_eq!(payload["content"], payload["text"]);
+        assert!(payload["content"]
+            .as_str()
+            .unwrap()
+            .contains("market_1: +1500.00 vol/sec"));
+        assert_eq!(payload["event"]["velocity"], 1500.0);
+        assert_eq!(payload["event"]["timestamp"], 1_700_000_000);
+    }
+}
@@ -2,15 +2,44 @@
 //!
 //! This module handles:
 //! - Environment validation (private key, database)
+//! - Network connectivity to the Polymarket APIs
+//! - On-chain USDC balance check
 //! - Dynamic CLOB authentication using the SDK
 
+use crate::config::Config;
+use anyhow::{Context, Result};
 use colored::*;
+use ethers::providers::{Http, Middleware, Provider};
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::{transaction::eip2718::TypedTransaction, Address, TransactionRequest, U256};
 use std::env;
 use std::fs;
 use std::io;
+use std::path::Path;
+use std::str::FromStr;
+use std::time::Duration;
 
-/// Run onboarding checks to ensure the user has all required configuration
-pub fn run_onboarding_checks() -> Result<(), OnboardingError> {
+/// Polymarket endpoints the bot depends on
+const POLYMARKET_ENDPOINTS: [&str; 2] = [
+    "https://gamma-api.polymarket.com",
+    "https://clob.polymarket.com",
+];
+
+/// Public Polygon RPC used when `RPC_URL` is not set
+const DEFAULT_RPC_URL: &str = "https://polygon-rpc.com";
+
+/// Bridged USDC (USDC.e) on Polygon, the collateral token used by Polymarket
+const USDC_ADDRESS: &str = "0x2791Bca1f2de4661ED88A30C99A7a9449Aa84174";
+
+/// USDC has 6 decimals
+const USDC_DECIMALS: i32 = 6;
+
+/// ERC-20 `balanceOf(address)` selector
+const BALANCE_OF_SELECTOR: [u8; 4] = [0x70, 0xa0, 0x82, 0x31];
+
+/// Run onboarding checks to ensure the user has all required configuration,
+/// using the database at `database_path`
+pub fn run_onboarding_checks(database_path: &str) -> Result<(), OnboardingError> {
     println!("{}", "=".repeat(60).bright_cyan());
     println!(
         "{}",
@@ -25,7 +54,7 @@ pub fn run_onboarding_checks() -> Result<(), OnboardingError> {
     check_private_key()?;
 
     // Check 2: Database Permissions
-    check_database_permissions()?;
+    check_database_permissions(database_path)?;
 
     println!("{}", "✓ All configuration checks passed!".green().bold());
     println!();
@@ -45,32 +74,157 @@ fn check_private_key() -> Result<(), OnboardingError> {
     Ok(())
 }
 
-fn check_database_permissions() -> Result<(), OnboardingError> {
-    let db_path = env::var("DATABASE_PATH").unwrap_or_else(|_| "./bot_history.db".to_string());
+/// Make sure the database file can be created at `db_path`, creating its
+/// parent directory first if it doesn't exist yet
+fn check_database_permissions(db_path: &str) -> Result<(), OnboardingError> {
+    if let Some(parent) = Path::new(db_path).parent() {
+        if !parent.as_os_str().is_empty() && !parent.exists() {
+            match fs::create_dir_all(parent) {
+                Ok(()) => println!(
+                    "{} Created database directory {}",
+                    "✓".green(),
+                    parent.display()
+                ),
+                Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
+                    return Err(OnboardingError::DatabasePermissionDenied(
+                        parent.display().to_string(),
+                    ));
+                }
+                Err(e) => {
+                    return Err(OnboardingError::DatabaseError(format!(
+                        "could not create directory {}: {}",
+                        parent.display(),
+                        e
+                    )));
+                }
+            }
+        }
+    }
 
     // Try to create/open the database file to check permissions
     match fs::OpenOptions::new()
         .write(true)
         .create(true)
-        .open(&db_path)
+        .truncate(false)
+        .open(db_path)
     {
         Ok(_) => {
             println!("{} Database permissions OK", "✓".green());
             Ok(())
         }
-        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => {
-            Err(OnboardingError::DatabasePermissionDenied(db_path))
+        Err(e) if e.kind() == io::ErrorKind::PermissionDenied => Err(
+            OnboardingError::DatabasePermissionDenied(db_path.to_string()),
+        ),
+        Err(e) => Err(OnboardingError::DatabaseError(format!(
+            "{}: {}",
+            db_path, e
+        ))),
+    }
+}
+
+/// Check that the Gamma and CLOB APIs respond within a short timeout.
+///
+/// Any HTTP response counts as reachable; only connection failures and
+/// timeouts are reported. Callers treat the error as a warning.
+pub async fn check_network_connectivity() -> Result<(), OnboardingError> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(5))
+        .build()
+        .map_err(|_| OnboardingError::NetworkUnreachable(POLYMARKET_ENDPOINTS.to_vec()))?;
+
+    let mut unreachable = Vec::new();
+    for endpoint in POLYMARKET_ENDPOINTS {
+        match client.get(endpoint).send().await {
+            Ok(_) => println!("{} Reachable: {}", "✓".green(), endpoint),
+            Err(_) => unreachable.push(endpoint),
         }
-        Err(e) => Err(OnboardingError::DatabaseError(e.to_string())),
+    }
+
+    if unreachable.is_empty() {
+        Ok(())
+    } else {
+        Err(OnboardingError::NetworkUnreachable(unreachable))
     }
 }
 
+/// Warn when the wallet holds too little USDC to place the smallest allowed order.
+///
+/// This never blocks startup: RPC failures are reported as warnings, and the
+/// check is skipped entirely when `SKIP_BALANCE_CHECK=true`.
+pub async fn check_usdc_balance(config: &Config) {
+    if config.skip_balance_check {
+        println!("{} USDC balance check skipped", "-".yellow());
+        return;
+    }
+
+    let rpc_url = config.rpc_url.as_deref().unwrap_or(DEFAULT_RPC_URL);
+    let lookup = tokio::time::timeout(
+        Duration::from_secs(10),
+        fetch_usdc_balance(&config.private_key, rpc_url),
+    )
+    .await;
+
+    match lookup {
+        Ok(Ok(balance)) if balance <= 0.0 => {
+            println!("{} USDC balance is zero", "⚠".yellow());
+            println!(
+                "   {}",
+                "Deposit USDC on Polygon before trading, or set SKIP_BALANCE_CHECK=true".yellow()
+            );
+        }
+        Ok(Ok(balance)) if balance < config.min_order_size => {
+            println!(
+                "{} USDC balance ${:.2} is below MIN_ORDER_SIZE (${:.2})",
+                "⚠".yellow(),
+                balance,
+                config.min_order_size
+            );
+        }
+        Ok(Ok(balance)) => {
+            println!("{} USDC balance: ${:.2}", "✓".green(), balance);
+        }
+        Ok(Err(e)) => {
+            println!("{} Could not check USDC balance: {}", "⚠".yellow(), e);
+        }
+        Err(_) => {
+            println!(
+                "{} Could not check USDC balance: RPC timed out ({})",
+                "⚠".yellow(),
+                rpc_url
+            );
+        }
+    }
+}
+
+/// Query the wallet's USDC balance via an `eth_call` to the token contract
+async fn fetch_usdc_balance(private_key: &str, rpc_url: &str) -> Result<f64> {
+    let wallet = LocalWallet::from_str(private_key).context("Failed to parse private key")?;
+    let provider = Provider::<Http>::try_from(rpc_url).context("Invalid RPC_URL")?;
+    let usdc = Address::from_str(USDC_ADDRESS)?;
+
+    // balanceOf(address): selector followed by the address left-padded to 32 bytes
+    let mut calldata = BALANCE_OF_SELECTOR.to_vec();
+    calldata.extend_from_slice(&[0u8; 12]);
+    calldata.extend_from_slice(wallet.address().as_bytes());
+
+    let tx: TypedTransaction = TransactionRequest::new().to(usdc).data(calldata).into();
+    let raw = provider
+        .call(&tx, None)
+        .await
+        .context("balanceOf call failed")?;
+
+    let balance = U256::from_big_endian(&raw);
+    Ok(balance.low_u128() as f64 / 10f64.powi(USDC_DECIMALS))
+}
+
 #[derive(Debug)]
 pub enum OnboardingError {
     MissingPrivateKey,
     InvalidPrivateKey,
     DatabasePermissionDenied(String),
     DatabaseError(String),
+    NetworkUnreachable(Vec<&'static str>),
+    AuthenticationFailed(String),
 }
 
 impl std::fmt::Display for OnboardingError {
@@ -107,6 +261,13 @@ impl std::fmt::Display for OnboardingError {
                     "{}",
                     "TIP: You can copy '.env.example' as a template!".yellow()
                 )?;
+                writeln!(
+                    f,
+                    "{} {} {}",
+                    "TIP: Or run with".yellow(),
+                    "--setup".cyan(),
+                    "to create '.env' step by step.".yellow()
+                )?;
                 writeln!(f, "{}", "-".repeat(60).red())?;
                 Ok(())
             }
@@ -156,6 +317,68 @@ impl std::fmt::Display for OnboardingError {
                 writeln!(f, "{}", "-".repeat(60).red())?;
                 Ok(())
             }
+            OnboardingError::NetworkUnreachable(endpoints) => {
+                writeln!(f)?;
+                writeln!(
+                    f,
+                    "{}",
+                    "[!] NETWORK WARNING: Polymarket API Unreachable"
+                        .yellow()
+                        .bold()
+                )?;
+                writeln!(f, "{}", "-".repeat(60).yellow())?;
+                writeln!(f, "Could not reach the following endpoints:")?;
+                for endpoint in endpoints {
+                    writeln!(f, "- {}", endpoint.yellow())?;
+                }
+                writeln!(f)?;
+                writeln!(f, "{}", ">> TRY:".yellow().bold())?;
+                writeln!(f, "- Check your internet connection")?;
+                writeln!(
+                    f,
+                    "- If you are behind a proxy, set {}",
+                    "HTTPS_PROXY".cyan()
+                )?;
+                writeln!(
+                    f,
+                    "- Polymarket is geo-restricted in some regions; check your VPN"
+                )?;
+                writeln!(f)?;
+                writeln!(
+                    f,
+                    "Continuing anyway; market data will fail until the network recovers."
+                )?;
+                writeln!(f, "{}", "-".repeat(60).yellow())?;
+                Ok(())
+            }
+            OnboardingError::AuthenticationFailed(err) => {
+                writeln!(f)?;
+                writeln!(
+                    f,
+                    "{}",
+                    "[!] AUTHENTICATION ERROR: CLOB Login Failed".red().bold()
+                )?;
+                writeln!(f, "{}", "-".repeat(60).red())?;
+                writeln!(f, "Error: {}", err)?;
+                writeln!(f)?;
+                writeln!(f, "{}", ">> DIAGNOSIS:".yellow().bold())?;
+                writeln!(
+                    f,
+                    "- Is {} the key of the wallet you use on Polymarket?",
+                    "POLYMARKET_PK".cyan()
+                )?;
+                writeln!(f, "- Is your internet connection working?")?;
+                writeln!(f)?;
+                writeln!(f, "{}", ">> TRY:".yellow().bold())?;
+                writeln!(f, "- Re-export the private key from your wallet")?;
+                writeln!(
+                    f,
+                    "- Run with {} to start without authenticating",
+                    "--offline".cyan()
+                )?;
+                writeln!(f, "{}", "-".repeat(60).red())?;
+                Ok(())
+            }
             OnboardingError::DatabaseError(err) => {
                 writeln!(f)?;
                 writeln!(f, "{}", "[!] DATABASE ERROR".red().bold())?;
@@ -169,3 +392,17 @@ impl std::fmt::Display for OnboardingError {
 }
 
 impl std::error::Error for OnboardingError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_database_check_creates_parent_directory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("data").join("nested").join("bot.db");
+
+        check_database_permissions(path.to_str().unwrap()).unwrap();
+        assert!(path.exists());
+    }
+}
@@ -0,0 +1,158 @@
+//! Line-based command prompt
+//!
+//! A lightweight alternative to the TUI for terminals where a full-screen
+//! interface is unwanted. Commands are the same ones accepted by the TUI
+//! command bar and are executed through the same `App` state.
+
+use crate::database::DbPool;
+use crate::execution::ExecutionEngine;
+use crate::markets::MarketService;
+use crate::spike_detection::SpikeDetector;
+use crate::tui::{App, DepthView, LogLevel};
+use anyhow::Result;
+use colored::*;
+use std::io::Write;
+use std::sync::Arc;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::sync::{watch, Mutex};
+
+const PROMPT: &str = "polymarket> ";
+
+/// Run the interactive prompt until `/exit`, end of input, or `shutdown` fires
+pub async fn run_repl(
+    db_pool: DbPool,
+    execution_engine: Arc<ExecutionEngine>,
+    spike_detector: Arc<Mutex<SpikeDetector>>,
+    market_service: MarketService,
+    wallet_address: Option<String>,
+    mut shutdown: watch::Receiver<bool>,
+) -> Result<()> {
+    let mut app = App::new(db_pool, execution_engine, spike_detector, market_service);
+    app.wallet_address = wallet_address;
+    // Drop the TUI's keyboard hints, they don't apply here
+    app.logs.clear();
+    app.init_watched_markets().await;
+
+    println!(
+        "{}",
+        "Polymarket Bot Summer - type /help for commands, /exit to quit".bright_cyan()
+    );
+    if app.paper_trading {
+        println!(
+            "{}",
+            "PAPER TRADING - orders are simulated".bright_magenta()
+        );
+    }
+    if let Some(ref address) = app.wallet_address {
+        println!("Wallet: {}", address.yellow());
+    }
+
+    print_new_logs(&mut app);
+
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    loop {
+        print!("{}", PROMPT.bright_green());
+        std::io::stdout().flush()?;
+
+        let line = tokio::select! {
+            line = lines.next_line() => line?,
+            _ = shutdown.changed() => break,
+        };
+
+        // End of input (Ctrl-D or a closed pipe)
+        let Some(line) = line else {
+            println!();
+            break;
+        };
+
+        let command = line.trim();
+        if command.is_empty() {
+            continue;
+        }
+
+        if matches!(command, "/exit" | "exit" | "/quit" | "quit") {
+            break;
+        }
+
+        app.execute_command(command).await;
+        app.drain_bot_events();
+        print_new_logs(&mut app);
+
+        // The TUI shows results in the Markets tab; here we list them inline
+        let cmd = command.split_whitespace().next().unwrap_or_default();
+        if matches!(
+            cmd.to_lowercase().as_str(),
+            "/search" | "search" | "/s" | "s" | "/trending" | "trending" | "/t" | "t"
+        ) {
+            print_markets(&app);
+        }
+        if matches!(cmd.to_lowercase().as_str(), "/depth" | "depth" | "/d") {
+            if let Some(depth) = app.depth.take() {
+                print_depth(&depth);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Print log entries added since the last call. Printed entries are dropped
+/// from the buffer since the prompt has no scrollback of its own.
+fn print_new_logs(app: &mut App) {
+    for log in app.logs.drain(..) {
+        let message = match log.level {
+            LogLevel::Info => log.message.normal(),
+            LogLevel::Success => log.message.green(),
+            LogLevel::Warning => log.message.yellow(),
+            LogLevel::Error => log.message.red(),
+        };
+        println!("{} {}", format!("[{}]", log.timestamp).dimmed(), message);
+    }
+}
+
+/// Print an order book as side-by-side columns, bids in green and asks in red
+fn print_depth(depth: &DepthView) {
+    println!("{} ({})", depth.question.bold(), depth.outcome);
+    println!(
+        "{:>10} {:>12}   {:<10} {:<12}",
+        "Bid", "Size", "Ask", "Size"
+    );
+
+    let rows = depth.book.bids.len().max(depth.book.asks.len());
+    for i in 0..rows {
+        let bid = depth.book.bids.get(i).map_or(format!("{:>23}", ""), |l| {
+            format!("{:>10.3} {:>12.2}", l.price, l.size)
+        });
+        let ask = depth.book.asks.get(i).map_or(String::new(), |l| {
+            format!("{:<10.3} {:<12.2}", l.price, l.size)
+        });
+        println!("{}   {}", bid.green(), ask.red());
+    }
+
+    if let (Some(spread), Some(mid)) = (depth.book.spread(), depth.book.mid_price()) {
+        println!("Spread {:.3}  Mid {:.3}", spread, mid);
+    }
+}
+
+fn print_markets(app: &App) {
+    for (i, market) in app.available_markets.iter().enumerate() {
+        let joined = if app.joined_markets.contains(&market.id) {
+            " ✓".green()
+        } else {
+            "".normal()
+        };
+        let prices = market
+            .prices
+            .iter()
+            .map(|p| format!("{:.0}%", p * 100.0))
+            .collect::<Vec<_>>()
+            .join("/");
+        println!(
+            "{:>3}. {} {}{}",
+            i + 1,
+            market.question,
+            format!("[{}]", prices).cyan(),
+            joined
+        );
+    }
+}
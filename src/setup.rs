@@ -0,0 +1,253 @@
+//! Interactive first-run setup
+//!
+//! `--setup` walks a new user through the values onboarding would otherwise
+//! complain about (private key, optional CLOB API credentials, database path)
+//! and writes them to a `.env` file, instead of making them craft it by hand.
+//! Secrets are read without echo and the file is only readable by its owner.
+
+use crate::config::DEFAULT_DATABASE_PATH;
+use anyhow::{bail, Context, Result};
+use colored::*;
+use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use crossterm::terminal;
+use ethers::signers::{LocalWallet, Signer};
+use ethers::types::Address;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+/// Everything the wizard asks for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SetupAnswers {
+    pub private_key: String,
+    pub clob_api_key: Option<String>,
+    pub clob_api_secret: Option<String>,
+    pub clob_passphrase: Option<String>,
+    pub database_path: String,
+}
+
+/// Prompt for the configuration and write it to `env_path`. An existing file
+/// is only replaced after the user confirms.
+pub fn run_setup_wizard(env_path: &Path) -> Result<()> {
+    println!("{}", "=".repeat(60).bright_cyan());
+    println!("{}", "  Polymarket Bot Summer - Setup".bright_cyan().bold());
+    println!("{}", "=".repeat(60).bright_cyan());
+    println!();
+
+    if env_path.exists() {
+        let answer = prompt(
+            &format!("{} already exists. Overwrite it? [y/N]", env_path.display()),
+            None,
+        )?;
+        if !matches!(answer.to_lowercase().as_str(), "y" | "yes") {
+            println!("Setup cancelled, {} left unchanged", env_path.display());
+            return Ok(());
+        }
+    }
+
+    println!("Export the private key of your Polymarket wallet (MetaMask/Rabby → Account");
+    println!("Details → Export Private Key). Nothing is shown as you type or paste it.");
+    let private_key = loop {
+        let key = read_secret("Private key (0x...): ")?;
+        match wallet_address(&key) {
+            Ok(address) => {
+                println!("{} Wallet {:?}", "✓".green(), address);
+                break key.trim().to_string();
+            }
+            Err(reason) => println!("{} {}", "✗".red(), reason),
+        }
+    };
+    println!();
+
+    println!("CLOB API credentials are optional: the bot derives them from the private");
+    println!("key at startup. Leave them blank to skip.");
+    let clob_api_key = optional(prompt("CLOB API key", None)?);
+    let clob_api_secret = optional(read_secret("CLOB API secret: ")?);
+    let clob_passphrase = optional(read_secret("CLOB passphrase: ")?);
+    println!();
+
+    let database_path = prompt("Database path", Some(DEFAULT_DATABASE_PATH))?;
+
+    let answers = SetupAnswers {
+        private_key,
+        clob_api_key,
+        clob_api_secret,
+        clob_passphrase,
+        database_path,
+    };
+    write_env_file(env_path, &render_env(&answers))
+        .with_context(|| format!("Failed to write {}", env_path.display()))?;
+
+    println!();
+    println!(
+        "{} Wrote {}. Run the bot again to start.",
+        "✓".green(),
+        env_path.display()
+    );
+    Ok(())
+}
+
+/// The `.env` contents for `answers`; blank CLOB credentials are left out
+pub fn render_env(answers: &SetupAnswers) -> String {
+    let mut env = String::from("# Written by --setup. See .env.example for every option.\n\n");
+    env.push_str(&format!("POLYMARKET_PK={}\n", answers.private_key));
+
+    let credentials = [
+        ("CLOB_API_KEY", &answers.clob_api_key),
+        ("CLOB_API_SECRET", &answers.clob_api_secret),
+        ("CLOB_PASSPHRASE", &answers.clob_passphrase),
+    ];
+    for (name, value) in credentials {
+        if let Some(value) = value {
+            env.push_str(&format!("{}={}\n", name, value));
+        }
+    }
+
+    env.push_str(&format!("DATABASE_PATH={}\n", answers.database_path));
+    env
+}
+
+/// Write `contents` to `path`, readable and writable only by the owner on
+/// Unix since it holds the private key. An existing file is tightened too.
+fn write_env_file(path: &Path, contents: &str) -> io::Result<()> {
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+        options.mode(0o600);
+        let file = options.open(path)?;
+        // `mode` only applies when the file is created
+        file.set_permissions(fs::Permissions::from_mode(0o600))?;
+        write_all(file, contents)
+    }
+    #[cfg(not(unix))]
+    write_all(options.open(path)?, contents)
+}
+
+fn write_all(mut file: fs::File, contents: &str) -> io::Result<()> {
+    file.write_all(contents.as_bytes())?;
+    file.sync_all()
+}
+
+/// Address of the wallet `key` belongs to, or why it isn't a usable key
+fn wallet_address(key: &str) -> Result<Address, String> {
+    let key = key.trim();
+    let Some(hex) = key.strip_prefix("0x") else {
+        return Err("The private key must start with 0x".to_string());
+    };
+    if hex.len() != 64 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err("The private key must be 0x followed by 64 hex digits".to_string());
+    }
+    LocalWallet::from_str(hex)
+        .map(|wallet| wallet.address())
+        .map_err(|e| format!("Not a valid private key: {}", e))
+}
+
+fn optional(value: String) -> Option<String> {
+    (!value.is_empty()).then_some(value)
+}
+
+/// Read a line, returning `default` when it is left blank
+fn prompt(label: &str, default: Option<&str>) -> Result<String> {
+    match default {
+        Some(default) => print!("{} [{}]: ", label, default),
+        None => print!("{}: ", label),
+    }
+    io::stdout().flush()?;
+
+    let mut line = String::new();
+    if io::stdin().read_line(&mut line)? == 0 {
+        bail!("Setup cancelled: end of input");
+    }
+    let line = line.trim();
+    Ok(match default {
+        Some(default) if line.is_empty() => default.to_string(),
+        _ => line.to_string(),
+    })
+}
+
+/// Read a line without displaying what is typed, for the private key and
+/// other credentials. Piped input (which isn't echoed anyway) is read as a
+/// plain line.
+pub fn read_secret(label: &str) -> Result<String> {
+    if !io::stdin().is_terminal() {
+        return prompt(label.trim_end().trim_end_matches(':'), None);
+    }
+
+    print!("{}", label);
+    io::stdout().flush()?;
+
+    terminal::enable_raw_mode()?;
+    let result = read_secret_keys();
+    terminal::disable_raw_mode()?;
+    println!();
+    result.map(|value| value.trim().to_string())
+}
+
+fn read_secret_keys() -> Result<String> {
+    let mut value = String::new();
+    loop {
+        let Event::Key(key) = event::read()? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+        match key.code {
+            KeyCode::Enter => return Ok(value),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                bail!("Setup cancelled")
+            }
+            KeyCode::Backspace => {
+                value.pop();
+            }
+            KeyCode::Char(c) => value.push(c),
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_setup_validates_key_and_renders_env() {
+        let key = format!("0x{}", "1".repeat(64));
+        assert!(wallet_address(&key).is_ok());
+        assert!(wallet_address(&"1".repeat(64)).is_err());
+        assert!(wallet_address(&format!("0x{}", "1".repeat(62))).is_err());
+        assert!(wallet_address(&format!("0x{}", "g".repeat(64))).is_err());
+
+        let answers = SetupAnswers {
+            private_key: key.clone(),
+            clob_api_key: Some("api-key".to_string()),
+            clob_api_secret: None,
+            clob_passphrase: None,
+            database_path: "./bot.db".to_string(),
+        };
+        let env = render_env(&answers);
+        assert!(env.contains(&format!("POLYMARKET_PK={}\n", key)));
+        assert!(env.contains("CLOB_API_KEY=api-key\n"));
+        assert!(!env.contains("CLOB_API_SECRET"));
+        assert!(env.ends_with("DATABASE_PATH=./bot.db\n"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_env_file_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(".env");
+        fs::write(&path, "OLD=1\n").unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o644)).unwrap();
+
+        write_env_file(&path, "POLYMARKET_PK=0x1\n").unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "POLYMARKET_PK=0x1\n");
+        let mode = fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+    }
+}
@@ -1,8 +1,16 @@
-use crate::database::DbPool;
+use crate::database::{BatchedWrite, DbPool, WriteBatcher};
+use crate::events::BotEvent;
+use crate::markets::OrderBook;
 use crate::types::VolumeVelocityEvent;
 use anyhow::Result;
 use chrono::Utc;
 use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::broadcast;
+
+/// Minimum time between two alerts for the same market
+pub const ALERT_DEBOUNCE: Duration = Duration::from_secs(60);
 
 /// Spike detection algorithms for identifying trading opportunities
 pub struct SpikeDetector {
@@ -12,12 +20,30 @@ pub struct SpikeDetector {
     // Configuration thresholds
     volume_velocity_threshold: f64,
     obi_threshold: f64,
+    // Spikes found since startup
+    spikes_detected: u64,
+    // Where detected spikes are published, if anyone listens
+    events: Option<broadcast::Sender<BotEvent>>,
+    // Per-market overrides of the thresholds above
+    market_thresholds: HashMap<String, MarketThresholds>,
+    // Queues event and snapshot rows instead of writing each one immediately
+    write_batcher: Option<Arc<WriteBatcher>>,
+}
+
+/// Thresholds set for a single market; `None` falls back to the global value
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MarketThresholds {
+    pub velocity: Option<f64>,
+    pub obi: Option<f64>,
 }
 
 #[derive(Debug, Clone)]
 struct VolumeHistory {
     last_volume: f64,
-    last_timestamp: i64,
+    // Milliseconds, so sub-second polls still see elapsed time
+    last_timestamp_ms: i64,
+    // Velocity between the last two observations, if there have been two
+    velocity: Option<f64>,
 }
 
 impl SpikeDetector {
@@ -27,9 +53,147 @@ impl SpikeDetector {
             volume_history: HashMap::new(),
             volume_velocity_threshold,
             obi_threshold,
+            spikes_detected: 0,
+            events: None,
+            market_thresholds: HashMap::new(),
+            write_batcher: None,
         }
     }
-    
+
+    /// Publish every detected spike on the event bus
+    pub fn with_events(mut self, events: broadcast::Sender<BotEvent>) -> Self {
+        self.events = Some(events);
+        self
+    }
+
+    /// Store velocity events in `db` instead, such as a scratch database for a
+    /// replay. Thresholds already loaded are kept.
+    pub fn with_database(mut self, db: DbPool) -> Self {
+        self.db = db;
+        self
+    }
+
+    /// Queue velocity events and order book snapshots on `batcher` rather
+    /// than writing each one as it happens
+    pub fn with_write_batcher(mut self, batcher: Arc<WriteBatcher>) -> Self {
+        self.write_batcher = Some(batcher);
+        self
+    }
+
+    pub fn volume_velocity_threshold(&self) -> f64 {
+        self.volume_velocity_threshold
+    }
+
+    pub fn obi_threshold(&self) -> f64 {
+        self.obi_threshold
+    }
+
+    /// Number of velocity spikes detected since startup
+    pub fn spikes_detected(&self) -> u64 {
+        self.spikes_detected
+    }
+
+    /// Change the velocity threshold at runtime; it must be positive
+    pub fn set_volume_velocity_threshold(&mut self, threshold: f64) -> Result<()> {
+        validate_velocity_threshold(threshold)?;
+        self.volume_velocity_threshold = threshold;
+        Ok(())
+    }
+
+    /// Change the OBI threshold at runtime; it must be between -1 and 1
+    pub fn set_obi_threshold(&mut self, threshold: f64) -> Result<()> {
+        validate_obi_threshold(threshold)?;
+        self.obi_threshold = threshold;
+        Ok(())
+    }
+
+    /// Velocity threshold for `market_id`, its override if it has one
+    pub fn velocity_threshold_for(&self, market_id: &str) -> f64 {
+        self.market_thresholds
+            .get(market_id)
+            .and_then(|t| t.velocity)
+            .unwrap_or(self.volume_velocity_threshold)
+    }
+
+    /// OBI threshold for `market_id`, its override if it has one
+    pub fn obi_threshold_for(&self, market_id: &str) -> f64 {
+        self.market_thresholds
+            .get(market_id)
+            .and_then(|t| t.obi)
+            .unwrap_or(self.obi_threshold)
+    }
+
+    /// Every market with at least one threshold override
+    pub fn market_thresholds(&self) -> &HashMap<String, MarketThresholds> {
+        &self.market_thresholds
+    }
+
+    /// Load per-market overrides saved on watched markets. Returns how many
+    /// markets have one.
+    pub async fn load_market_thresholds(&mut self) -> Result<usize> {
+        let rows: Vec<(String, Option<f64>, Option<f64>)> = sqlx::query_as(
+            r#"
+            SELECT id, velocity_threshold, obi_threshold
+            FROM watched_markets
+            WHERE velocity_threshold IS NOT NULL OR obi_threshold IS NOT NULL
+            "#,
+        )
+        .fetch_all(&self.db)
+        .await?;
+
+        self.market_thresholds = rows
+            .into_iter()
+            .map(|(id, velocity, obi)| (id, MarketThresholds { velocity, obi }))
+            .collect();
+        Ok(self.market_thresholds.len())
+    }
+
+    /// Override the velocity threshold for one watched market and save it
+    pub async fn set_market_velocity_threshold(
+        &mut self,
+        market_id: &str,
+        threshold: f64,
+    ) -> Result<()> {
+        validate_velocity_threshold(threshold)?;
+        let result = sqlx::query("UPDATE watched_markets SET velocity_threshold = ? WHERE id = ?")
+            .bind(threshold)
+            .bind(market_id)
+            .execute(&self.db)
+            .await?;
+        if result.rows_affected() == 0 {
+            anyhow::bail!("market {} is not watched", market_id);
+        }
+
+        self.market_thresholds
+            .entry(market_id.to_string())
+            .or_default()
+            .velocity = Some(threshold);
+        Ok(())
+    }
+
+    /// Override the OBI threshold for one watched market and save it
+    pub async fn set_market_obi_threshold(
+        &mut self,
+        market_id: &str,
+        threshold: f64,
+    ) -> Result<()> {
+        validate_obi_threshold(threshold)?;
+        let result = sqlx::query("UPDATE watched_markets SET obi_threshold = ? WHERE id = ?")
+            .bind(threshold)
+            .bind(market_id)
+            .execute(&self.db)
+            .await?;
+        if result.rows_affected() == 0 {
+            anyhow::bail!("market {} is not watched", market_id);
+        }
+
+        self.market_thresholds
+            .entry(market_id.to_string())
+            .or_default()
+            .obi = Some(threshold);
+        Ok(())
+    }
+
     /// Calculate volume velocity: V_v = Delta_Volume / Delta_t
     /// Returns true if velocity exceeds threshold
     pub async fn check_volume_velocity(
@@ -37,72 +201,117 @@ impl SpikeDetector {
         market_id: &str,
         current_volume: f64,
     ) -> Result<Option<VolumeVelocityEvent>> {
-        let now = Utc::now().timestamp();
-        
-        // Get previous state for this market
-        let event = if let Some(history) = self.volume_history.get(market_id) {
-            let volume_delta = current_volume - history.last_volume;
-            let time_delta = (now - history.last_timestamp) as f64;
-            
-            if time_delta > 0.0 {
-                let velocity = volume_delta / time_delta;
-                
-                // Check if velocity exceeds threshold
-                if velocity.abs() > self.volume_velocity_threshold {
-                    Some(VolumeVelocityEvent {
-                        market_id: market_id.to_string(),
-                        velocity,
-                        volume_delta,
-                        time_delta,
-                        timestamp: now,
-                    })
-                } else {
-                    None
-                }
-            } else {
-                None
-            }
-        } else {
-            None
-        };
-        
+        let now_ms = Utc::now().timestamp_millis();
+        self.check_volume_velocity_at(market_id, current_volume, now_ms)
+            .await
+    }
+
+    /// `check_volume_velocity` for a volume observed at `now_ms` rather than
+    /// now, used when replaying a recorded session
+    pub async fn check_volume_velocity_at(
+        &mut self,
+        market_id: &str,
+        current_volume: f64,
+        now_ms: i64,
+    ) -> Result<Option<VolumeVelocityEvent>> {
+        // Compare against the previous state for this market, if any
+        let history = self.volume_history.get(market_id);
+        let previous = history.map(|h| (h.last_volume, h.last_timestamp_ms));
+        let velocity = previous
+            .and_then(|(volume, timestamp_ms)| {
+                let time_delta = (now_ms - timestamp_ms) as f64 / 1000.0;
+                compute_velocity(volume, current_volume, time_delta)
+            })
+            .or(history.and_then(|h| h.velocity));
+        let event = detect_velocity_spike(
+            market_id,
+            previous,
+            current_volume,
+            now_ms,
+            self.velocity_threshold_for(market_id),
+        );
+
         // Update history
         self.volume_history.insert(
             market_id.to_string(),
             VolumeHistory {
                 last_volume: current_volume,
-                last_timestamp: now,
+                last_timestamp_ms: now_ms,
+                velocity,
             },
         );
-        
+
         // If we detected a spike, save it to database
         if let Some(ref evt) = event {
+            self.spikes_detected += 1;
             self.save_velocity_event(evt).await?;
+            if let Some(ref events) = self.events {
+                // No subscribers is fine
+                let _ = events.send(BotEvent::Spike(evt.clone()));
+            }
         }
-        
+
         Ok(event)
     }
-    
+
+    /// Volume velocity of `market_id` as of its latest observation, once it
+    /// has been observed twice
+    pub fn current_velocity(&self, market_id: &str) -> Option<f64> {
+        self.volume_history.get(market_id)?.velocity
+    }
+
     /// Calculate order book imbalance: OBI = (V_bids - V_asks) / (V_bids + V_asks)
     /// Returns OBI value between -1 and 1
-    pub fn calculate_order_book_imbalance(
+    pub fn calculate_order_book_imbalance(&self, bids_volume: f64, asks_volume: f64) -> f64 {
+        order_book_imbalance(bids_volume, asks_volume)
+    }
+
+    /// Check if OBI indicates a significant imbalance for `market_id`, using
+    /// its override if it has one
+    pub fn is_significant_imbalance(&self, market_id: &str, obi: f64) -> bool {
+        obi.abs() > self.obi_threshold_for(market_id)
+    }
+
+    /// Store the volumes and best prices of `book` for `market_id`, through
+    /// the write batcher when there is one
+    pub async fn record_order_book_snapshot(
         &self,
-        bids_volume: f64,
-        asks_volume: f64,
-    ) -> f64 {
-        let total_volume = bids_volume + asks_volume;
-        if total_volume == 0.0 {
-            return 0.0;
+        market_id: &str,
+        book: &OrderBook,
+    ) -> Result<()> {
+        let snapshot = BatchedWrite::OrderBookSnapshot {
+            market_id: market_id.to_string(),
+            bids_volume: book.bids.iter().map(|l| l.size).sum(),
+            asks_volume: book.asks.iter().map(|l| l.size).sum(),
+            best_bid: book.bids.iter().map(|l| l.price).reduce(f64::max),
+            best_ask: book.asks.iter().map(|l| l.price).reduce(f64::min),
+            timestamp: Utc::now().timestamp(),
+        };
+        if let Some(ref batcher) = self.write_batcher {
+            batcher.push(snapshot).await;
+            return Ok(());
         }
-        (bids_volume - asks_volume) / total_volume
+
+        crate::database::ensure_market(&self.db, market_id).await?;
+        snapshot.insert(&self.db).await
     }
-    
-    /// Check if OBI indicates a significant imbalance
-    pub fn is_significant_imbalance(&self, obi: f64) -> bool {
-        obi.abs() > self.obi_threshold
+
+    /// Where queued rows wait to be written, if batching. Flush it after
+    /// releasing the detector's lock so writes don't stall detection.
+    pub fn write_batcher(&self) -> Option<Arc<WriteBatcher>> {
+        self.write_batcher.clone()
     }
-    
+
     async fn save_velocity_event(&self, event: &VolumeVelocityEvent) -> Result<()> {
+        if let Some(ref batcher) = self.write_batcher {
+            batcher
+                .push(BatchedWrite::VelocityEvent(event.clone()))
+                .await;
+            return Ok(());
+        }
+
+        crate::database::ensure_market(&self.db, &event.market_id).await?;
+
         sqlx::query(
             r#"
             INSERT INTO volume_velocity_events 
@@ -117,35 +326,378 @@ impl SpikeDetector {
         .bind(event.timestamp)
         .execute(&self.db)
         .await?;
-        
+
         Ok(())
     }
 }
 
+fn validate_velocity_threshold(threshold: f64) -> Result<()> {
+    if !(threshold > 0.0 && threshold.is_finite()) {
+        anyhow::bail!("velocity threshold must be greater than 0");
+    }
+    Ok(())
+}
+
+fn validate_obi_threshold(threshold: f64) -> Result<()> {
+    if !(-1.0..=1.0).contains(&threshold) {
+        anyhow::bail!("OBI threshold must be between -1.0 and 1.0");
+    }
+    Ok(())
+}
+
+/// Volume velocity V_v = Delta_Volume / Delta_t, or `None` when no time has
+/// passed (or the clock went backwards) and the rate is meaningless
+pub fn compute_velocity(previous_volume: f64, current_volume: f64, time_delta: f64) -> Option<f64> {
+    if time_delta > 0.0 {
+        Some((current_volume - previous_volume) / time_delta)
+    } else {
+        None
+    }
+}
+
+/// Build a spike event when the per-second velocity since the `previous`
+/// (volume, timestamp in ms) observation exceeds `threshold` in either
+/// direction. The first observation of a market has nothing to compare against
+/// and never spikes. The event's `timestamp` is in seconds, like the database.
+pub fn detect_velocity_spike(
+    market_id: &str,
+    previous: Option<(f64, i64)>,
+    current_volume: f64,
+    now_ms: i64,
+    threshold: f64,
+) -> Option<VolumeVelocityEvent> {
+    let (previous_volume, previous_timestamp_ms) = previous?;
+    let time_delta = (now_ms - previous_timestamp_ms) as f64 / 1000.0;
+    let velocity = compute_velocity(previous_volume, current_volume, time_delta)?;
+
+    if velocity.abs() > threshold {
+        Some(VolumeVelocityEvent {
+            market_id: market_id.to_string(),
+            velocity,
+            volume_delta: current_volume - previous_volume,
+            time_delta,
+            timestamp: now_ms / 1000,
+        })
+    } else {
+        None
+    }
+}
+
+/// Order book imbalance OBI = (V_bids - V_asks) / (V_bids + V_asks), between
+/// -1 and 1; an empty book is balanced
+pub fn order_book_imbalance(bids_volume: f64, asks_volume: f64) -> f64 {
+    let total_volume = bids_volume + asks_volume;
+    if total_volume == 0.0 {
+        return 0.0;
+    }
+    (bids_volume - asks_volume) / total_volume
+}
+
+/// Delay before a market's next poll: `min` once its velocity reaches the
+/// spike threshold, growing linearly to `max` as the market goes quiet. A
+/// market with no velocity yet counts as quiet.
+pub fn adaptive_poll_interval(
+    velocity: Option<f64>,
+    velocity_threshold: f64,
+    min: Duration,
+    max: Duration,
+) -> Duration {
+    let activity = match velocity {
+        Some(_) if velocity_threshold <= 0.0 => 1.0,
+        Some(velocity) => (velocity.abs() / velocity_threshold).min(1.0),
+        None => 0.0,
+    };
+    max - (max - min).mul_f64(activity)
+}
+
+/// Suppresses repeat alerts for a market within a time window, so one
+/// sustained spike produces a single alert instead of one per poll
+#[derive(Debug)]
+pub struct AlertDebouncer {
+    window: Duration,
+    last_alert: HashMap<String, Instant>,
+}
+
+impl AlertDebouncer {
+    pub fn new(window: Duration) -> Self {
+        Self {
+            window,
+            last_alert: HashMap::new(),
+        }
+    }
+
+    /// Returns true if an alert for `market_id` should fire at `now`, and
+    /// records it as the latest alert if so
+    pub fn should_alert(&mut self, market_id: &str, now: Instant) -> bool {
+        match self.last_alert.get(market_id) {
+            Some(last) if now.duration_since(*last) < self.window => false,
+            _ => {
+                self.last_alert.insert(market_id.to_string(), now);
+                true
+            }
+        }
+    }
+}
+
+/// Load the last `limit` persisted velocity events for a market, newest first
+pub async fn load_recent_events(
+    pool: &DbPool,
+    market_id: &str,
+    limit: i64,
+) -> Result<Vec<VolumeVelocityEvent>> {
+    let rows: Vec<(String, f64, f64, f64, i64)> = sqlx::query_as(
+        r#"
+        SELECT market_id, velocity, volume_delta, time_delta, timestamp
+        FROM volume_velocity_events
+        WHERE market_id = ?
+        ORDER BY timestamp DESC, id DESC
+        LIMIT ?
+        "#,
+    )
+    .bind(market_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows
+        .into_iter()
+        .map(
+            |(market_id, velocity, volume_delta, time_delta, timestamp)| VolumeVelocityEvent {
+                market_id,
+                velocity,
+                volume_delta,
+                time_delta,
+                timestamp,
+            },
+        )
+        .collect())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    
+
+    #[test]
+    fn test_alert_debouncer_suppresses_repeats() {
+        let mut debouncer = AlertDebouncer::new(Duration::from_secs(60));
+        let start = Instant::now();
+
+        assert!(debouncer.should_alert("market_1", start));
+        assert!(!debouncer.should_alert("market_1", start + Duration::from_secs(30)));
+        // Other markets are tracked separately
+        assert!(debouncer.should_alert("market_2", start + Duration::from_secs(30)));
+        assert!(debouncer.should_alert("market_1", start + Duration::from_secs(61)));
+    }
+
     #[test]
     fn test_obi_calculation() {
-        let detector = SpikeDetector {
-            db: unimplemented!(),
-            volume_history: HashMap::new(),
-            volume_velocity_threshold: 1000.0,
-            obi_threshold: 0.3,
-        };
-        
         // Equal volumes = 0 imbalance
-        assert_eq!(detector.calculate_order_book_imbalance(100.0, 100.0), 0.0);
-        
+        assert_eq!(order_book_imbalance(100.0, 100.0), 0.0);
+
         // All bids = 1.0
-        assert_eq!(detector.calculate_order_book_imbalance(100.0, 0.0), 1.0);
-        
+        assert_eq!(order_book_imbalance(100.0, 0.0), 1.0);
+
         // All asks = -1.0
-        assert_eq!(detector.calculate_order_book_imbalance(0.0, 100.0), -1.0);
-        
+        assert_eq!(order_book_imbalance(0.0, 100.0), -1.0);
+
         // 60/40 split
-        let obi = detector.calculate_order_book_imbalance(60.0, 40.0);
+        let obi = order_book_imbalance(60.0, 40.0);
         assert!((obi - 0.2).abs() < 0.01);
+
+        // Empty book
+        assert_eq!(order_book_imbalance(0.0, 0.0), 0.0);
+    }
+
+    #[test]
+    fn test_compute_velocity() {
+        assert_eq!(compute_velocity(100.0, 400.0, 3.0), Some(100.0));
+        assert_eq!(compute_velocity(400.0, 100.0, 3.0), Some(-100.0));
+        // No elapsed time, or a clock that went backwards
+        assert_eq!(compute_velocity(100.0, 400.0, 0.0), None);
+        assert_eq!(compute_velocity(100.0, 400.0, -5.0), None);
+    }
+
+    #[test]
+    fn test_sub_second_samples_produce_velocity() {
+        // Two polls 500ms apart, as in the TUI refresh loop
+        let event = detect_velocity_spike("market_1", Some((1000.0, 1_000)), 2000.0, 1_500, 1000.0)
+            .unwrap();
+        assert_eq!(event.time_delta, 0.5);
+        assert_eq!(event.velocity, 2000.0);
+        assert_eq!(event.timestamp, 1);
+    }
+
+    #[test]
+    fn test_first_observation_never_spikes() {
+        assert!(detect_velocity_spike("market_1", None, 1_000_000.0, 10_000, 1.0).is_none());
+    }
+
+    #[test]
+    fn test_spike_threshold_boundaries() {
+        let previous = Some((0.0, 0));
+
+        // 1000 vol/sec over 1s: exactly at the threshold is not a spike
+        assert!(detect_velocity_spike("market_1", previous, 1000.0, 1000, 1000.0).is_none());
+
+        let event = detect_velocity_spike("market_1", previous, 1001.0, 1000, 1000.0).unwrap();
+        assert_eq!(event.velocity, 1001.0);
+        assert_eq!(event.volume_delta, 1001.0);
+        assert_eq!(event.time_delta, 1.0);
+        assert_eq!(event.timestamp, 1);
+
+        // Falling volume spikes too
+        let event =
+            detect_velocity_spike("market_1", Some((5000.0, 0)), 2000.0, 2000, 1000.0).unwrap();
+        assert_eq!(event.velocity, -1500.0);
+
+        // Same timestamp as the previous observation
+        assert!(detect_velocity_spike("market_1", previous, 5000.0, 0, 1000.0).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_load_recent_events_newest_first() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let db = crate::database::init_database(path.to_str().unwrap())
+            .await
+            .unwrap();
+        crate::database::ensure_market(&db, "market_1")
+            .await
+            .unwrap();
+
+        let detector = SpikeDetector::new(db.clone(), 1000.0, 0.3);
+        for timestamp in [100, 300, 200] {
+            detector
+                .save_velocity_event(&VolumeVelocityEvent {
+                    market_id: "market_1".to_string(),
+                    velocity: 1500.0,
+                    volume_delta: 1500.0,
+                    time_delta: 1.0,
+                    timestamp,
+                })
+                .await
+                .unwrap();
+        }
+
+        let events = load_recent_events(&db, "market_1", 2).await.unwrap();
+        let timestamps: Vec<i64> = events.iter().map(|e| e.timestamp).collect();
+        assert_eq!(timestamps, vec![300, 200]);
+        assert!(load_recent_events(&db, "market_2", 10)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_order_book_snapshot_without_batcher_is_written_directly() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let db = crate::database::init_database(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let book = OrderBook {
+            bids: vec![crate::markets::OrderBookLevel {
+                price: 0.48,
+                size: 30.0,
+            }],
+            asks: vec![crate::markets::OrderBookLevel {
+                price: 0.52,
+                size: 10.0,
+            }],
+        };
+        let snapshots = || async {
+            let (rows,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM orderbook_snapshots")
+                .fetch_one(&db)
+                .await
+                .unwrap();
+            rows
+        };
+
+        let detector = SpikeDetector::new(db.clone(), 1000.0, 0.3);
+        detector
+            .record_order_book_snapshot("market_1", &book)
+            .await
+            .unwrap();
+        assert_eq!(snapshots().await, 1);
+
+        // With a batcher the row waits for the next flush
+        let batcher = Arc::new(WriteBatcher::new(db.clone(), 10));
+        let detector = detector.with_write_batcher(batcher.clone());
+        detector
+            .record_order_book_snapshot("market_1", &book)
+            .await
+            .unwrap();
+        assert_eq!(snapshots().await, 1);
+        assert_eq!(batcher.flush().await.unwrap(), 1);
+        assert_eq!(snapshots().await, 2);
+    }
+
+    #[tokio::test]
+    async fn test_market_threshold_overrides_global() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("test.db");
+        let db = crate::database::init_database(path.to_str().unwrap())
+            .await
+            .unwrap();
+        let market = crate::markets::MarketInfo {
+            id: "market_1".to_string(),
+            question: "Will it rain tomorrow?".to_string(),
+            active: true,
+            order_book_enabled: true,
+            volume: "0".to_string(),
+            outcomes: Vec::new(),
+            prices: Vec::new(),
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+        crate::markets::save_watched_market(&db, &market)
+            .await
+            .unwrap();
+
+        let mut detector = SpikeDetector::new(db.clone(), 1000.0, 0.5);
+        detector
+            .set_market_velocity_threshold("market_1", 50.0)
+            .await
+            .unwrap();
+        assert!(detector
+            .set_market_velocity_threshold("unknown", 50.0)
+            .await
+            .is_err());
+
+        // Overrides survive a restart and rejoining the market
+        crate::markets::save_watched_market(&db, &market)
+            .await
+            .unwrap();
+        let mut detector = SpikeDetector::new(db, 1000.0, 0.5);
+        assert_eq!(detector.load_market_thresholds().await.unwrap(), 1);
+        assert_eq!(detector.velocity_threshold_for("market_1"), 50.0);
+        assert_eq!(detector.obi_threshold_for("market_1"), 0.5);
+        assert_eq!(detector.velocity_threshold_for("market_2"), 1000.0);
+
+        // A lower OBI override flags a book the global threshold lets through
+        detector
+            .set_market_obi_threshold("market_1", 0.2)
+            .await
+            .unwrap();
+        assert!(detector.is_significant_imbalance("market_1", 0.3));
+        assert!(!detector.is_significant_imbalance("market_2", 0.3));
+
+        // 100 vol/sec spikes market_1 but not a market on the global threshold
+        for market_id in ["market_1", "market_2"] {
+            detector
+                .check_volume_velocity_at(market_id, 0.0, 0)
+                .await
+                .unwrap();
+        }
+        assert!(detector
+            .check_volume_velocity_at("market_1", 100.0, 1_000)
+            .await
+            .unwrap()
+            .is_some());
+        assert!(detector
+            .check_volume_velocity_at("market_2", 100.0, 1_000)
+            .await
+            .unwrap()
+            .is_none());
     }
 }
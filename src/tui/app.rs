@@ -1,10 +1,77 @@
+use crate::events::BotEvent;
 use crate::execution::ExecutionEngine;
-use crate::markets::{MarketInfo, MarketService};
-use crate::types::{OrderInfo, Portfolio};
+use crate::export::{load_export_data, write_export, ExportFormat};
+use crate::markets::{
+    parse_volume, ConnectionStatus, MarketInfo, MarketService, MarketServiceError, OrderBook,
+    SearchFilter,
+};
+use crate::spike_detection::{AlertDebouncer, MarketThresholds, SpikeDetector, ALERT_DEBOUNCE};
+use crate::tui::clipboard::copy_to_clipboard;
+use crate::tui::events::AppEvent;
+use crate::tui::ui::doc_line_count;
+use crate::types::{OrderInfo, OrderType, Portfolio, VolumeVelocityEvent};
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use std::sync::Arc;
 use std::time::Instant;
+use tokio::sync::{broadcast, Mutex};
+use tokio::task::JoinHandle;
+
+/// What the TUI reads off the UI thread at startup
+struct WatchlistLoad {
+    /// Watched markets and each one's persisted spikes (or why those failed to load)
+    markets: Vec<(MarketInfo, Result<Vec<VolumeVelocityEvent>>)>,
+    favorites: std::collections::HashSet<String>,
+    notes: std::collections::HashMap<String, String>,
+}
+
+/// Number of log entries moved by PageUp/PageDown in the Logs tab
+const LOGS_PAGE_SIZE: usize = 10;
+
+/// Velocity events kept per market in the Recent Events panel
+const RECENT_EVENTS_LIMIT: usize = 10;
+
+/// OBI readings kept per market for the trend sparkline
+const OBI_HISTORY_LIMIT: usize = 20;
+
+/// How often the selected market's order book is refetched in Market Detail
+const ORDER_BOOK_REFRESH_INTERVAL: std::time::Duration = std::time::Duration::from_secs(5);
+
+/// Market Detail data older than this is flagged as stale, a few missed refreshes
+pub const STALE_DATA_AFTER: std::time::Duration =
+    std::time::Duration::from_secs(ORDER_BOOK_REFRESH_INTERVAL.as_secs() * 3);
+
+/// How long a flash banner stays over the header
+const BANNER_DURATION: std::time::Duration = std::time::Duration::from_secs(4);
+
+/// `ui_state` keys for what the TUI restores on launch
+const UI_STATE_TAB: &str = "tab";
+const UI_STATE_SELECTED_MARKET: &str = "selected_market";
+const UI_STATE_PNL_PERCENT: &str = "pnl_percent";
+
+/// A second `q` within this window of the first skips the quit confirmation
+const FAST_QUIT_WINDOW: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// Markets listed by `/top` when no count is given
+const TOP_MARKETS: usize = 5;
+
+/// Directory `/export` writes into when none is given
+const DEFAULT_EXPORT_DIR: &str = "exports";
+
+/// How long a closed market stays watched before `AUTO_REMOVE_CLOSED` drops it
+const CLOSED_MARKET_GRACE: std::time::Duration = std::time::Duration::from_secs(60 * 60);
+
+/// Keyword `/selftest` searches Gamma for; any answer, even no markets, passes
+const SELF_TEST_QUERY: &str = "election";
+
+/// Price levels per side shown by `/depth` when no count is given
+const DEPTH_LEVELS: usize = 10;
+
+/// Price steps per side in `/depthchart` when no count is given
+const DEPTH_CHART_BINS: usize = 8;
+
+/// Characters the deepest `/depthchart` bar spans
+const DEPTH_CHART_WIDTH: usize = 40;
 
 /// Available tabs in the TUI
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -15,6 +82,7 @@ pub enum Tab {
     MarketDetail,
     Logs,
     Docs,
+    Settings,
 }
 
 impl Tab {
@@ -25,18 +93,20 @@ impl Tab {
             Tab::Markets => Tab::MarketDetail,
             Tab::MarketDetail => Tab::Logs,
             Tab::Logs => Tab::Docs,
-            Tab::Docs => Tab::Dashboard,
+            Tab::Docs => Tab::Settings,
+            Tab::Settings => Tab::Dashboard,
         }
     }
 
     pub fn prev(&self) -> Self {
         match self {
-            Tab::Dashboard => Tab::Docs,
+            Tab::Dashboard => Tab::Settings,
             Tab::Orders => Tab::Dashboard,
             Tab::Markets => Tab::Orders,
             Tab::MarketDetail => Tab::Markets,
             Tab::Logs => Tab::MarketDetail,
             Tab::Docs => Tab::Logs,
+            Tab::Settings => Tab::Docs,
         }
     }
 
@@ -48,10 +118,16 @@ impl Tab {
             Tab::MarketDetail => "Market Detail",
             Tab::Logs => "Logs",
             Tab::Docs => "Docs",
+            Tab::Settings => "Settings",
         }
     }
 
-    pub fn all() -> [Tab; 6] {
+    /// Inverse of `title`, used to restore the saved tab
+    pub fn from_title(title: &str) -> Option<Self> {
+        Tab::all().into_iter().find(|tab| tab.title() == title)
+    }
+
+    pub fn all() -> [Tab; 7] {
         [
             Tab::Dashboard,
             Tab::Orders,
@@ -59,8 +135,67 @@ impl Tab {
             Tab::MarketDetail,
             Tab::Logs,
             Tab::Docs,
+            Tab::Settings,
         ]
     }
+
+    /// Keybindings shown in the `?` help overlay: the tab's own keys first,
+    /// then the ones available everywhere
+    pub fn keybindings(&self) -> Vec<(&'static str, &'static str)> {
+        let mut keys = match self {
+            Tab::Dashboard => vec![
+                ("%", "Toggle P&L between $ and % return"),
+                ("Shift-F", "Show favorites only"),
+            ],
+            Tab::Orders => vec![
+                ("↑/↓", "Select order"),
+                ("X", "Cancel selected order"),
+                ("O", "New order"),
+            ],
+            Tab::Markets => vec![
+                ("↑/↓", "Select market"),
+                ("Enter", "Join selected market"),
+                ("V / $ / A", "Sort by volume / price / name"),
+                ("Y", "Copy market ID"),
+            ],
+            Tab::MarketDetail => vec![
+                ("↑/↓", "Select watched market"),
+                ("Del/⌫", "Leave selected market"),
+                ("Ctrl-Z", "Rejoin last market left"),
+                ("f", "Toggle favorite"),
+                ("Shift-F", "Show favorites only"),
+                ("O", "New order"),
+                ("R / F5", "Refresh order book"),
+                ("Y", "Copy market ID"),
+            ],
+            Tab::Logs => vec![
+                ("↑/↓", "Scroll logs"),
+                ("PgUp/PgDn", "Scroll a page"),
+                ("Home/End", "Jump to newest / oldest"),
+                ("F", "Search logs"),
+                ("L", "Cycle level filter"),
+            ],
+            Tab::Docs => vec![
+                ("↑/↓", "Select section / scroll"),
+                ("Enter", "Open section"),
+                ("⌫/←/Esc", "Back to sections"),
+            ],
+            Tab::Settings => vec![("↑/↓", "Select setting"), ("Enter", "Edit value")],
+        };
+        keys.extend([
+            (":", "Command mode"),
+            ("S / T", "Search / trending markets"),
+            ("Tab/←/→", "Switch tabs"),
+            ("1-7", "Jump to tab"),
+            ("P / R", "Pause / resume bot"),
+            ("!", "PANIC - cancel all orders"),
+            ("E", "Export history as CSV to exports/"),
+            ("H", "Keyboard shortcuts in logs"),
+            ("?", "This help"),
+            ("Q", "Quit (Shift-Q instantly)"),
+        ]);
+        keys
+    }
 }
 
 /// Input mode for command entry
@@ -68,8 +203,44 @@ impl Tab {
 pub enum InputMode {
     Normal,
     Command,
+    LogSearch,
     QuitConfirmation,
     LeaveMarketConfirmation,
+    OrderEntry,
+    OrderConfirmation,
+    SettingsEdit,
+    HelpOverlay,
+}
+
+/// Config values that can be changed from the Settings tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SettingsField {
+    VelocityThreshold,
+    ObiThreshold,
+}
+
+impl SettingsField {
+    pub fn all() -> [SettingsField; 2] {
+        [
+            SettingsField::VelocityThreshold,
+            SettingsField::ObiThreshold,
+        ]
+    }
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            SettingsField::VelocityThreshold => "Velocity threshold",
+            SettingsField::ObiThreshold => "OBI threshold",
+        }
+    }
+
+    /// Threshold name accepted by `/setthreshold`
+    fn threshold_kind(&self) -> &'static str {
+        match self {
+            SettingsField::VelocityThreshold => "velocity",
+            SettingsField::ObiThreshold => "obi",
+        }
+    }
 }
 
 /// Quit confirmation selection
@@ -86,6 +257,61 @@ pub enum LeaveSelection {
     Yes,
 }
 
+/// Order confirmation selection
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderSelection {
+    No, // Default
+    Yes,
+}
+
+/// Order awaiting confirmation in the order modal
+#[derive(Debug, Clone)]
+pub struct PendingOrder {
+    pub market_id: String,
+    pub question: String,
+    pub side: String,
+    pub order_type: OrderType,
+    pub size: f64,
+    pub price: Option<f64>,
+    /// Index of the traded outcome in the market's outcomes. The order form
+    /// always trades the first one.
+    pub outcome: usize,
+}
+
+/// Current step of the order-entry form
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderFormStep {
+    Side,
+    Size,
+    Price,
+}
+
+/// In-progress order being entered from the TUI
+#[derive(Debug, Clone)]
+pub struct OrderForm {
+    pub market_id: String,
+    pub question: String,
+    pub step: OrderFormStep,
+    pub side: String,
+    pub size_input: String,
+    pub price_input: String,
+    pub error: Option<String>,
+}
+
+impl OrderForm {
+    pub fn new(market: &MarketInfo) -> Self {
+        Self {
+            market_id: market.id.clone(),
+            question: market.question.clone(),
+            step: OrderFormStep::Side,
+            side: "BUY".to_string(),
+            size_input: String::new(),
+            price_input: String::new(),
+            error: None,
+        }
+    }
+}
+
 /// Log entry for the logs tab
 #[derive(Debug, Clone)]
 pub struct LogEntry {
@@ -94,7 +320,7 @@ pub struct LogEntry {
     pub message: String,
 }
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum LogLevel {
     Info,
     Warning,
@@ -102,100 +328,373 @@ pub enum LogLevel {
     Success,
 }
 
-/// Market analysis data for real-time detection visualization
+impl LogLevel {
+    /// The panel level matching a `tracing` filter's default level, e.g.
+    /// "warn" or "warn,polymarket_bot_summer=debug". Trace and debug show
+    /// everything, like info; a filter with only per-target levels counts as info.
+    pub fn from_filter(filter: &str) -> LogLevel {
+        let default = filter
+            .split(',')
+            .map(str::trim)
+            .rfind(|directive| !directive.contains('='))
+            .unwrap_or_default();
+        match default.to_ascii_lowercase().as_str() {
+            "warn" | "warning" => LogLevel::Warning,
+            "error" | "off" => LogLevel::Error,
+            _ => LogLevel::Info,
+        }
+    }
+
+    /// Ordering for the minimum-level check; success is info-level
+    fn severity(self) -> u8 {
+        match self {
+            LogLevel::Info | LogLevel::Success => 0,
+            LogLevel::Warning => 1,
+            LogLevel::Error => 2,
+        }
+    }
+}
+
+/// Transient notification drawn over the header for high-priority events
 #[derive(Debug, Clone)]
+pub struct Banner {
+    pub message: String,
+    pub level: LogLevel,
+    pub expires_at: Instant,
+}
+
+/// Sort key applied to the Markets tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MarketSort {
+    /// Order returned by the API
+    None,
+    Volume,
+    Price,
+    Question,
+}
+
+impl MarketSort {
+    pub fn title(&self) -> &'static str {
+        match self {
+            MarketSort::None => "API order",
+            MarketSort::Volume => "Volume",
+            MarketSort::Price => "Price",
+            MarketSort::Question => "Question",
+        }
+    }
+
+    fn compare(&self, a: &MarketInfo, b: &MarketInfo) -> std::cmp::Ordering {
+        match self {
+            MarketSort::None => std::cmp::Ordering::Equal,
+            // Volume arrives as a string; parse it so "9" doesn't sort above "10"
+            MarketSort::Volume => {
+                let va = parse_volume(&a.volume);
+                let vb = parse_volume(&b.volume);
+                va.total_cmp(&vb)
+            }
+            MarketSort::Price => {
+                let pa = a.prices.first().copied().unwrap_or(0.0);
+                let pb = b.prices.first().copied().unwrap_or(0.0);
+                pa.total_cmp(&pb)
+            }
+            MarketSort::Question => a.question.to_lowercase().cmp(&b.question.to_lowercase()),
+        }
+    }
+}
+
+/// Level filter applied to the Logs tab
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LogFilter {
+    All,
+    ErrorsAndWarnings,
+    ErrorsOnly,
+}
+
+impl LogFilter {
+    pub fn next(&self) -> Self {
+        match self {
+            LogFilter::All => LogFilter::ErrorsAndWarnings,
+            LogFilter::ErrorsAndWarnings => LogFilter::ErrorsOnly,
+            LogFilter::ErrorsOnly => LogFilter::All,
+        }
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            LogFilter::All => "All",
+            LogFilter::ErrorsAndWarnings => "Errors+Warnings",
+            LogFilter::ErrorsOnly => "Errors",
+        }
+    }
+
+    pub fn matches(&self, level: LogLevel) -> bool {
+        match self {
+            LogFilter::All => true,
+            LogFilter::ErrorsAndWarnings => matches!(level, LogLevel::Error | LogLevel::Warning),
+            LogFilter::ErrorsOnly => level == LogLevel::Error,
+        }
+    }
+}
+
+/// Market analysis data for real-time detection visualization
+#[derive(Debug, Clone, Default)]
 pub struct MarketAnalysis {
     pub volume_history: Vec<(i64, f64)>, // timestamp, volume
     pub current_velocity: Option<f64>,
     pub current_obi: Option<f64>,
+    /// Latest OBI readings, oldest first, at most `OBI_HISTORY_LIMIT`
+    pub obi_history: std::collections::VecDeque<f64>,
     pub recent_events: Vec<crate::types::VolumeVelocityEvent>,
+    /// Latest order book, only fetched while the market is selected
+    pub order_book: Option<OrderBook>,
+    /// When the market or its order book was last fetched successfully
+    pub last_updated: Option<Instant>,
+    /// When the market is next refetched, `None` until it is first polled
+    pub next_poll: Option<Instant>,
 }
 
-impl Default for MarketAnalysis {
-    fn default() -> Self {
-        Self {
-            volume_history: Vec::new(),
-            current_velocity: None,
-            current_obi: None,
-            recent_events: Vec::new(),
+impl MarketAnalysis {
+    /// Make `obi` the current reading and add it to the history, dropping the
+    /// oldest reading once the history is full
+    pub fn record_obi(&mut self, obi: f64) {
+        self.current_obi = Some(obi);
+        self.obi_history.push_back(obi);
+        while self.obi_history.len() > OBI_HISTORY_LIMIT {
+            self.obi_history.pop_front();
         }
     }
+
+    /// Time since the last successful fetch, `None` before the first one
+    pub fn data_age(&self) -> Option<std::time::Duration> {
+        self.last_updated.map(|t| t.elapsed())
+    }
+
+    /// Delay before the next poll, see `adaptive_poll_interval`
+    pub fn poll_interval(
+        &self,
+        velocity_threshold: f64,
+        min: std::time::Duration,
+        max: std::time::Duration,
+    ) -> std::time::Duration {
+        crate::spike_detection::adaptive_poll_interval(
+            self.current_velocity,
+            velocity_threshold,
+            min,
+            max,
+        )
+    }
+}
+
+/// One market's column in `/compare`
+#[derive(Debug, Clone, PartialEq)]
+struct ComparisonSide {
+    question: String,
+    /// Price of the first outcome
+    price: Option<f64>,
+    volume: f64,
+    velocity: Option<f64>,
+    spread: Option<f64>,
+    mid: Option<f64>,
+}
+
+/// Order book fetched by the last `/depth` command
+#[derive(Debug, Clone)]
+pub struct DepthView {
+    pub question: String,
+    pub outcome: String,
+    /// Trimmed to the requested number of levels per side
+    pub book: OrderBook,
 }
 
 /// Main application state
 pub struct App {
     pub db_pool: crate::database::DbPool,
     pub execution_engine: Arc<ExecutionEngine>,
+    /// Detector shared with `main`, so every interface sees the same state
+    pub spike_detector: Arc<Mutex<SpikeDetector>>,
     pub market_service: MarketService,
+    pub connection_status: ConnectionStatus,
+    /// Authenticated wallet, `None` when running with `--offline`
+    pub wallet_address: Option<String>,
     pub current_tab: Tab,
     pub should_quit: bool,
+    pub theme: crate::tui::Theme,
+    /// When the quit confirmation was opened, for the double-`q` fast quit
+    quit_requested_at: Option<Instant>,
     pub logs: Vec<LogEntry>,
+    /// Background entries below this level are dropped, following `RUST_LOG`
+    pub min_log_level: LogLevel,
+    /// Set while handling user input, whose replies are always shown
+    user_initiated: bool,
     pub portfolio: Option<Portfolio>,
+    /// Total value when the session's first portfolio was loaded, the base
+    /// for percentage P&L
+    pub starting_capital: Option<f64>,
+    /// Show P&L as a percentage of `starting_capital` instead of dollars
+    pub pnl_as_percent: bool,
     pub active_orders: Vec<OrderInfo>,
+    pub selected_order_index: usize,
+    pub equity_curve: Vec<(i64, f64)>, // timestamp, total value
     pub is_paused: bool,
+    /// Orders are simulated rather than sent to the exchange
+    pub paper_trading: bool,
     pub last_order_id: Option<String>,
     pub last_refresh: Instant,
+    last_order_book_refresh: Option<Instant>,
+    /// When each watched market was first seen closed
+    closed_since: std::collections::HashMap<String, Instant>,
+    /// Set by F5; the next refresh skips the timer. The UI shows it as
+    /// "refreshing" since a frame is drawn before the refresh runs.
+    pub refresh_requested: bool,
 
     // Command input
     pub input_mode: InputMode,
     pub command_input: String,
     pub quit_selection: QuitSelection,
     pub leave_selection: LeaveSelection,
+    pub order_selection: OrderSelection,
+    pub pending_order: Option<PendingOrder>,
+    pub order_form: Option<OrderForm>,
 
     // Markets
     pub available_markets: Vec<MarketInfo>,
     pub joined_markets: Vec<String>,
     pub watched_markets_info: Vec<MarketInfo>,
+    /// Watched markets marked with `f`, listed first
+    pub favorite_markets: std::collections::HashSet<String>,
+    /// Hide watched markets that aren't favorites
+    pub favorites_only: bool,
+    /// The most recently left market, which `/unleave` (Ctrl-Z) restores once
+    last_left_market: Option<MarketInfo>,
+    /// `/note` annotations by market ID, shown in Market Detail
+    pub market_notes: std::collections::HashMap<String, String>,
     pub market_search_query: String,
     pub selected_market_index: usize,
     pub selected_watched_market_index: usize,
     pub is_loading_markets: bool,
+    /// The watchlist is still being read from the database
+    pub is_loading_watchlist: bool,
+    watchlist_load: Option<JoinHandle<Result<WatchlistLoad>>>,
+    /// Saved watched-market selection, applied once the watchlist has loaded
+    pending_watched_selection: Option<usize>,
+    pub market_sort: MarketSort,
+    pub market_sort_ascending: bool,
 
     // Market analysis
     pub market_analysis_data: std::collections::HashMap<String, MarketAnalysis>,
-
-    // RNG state
-    rng_state: u64,
+    /// Spike thresholds shown in the Market Detail panels
+    pub velocity_threshold: f64,
+    pub obi_threshold: f64,
+    /// Per-market overrides, mirrored from the spike detector
+    pub market_thresholds: std::collections::HashMap<String, MarketThresholds>,
+    spike_alerts: AlertDebouncer,
+    /// Events published by the engine and spike detector, drained every tick
+    bot_events: broadcast::Receiver<BotEvent>,
+    /// Flash notification, cleared once it expires
+    pub banner: Option<Banner>,
 
     // Docs tab state
     pub docs_selected_section: usize,
     pub docs_viewing_content: bool,
     pub docs_scroll_offset: u16,
+
+    // Logs tab state (entries scrolled past, counted from the newest)
+    pub logs_scroll_offset: usize,
+    pub log_filter: LogFilter,
+    pub log_search: String,
+
+    /// Last order book fetched with `/depth`
+    pub depth: Option<DepthView>,
+
+    // Settings tab state
+    pub settings_selected: usize,
+    pub settings_input: String,
 }
 
 impl App {
-    pub fn new(db_pool: crate::database::DbPool, execution_engine: Arc<ExecutionEngine>) -> Self {
+    pub fn new(
+        db_pool: crate::database::DbPool,
+        execution_engine: Arc<ExecutionEngine>,
+        spike_detector: Arc<Mutex<SpikeDetector>>,
+        market_service: MarketService,
+    ) -> Self {
+        let velocity_threshold = execution_engine.config().volume_velocity_threshold;
+        let obi_threshold = execution_engine.config().obi_threshold;
+        let paper_trading = execution_engine.is_paper_trading();
+        let theme =
+            crate::tui::Theme::from_name(&execution_engine.config().ui_theme).unwrap_or_default();
+        let bot_events = execution_engine.events().subscribe();
+        let min_log_level = LogLevel::from_filter(&execution_engine.config().log_level);
+
         let mut app = Self {
             db_pool,
             execution_engine,
-            market_service: MarketService::new(),
+            spike_detector,
+            market_service,
+            connection_status: ConnectionStatus {
+                state: crate::markets::ConnectionState::Idle,
+                latency: None,
+            },
+            wallet_address: None,
             current_tab: Tab::Dashboard,
             should_quit: false,
+            theme,
+            quit_requested_at: None,
             logs: Vec::new(),
+            min_log_level,
+            user_initiated: false,
             portfolio: None,
+            starting_capital: None,
+            pnl_as_percent: false,
             active_orders: Vec::new(),
+            selected_order_index: 0,
+            equity_curve: Vec::new(),
             is_paused: false,
+            paper_trading,
             last_order_id: None,
             last_refresh: Instant::now(),
+            last_order_book_refresh: None,
+            closed_since: std::collections::HashMap::new(),
+            refresh_requested: false,
             input_mode: InputMode::Normal,
             command_input: String::new(),
             quit_selection: QuitSelection::No,
             leave_selection: LeaveSelection::No,
+            order_selection: OrderSelection::No,
+            pending_order: None,
+            order_form: None,
             available_markets: Vec::new(),
             joined_markets: Vec::new(),
             watched_markets_info: Vec::new(),
+            favorite_markets: std::collections::HashSet::new(),
+            favorites_only: false,
+            last_left_market: None,
+            market_notes: std::collections::HashMap::new(),
             market_search_query: String::new(),
             selected_market_index: 0,
             selected_watched_market_index: 0,
             is_loading_markets: false,
+            is_loading_watchlist: false,
+            watchlist_load: None,
+            pending_watched_selection: None,
+            market_sort: MarketSort::None,
+            market_sort_ascending: false,
             market_analysis_data: std::collections::HashMap::new(),
-            rng_state: std::time::SystemTime::now()
-                .duration_since(std::time::UNIX_EPOCH)
-                .unwrap()
-                .as_nanos() as u64,
+            velocity_threshold,
+            obi_threshold,
+            market_thresholds: std::collections::HashMap::new(),
+            bot_events,
+            banner: None,
+            spike_alerts: AlertDebouncer::new(ALERT_DEBOUNCE),
             docs_selected_section: 0,
             docs_viewing_content: false,
             docs_scroll_offset: 0,
+            logs_scroll_offset: 0,
+            log_filter: LogFilter::All,
+            log_search: String::new(),
+            depth: None,
+            settings_selected: 0,
+            settings_input: String::new(),
         };
 
         app.add_log(LogLevel::Info, "TUI initialized successfully");
@@ -207,12 +706,84 @@ impl App {
         app
     }
 
-    /// Initialize watched markets - call this after creating App
+    /// Initialize watched markets and wait for them - call this after
+    /// creating App when nothing can be shown until they're loaded
     pub async fn init_watched_markets(&mut self) {
-        match crate::markets::load_watched_markets(&self.db_pool).await {
-            Ok(markets) => {
+        self.start_loading_watched_markets();
+        self.finish_loading_watched_markets(true).await;
+    }
+
+    /// Read the watchlist and its recent spikes in a background task so the
+    /// first frames draw right away. `refresh_data` picks up the result.
+    pub fn start_loading_watched_markets(&mut self) {
+        let db = self.db_pool.clone();
+        self.is_loading_watchlist = true;
+        self.watchlist_load = Some(tokio::spawn(async move {
+            let markets = crate::markets::load_watched_markets(&db).await?;
+            let mut loaded = Vec::with_capacity(markets.len());
+            for market in markets {
+                let events = crate::spike_detection::load_recent_events(
+                    &db,
+                    &market.id,
+                    RECENT_EVENTS_LIMIT as i64,
+                )
+                .await;
+                loaded.push((market, events));
+            }
+            Ok(WatchlistLoad {
+                markets: loaded,
+                favorites: crate::markets::load_favorite_markets(&db).await?,
+                notes: crate::markets::load_market_notes(&db).await?,
+            })
+        }));
+    }
+
+    /// Apply the background watchlist load once it has finished, or right
+    /// away waiting for it with `wait`
+    async fn finish_loading_watched_markets(&mut self, wait: bool) {
+        let Some(handle) = self.watchlist_load.take_if(|h| wait || h.is_finished()) else {
+            return;
+        };
+        self.is_loading_watchlist = false;
+
+        let loaded = match handle.await {
+            Ok(loaded) => loaded,
+            Err(e) => Err(e.into()),
+        };
+        match loaded {
+            Ok(loaded) => {
+                let mut markets = Vec::with_capacity(loaded.markets.len());
+                for (market, events) in loaded.markets {
+                    match events {
+                        Ok(events) => {
+                            self.market_analysis_data
+                                .entry(market.id.clone())
+                                .or_default()
+                                .recent_events = events;
+                        }
+                        Err(e) => self.add_log(
+                            LogLevel::Warning,
+                            &format!("Failed to load events for {}: {}", market.id, e),
+                        ),
+                    }
+                    markets.push(market);
+                }
+
                 self.joined_markets = markets.iter().map(|m| m.id.clone()).collect();
+                self.execution_engine
+                    .set_monitored_markets(self.joined_markets.clone())
+                    .await;
                 self.watched_markets_info = markets;
+                self.favorite_markets = loaded.favorites;
+                self.market_notes = loaded.notes;
+                self.sort_watchlist();
+                if let Some(index) = self.pending_watched_selection.take() {
+                    if index < self.watched_markets_info.len() {
+                        self.selected_watched_market_index = index;
+                    }
+                }
+                self.market_thresholds =
+                    self.spike_detector.lock().await.market_thresholds().clone();
                 self.add_log(
                     LogLevel::Success,
                     &format!("Loaded {} watched markets", self.joined_markets.len()),
@@ -227,7 +798,103 @@ impl App {
         }
     }
 
+    /// Persist the session's final state: a last portfolio snapshot, so the
+    /// equity curve ends where the session did, and the UI state. Call once
+    /// after the main loop exits; every other write is made as it happens.
+    pub async fn shutdown(&self) {
+        if let Err(e) = self.execution_engine.write_portfolio_snapshot().await {
+            tracing::warn!("Failed to write final portfolio snapshot: {}", e);
+        }
+        let write_batcher = self.spike_detector.lock().await.write_batcher();
+        if let Some(batcher) = write_batcher {
+            if let Err(e) = batcher.flush().await {
+                tracing::warn!("Failed to flush batched writes: {}", e);
+            }
+        }
+        self.save_ui_state().await;
+    }
+
+    /// Save the active tab, watched-market selection and P&L display for the
+    /// next launch
+    pub async fn save_ui_state(&self) {
+        let values = [
+            (UI_STATE_TAB, self.current_tab.title().to_string()),
+            (
+                UI_STATE_SELECTED_MARKET,
+                self.pending_watched_selection
+                    .unwrap_or(self.selected_watched_market_index)
+                    .to_string(),
+            ),
+            (UI_STATE_PNL_PERCENT, self.pnl_as_percent.to_string()),
+        ];
+        for (key, value) in values {
+            if let Err(e) = crate::database::save_ui_state(&self.db_pool, key, &value).await {
+                tracing::warn!("Failed to save UI state '{}': {}", key, e);
+            }
+        }
+    }
+
+    /// Restore what `save_ui_state` stored. A saved selection past the end of
+    /// the watchlist is ignored; while the watchlist is loading it is kept
+    /// until the load finishes.
+    pub async fn restore_ui_state(&mut self) {
+        let load = |key| crate::database::load_ui_state(&self.db_pool, key);
+
+        if let Ok(Some(title)) = load(UI_STATE_TAB).await {
+            if let Some(tab) = Tab::from_title(&title) {
+                self.current_tab = tab;
+            }
+        }
+        if let Ok(Some(index)) = load(UI_STATE_SELECTED_MARKET).await {
+            match index.parse::<usize>() {
+                Ok(index) if self.is_loading_watchlist => {
+                    self.pending_watched_selection = Some(index);
+                }
+                Ok(index) if index < self.watched_markets_info.len() => {
+                    self.selected_watched_market_index = index;
+                }
+                _ => {}
+            }
+        }
+        if let Ok(Some(value)) = load(UI_STATE_PNL_PERCENT).await {
+            self.pnl_as_percent = value == "true";
+        }
+    }
+
+    /// Seed a market's Recent Events panel from spikes persisted in earlier sessions
+    async fn load_recent_events(&mut self, market_id: &str) {
+        match crate::spike_detection::load_recent_events(
+            &self.db_pool,
+            market_id,
+            RECENT_EVENTS_LIMIT as i64,
+        )
+        .await
+        {
+            Ok(events) => {
+                self.market_analysis_data
+                    .entry(market_id.to_string())
+                    .or_default()
+                    .recent_events = events;
+            }
+            Err(e) => self.add_log(
+                LogLevel::Warning,
+                &format!("Failed to load events for {}: {}", market_id, e),
+            ),
+        }
+    }
+
     pub fn add_log(&mut self, level: LogLevel, message: &str) {
+        if !self.user_initiated && level.severity() < self.min_log_level.severity() {
+            return;
+        }
+
+        // Mirror into the tracing stream so the log file sees TUI events too
+        match level {
+            LogLevel::Info | LogLevel::Success => tracing::info!(target: "tui", "{}", message),
+            LogLevel::Warning => tracing::warn!(target: "tui", "{}", message),
+            LogLevel::Error => tracing::error!(target: "tui", "{}", message),
+        }
+
         let timestamp = chrono::Local::now().format("%H:%M:%S").to_string();
         self.logs.push(LogEntry {
             timestamp,
@@ -235,15 +902,54 @@ impl App {
             message: message.to_string(),
         });
 
-        // Keep only last 100 logs
-        if self.logs.len() > 100 {
-            self.logs.remove(0);
+        // Keep the view anchored on the same entries while scrolled back
+        if self.logs_scroll_offset > 0
+            && self.log_filter.matches(level)
+            && contains_ignore_ascii_case(message, &self.log_search)
+        {
+            self.logs_scroll_offset += 1;
+        }
+
+        // Keep only the configured number of logs
+        let retention = self.execution_engine.config().log_retention;
+        if self.logs.len() > retention {
+            let excess = self.logs.len() - retention;
+            self.logs.drain(..excess);
         }
+        self.scroll_logs(0);
+    }
+
+    /// Log entries that pass the active level filter and search text, oldest first
+    pub fn filtered_logs(&self) -> Vec<&LogEntry> {
+        self.logs
+            .iter()
+            .filter(|log| self.log_filter.matches(log.level))
+            .filter(|log| contains_ignore_ascii_case(&log.message, &self.log_search))
+            .collect()
+    }
+
+    /// Move the logs view by `delta` entries (positive = older), clamped to the buffer
+    pub fn scroll_logs(&mut self, delta: isize) {
+        let max_offset = self.filtered_logs().len().saturating_sub(1);
+        self.logs_scroll_offset = self
+            .logs_scroll_offset
+            .saturating_add_signed(delta)
+            .min(max_offset);
     }
 
     pub async fn refresh_data(&mut self) {
-        // Refresh every 500ms
-        if self.last_refresh.elapsed().as_millis() < 500 {
+        self.drain_bot_events();
+        self.finish_loading_watched_markets(false).await;
+        if self
+            .banner
+            .as_ref()
+            .is_some_and(|b| b.expires_at <= Instant::now())
+        {
+            self.banner = None;
+        }
+
+        // Refresh every 500ms, or right away when requested with F5
+        if !self.refresh_requested && self.last_refresh.elapsed().as_millis() < 500 {
             return;
         }
         self.last_refresh = Instant::now();
@@ -251,147 +957,691 @@ impl App {
         // Update paused state
         self.is_paused = self.execution_engine.is_paused().await;
 
+        // Reflect the outcome of the latest API calls
+        self.connection_status = self.market_service.connection_status();
+
         // Update last order ID
         self.last_order_id = self.execution_engine.get_last_order_id().await;
 
         // Update portfolio
         if let Ok(portfolio) = self.execution_engine.get_portfolio().await {
+            // An empty or not yet funded wallet would make every percentage n/a
+            if self.starting_capital.is_none() && portfolio.total_value > 0.0 {
+                self.starting_capital = Some(portfolio.total_value);
+            }
             self.portfolio = Some(portfolio);
         }
 
         // Update active orders
         if let Ok(orders) = self.execution_engine.get_active_orders().await {
             self.active_orders = orders;
+            self.selected_order_index = self
+                .selected_order_index
+                .min(self.active_orders.len().saturating_sub(1));
+        }
+
+        // Update equity curve from recorded portfolio snapshots
+        if let Ok(curve) = crate::database::load_equity_curve(&self.db_pool, 50).await {
+            self.equity_curve = curve;
+        }
+
+        // Top of book for the market being looked at, on a slower cadence
+        let order_book_due = self
+            .last_order_book_refresh
+            .is_none_or(|t| t.elapsed() >= ORDER_BOOK_REFRESH_INTERVAL);
+        if self.current_tab == Tab::MarketDetail && (order_book_due || self.refresh_requested) {
+            self.last_order_book_refresh = Some(Instant::now());
+            self.refresh_selected_market(self.refresh_requested).await;
         }
+        self.refresh_requested = false;
 
-        // Simulate market analysis data updates
-        self.simulate_market_data();
+        self.poll_next_due_market().await;
+        if self.execution_engine.config().auto_remove_closed {
+            self.remove_expired_closed_markets().await;
+        }
     }
 
-    fn simulate_market_data(&mut self) {
-        let mut rng_state = self.rng_state;
+    /// Fetch the order book of the selected watched market's first outcome.
+    /// With `full` the market itself is refetched too, updating volume and prices.
+    async fn refresh_selected_market(&mut self, full: bool) {
+        let index = self
+            .selected_watched_market_index
+            .min(self.watched_markets_info.len().saturating_sub(1));
+        let Some(market) = self.watched_markets_info.get(index) else {
+            return;
+        };
+        let market_id = market.id.clone();
+
+        // Watchlist entries loaded from the database don't carry token IDs
+        if full || market.token_ids.is_empty() {
+            match self.market_service.get_market(&market_id).await {
+                Ok(Some(latest)) => self.apply_market_update(&market_id, latest).await,
+                Ok(None) => return,
+                Err(e) if full => {
+                    self.log_market_error("Refresh failed", &e);
+                    return;
+                }
+                Err(e) => {
+                    tracing::debug!("Failed to load market {}: {}", market_id, e);
+                    return;
+                }
+            }
+        }
 
-        // Clone market IDs to avoid borrowing self while mutating analysis data
-        let markets: Vec<String> = self
+        // The update may have re-sorted the watchlist, so look the market up again
+        let Some(token_id) = self
             .watched_markets_info
             .iter()
-            .map(|m| m.id.clone())
-            .collect();
-
-        let next_random = |state: &mut u64| -> f64 {
-            *state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
-            (*state as f64) / (u64::MAX as f64)
-        };
-
-        for market_id in markets {
-            let entry = self
-                .market_analysis_data
-                .entry(market_id.clone())
-                .or_default();
-
-            // Initialize if empty
-            if entry.current_velocity.is_none() {
-                entry.current_velocity = Some(0.0);
-            }
-            if entry.current_obi.is_none() {
-                entry.current_obi = Some(0.0);
-            }
-
-            // Random walk for Velocity (-2000 to +2000)
-            let rnd = next_random(&mut rng_state);
-            if let Some(vel) = entry.current_velocity {
-                let change = (rnd - 0.5) * 200.0; // Change by up to +/- 100
-                let new_vel = (vel + change).clamp(-2000.0, 2000.0);
-                entry.current_velocity = Some(new_vel);
-
-                // Add event if spike
-                if new_vel.abs() > 1000.0 && next_random(&mut rng_state) > 0.95 {
-                    entry.recent_events.insert(
-                        0,
-                        crate::types::VolumeVelocityEvent {
-                            market_id: market_id.clone(),
-                            velocity: new_vel,
-                            volume_delta: change,
-                            time_delta: 0.5,
-                            timestamp: chrono::Utc::now().timestamp(),
-                        },
-                    );
-                    if entry.recent_events.len() > 10 {
-                        entry.recent_events.pop();
-                    }
+            .find(|m| m.id == market_id)
+            .and_then(|m| m.token_ids.first().cloned())
+        else {
+            return;
+        };
+        match self
+            .market_service
+            .get_order_book(&market_id, &token_id)
+            .await
+        {
+            Ok(book) => {
+                let detector = self.spike_detector.lock().await;
+                if let Err(e) = detector.record_order_book_snapshot(&market_id, &book).await {
+                    tracing::warn!("Failed to record order book snapshot: {}", e);
                 }
-            }
+                let obi = detector.calculate_order_book_imbalance(
+                    book.bids.iter().map(|l| l.size).sum(),
+                    book.asks.iter().map(|l| l.size).sum(),
+                );
+                drop(detector);
 
-            // Random walk for OBI (-1.0 to 1.0)
-            let rnd2 = next_random(&mut rng_state);
-            if let Some(obi) = entry.current_obi {
-                let change = (rnd2 - 0.5) * 0.1; // Change by up to +/- 0.05
-                let new_obi = (obi + change).clamp(-1.0, 1.0);
-                entry.current_obi = Some(new_obi);
+                let analysis = self.market_analysis_data.entry(market_id).or_default();
+                analysis.record_obi(obi);
+                analysis.order_book = Some(book);
+                analysis.last_updated = Some(Instant::now());
             }
+            Err(e) => tracing::debug!("Failed to load order book for {}: {}", market_id, e),
+        }
+    }
+
+    /// Store freshly fetched details for the watched market `market_id`.
+    /// The fetched market may carry a different ID (Gamma reports the
+    /// conditionId), so the watchlist entry keeps the ID it was joined with.
+    /// Closed markets are logged the first time they're seen and sorted to the
+    /// bottom of the list.
+    async fn apply_market_update(&mut self, market_id: &str, latest: MarketInfo) {
+        let volume = parse_volume(&latest.volume);
+        let Some(market) = self
+            .watched_markets_info
+            .iter_mut()
+            .find(|m| m.id == market_id)
+        else {
+            return;
+        };
+        market.active = latest.active;
+        market.volume = latest.volume;
+        market.prices = latest.prices;
+        market.token_ids = latest.token_ids;
+        market.end_date = latest.end_date;
+        self.market_analysis_data
+            .entry(market_id.to_string())
+            .or_default()
+            .last_updated = Some(Instant::now());
+
+        if market.active {
+            self.closed_since.remove(market_id);
+        } else if let std::collections::hash_map::Entry::Vacant(entry) =
+            self.closed_since.entry(market_id.to_string())
+        {
+            entry.insert(Instant::now());
+            let question = market.question.clone();
+            self.add_log(LogLevel::Warning, &format!("Market closed: {}", question));
         }
 
-        self.rng_state = rng_state;
+        self.sort_watchlist();
+        self.update_velocity(market_id, volume).await;
     }
 
-    pub async fn handle_event(&mut self, event: KeyEvent) -> Result<()> {
-        match self.input_mode {
-            InputMode::Command => self.handle_command_input(event).await,
-            InputMode::QuitConfirmation => self.handle_quit_confirmation(event),
-            InputMode::LeaveMarketConfirmation => self.handle_leave_confirmation(event).await,
-            InputMode::Normal => self.handle_normal_input(event).await,
+    /// Feed a market's latest volume to the spike detector and show the
+    /// resulting velocity. Spikes reach the UI over the event bus.
+    async fn update_velocity(&mut self, market_id: &str, volume: f64) {
+        let mut detector = self.spike_detector.lock().await;
+        if let Err(e) = detector.check_volume_velocity(market_id, volume).await {
+            tracing::warn!("Spike detection failed for {}: {}", market_id, e);
+        }
+        let velocity = detector.current_velocity(market_id);
+        drop(detector);
+
+        if velocity.is_some() {
+            self.market_analysis_data
+                .entry(market_id.to_string())
+                .or_default()
+                .current_velocity = velocity;
         }
     }
 
-    async fn handle_command_input(&mut self, event: KeyEvent) -> Result<()> {
-        match event.code {
-            KeyCode::Enter => {
-                let command = self.command_input.clone();
-                self.command_input.clear();
-                self.input_mode = InputMode::Normal;
-                self.execute_command(&command).await;
-            }
-            KeyCode::Esc => {
-                self.command_input.clear();
-                self.input_mode = InputMode::Normal;
-            }
-            KeyCode::Backspace => {
-                self.command_input.pop();
-            }
-            KeyCode::Char(c) => {
-                self.command_input.push(c);
+    /// Move closed markets below open ones and favorites above the rest of
+    /// each group, keeping the selection on the same market
+    fn sort_watchlist(&mut self) {
+        let selected_id = self
+            .watched_markets_info
+            .get(self.selected_watched_market_index)
+            .map(|m| m.id.clone());
+
+        // Stable, so markets keep their order within each group
+        let favorites = &self.favorite_markets;
+        self.watched_markets_info
+            .sort_by_key(|m| (!m.active, !favorites.contains(&m.id)));
+
+        if let Some(id) = selected_id {
+            if let Some(index) = self.watched_markets_info.iter().position(|m| m.id == id) {
+                self.selected_watched_market_index = index;
             }
-            _ => {}
         }
-        Ok(())
     }
 
-    async fn execute_command(&mut self, command: &str) {
-        let parts: Vec<&str> = command.trim().split_whitespace().collect();
-        if parts.is_empty() {
+    /// Whether `market` is listed under the current favorites filter
+    pub fn is_watched_market_shown(&self, market: &MarketInfo) -> bool {
+        !self.favorites_only || self.favorite_markets.contains(&market.id)
+    }
+
+    /// Move the Market Detail selection to the next (or previous) listed market
+    fn step_watched_selection(&mut self, forward: bool) {
+        let current = self.selected_watched_market_index;
+        let listed = |i: &usize| self.is_watched_market_shown(&self.watched_markets_info[*i]);
+        let next = if forward {
+            (current + 1..self.watched_markets_info.len()).find(listed)
+        } else {
+            (0..current).rfind(listed)
+        };
+        if let Some(index) = next {
+            self.selected_watched_market_index = index;
+            self.last_order_book_refresh = None;
+        }
+    }
+
+    /// Keep the selection on a listed market, dropping the favorites filter
+    /// once no favorite is left to show
+    fn ensure_visible_watched_selection(&mut self) {
+        self.selected_watched_market_index = self
+            .selected_watched_market_index
+            .min(self.watched_markets_info.len().saturating_sub(1));
+        let selected = self
+            .watched_markets_info
+            .get(self.selected_watched_market_index);
+        if selected.is_none_or(|m| self.is_watched_market_shown(m)) {
             return;
         }
+        match (0..self.watched_markets_info.len())
+            .find(|&i| self.is_watched_market_shown(&self.watched_markets_info[i]))
+        {
+            Some(index) => {
+                self.selected_watched_market_index = index;
+                self.last_order_book_refresh = None;
+            }
+            None => {
+                self.favorites_only = false;
+                self.add_log(
+                    LogLevel::Info,
+                    "No favorites left - showing all watched markets",
+                );
+            }
+        }
+    }
 
-        let cmd = parts[0].to_lowercase();
-        let args: Vec<&str> = parts[1..].to_vec();
+    /// Mark or unmark the selected watched market as a favorite, saved with it
+    async fn toggle_favorite(&mut self) {
+        let Some(market) = self
+            .watched_markets_info
+            .get(self.selected_watched_market_index)
+        else {
+            self.add_log(LogLevel::Warning, "Join a market first");
+            return;
+        };
+        let (id, question) = (market.id.clone(), market.question.clone());
+        let favorite = !self.favorite_markets.contains(&id);
 
-        match cmd.as_str() {
-            "/search" | "search" | "/s" | "s" => {
-                if args.is_empty() {
-                    self.add_log(LogLevel::Warning, "Usage: /search <keyword>");
+        if let Err(e) = crate::markets::set_favorite_market(&self.db_pool, &id, favorite).await {
+            self.add_log(LogLevel::Error, &format!("Failed to save favorite: {}", e));
+            return;
+        }
+        if favorite {
+            self.favorite_markets.insert(id);
+            self.add_log(LogLevel::Success, &format!("★ Favorited: {}", question));
+        } else {
+            self.favorite_markets.remove(&id);
+            self.add_log(LogLevel::Info, &format!("Unfavorited: {}", question));
+        }
+        self.sort_watchlist();
+        self.ensure_visible_watched_selection();
+    }
+
+    /// Switch between listing every watched market and only the favorites
+    fn toggle_favorites_only(&mut self) {
+        if !self.favorites_only
+            && !self
+                .watched_markets_info
+                .iter()
+                .any(|m| self.favorite_markets.contains(&m.id))
+        {
+            self.add_log(
+                LogLevel::Warning,
+                "No favorites yet - press 'f' on a market in Market Detail",
+            );
+            return;
+        }
+        self.favorites_only = !self.favorites_only;
+        self.ensure_visible_watched_selection();
+        let shown = if self.favorites_only {
+            "favorites only"
+        } else {
+            "all watched markets"
+        };
+        self.add_log(LogLevel::Info, &format!("Showing {}", shown));
+    }
+
+    /// Refetch the watched market that is most overdue for a poll, if any, so
+    /// closures and volume changes are noticed. One market per refresh keeps
+    /// the requests spread out instead of bursting for the whole watchlist.
+    async fn poll_next_due_market(&mut self) {
+        let now = Instant::now();
+        let due = self
+            .watched_markets_info
+            .iter()
+            .map(|m| {
+                let next_poll = self
+                    .market_analysis_data
+                    .get(&m.id)
+                    .and_then(|a| a.next_poll);
+                (next_poll, &m.id)
+            })
+            .filter(|(next_poll, _)| next_poll.is_none_or(|t| t <= now))
+            .min_by_key(|(next_poll, _)| *next_poll);
+        let Some((_, market_id)) = due else {
+            return;
+        };
+        let market_id = market_id.clone();
+
+        match self.market_service.get_market(&market_id).await {
+            Ok(Some(latest)) => self.apply_market_update(&market_id, latest).await,
+            Ok(None) => {}
+            Err(e) => tracing::debug!("Failed to poll market {}: {}", market_id, e),
+        }
+
+        // Failures wait a full interval too, so a broken market isn't hammered
+        let interval = self.poll_interval_for(&market_id);
+        self.market_analysis_data
+            .entry(market_id)
+            .or_default()
+            .next_poll = Some(Instant::now() + interval);
+    }
+
+    /// How long until `market_id` is polled again, following its activity
+    /// unless adaptive polling is off
+    pub fn poll_interval_for(&self, market_id: &str) -> std::time::Duration {
+        let config = self.execution_engine.config();
+        let min = std::time::Duration::from_secs(config.poll_interval_min_secs);
+        let max = std::time::Duration::from_secs(config.poll_interval_max_secs);
+        if !config.adaptive_polling {
+            return max;
+        }
+        self.market_analysis_data.get(market_id).map_or(max, |a| {
+            a.poll_interval(self.velocity_threshold_for(market_id), min, max)
+        })
+    }
+
+    /// Leave markets that have been closed for longer than the grace period
+    async fn remove_expired_closed_markets(&mut self) {
+        let expired: Vec<String> = self
+            .closed_since
+            .iter()
+            .filter(|(_, since)| since.elapsed() >= CLOSED_MARKET_GRACE)
+            .map(|(id, _)| id.clone())
+            .collect();
+
+        for market_id in expired {
+            self.closed_since.remove(&market_id);
+            self.add_log(
+                LogLevel::Info,
+                &format!("Removing closed market {} from the watchlist", market_id),
+            );
+            self.leave_market(&market_id).await;
+        }
+    }
+
+    /// Log a volume spike, escalating to an error at twice the threshold.
+    /// Repeat alerts for the same market are debounced.
+    fn alert_spike(&mut self, event: &crate::types::VolumeVelocityEvent) {
+        if !self
+            .spike_alerts
+            .should_alert(&event.market_id, Instant::now())
+        {
+            return;
+        }
+
+        let question = self
+            .watched_markets_info
+            .iter()
+            .find(|m| m.id == event.market_id)
+            .map_or_else(|| event.market_id.clone(), |m| m.question.clone());
+        let threshold = self.velocity_threshold_for(&event.market_id);
+        let level = if event.velocity.abs() >= threshold * 2.0 {
+            LogLevel::Error
+        } else {
+            LogLevel::Warning
+        };
+
+        let message = format!(
+            "🚨 Volume spike on {}: {:+.2} vol/sec (threshold {:.0})",
+            question, event.velocity, threshold
+        );
+        self.add_log(level, &message);
+        self.flash(level, &message);
+    }
+
+    /// Show `message` in the banner over the header for `BANNER_DURATION`,
+    /// replacing any banner already showing
+    fn flash(&mut self, level: LogLevel, message: &str) {
+        self.banner = Some(Banner {
+            message: message.to_string(),
+            level,
+            expires_at: Instant::now() + BANNER_DURATION,
+        });
+    }
+
+    /// Apply every event published since the last call
+    pub fn drain_bot_events(&mut self) {
+        loop {
+            match self.bot_events.try_recv() {
+                Ok(event) => self.handle_bot_event(event),
+                Err(broadcast::error::TryRecvError::Lagged(missed)) => {
+                    tracing::warn!("TUI fell behind the event bus, skipped {} event(s)", missed);
+                }
+                Err(_) => break,
+            }
+        }
+    }
+
+    fn handle_bot_event(&mut self, event: BotEvent) {
+        match event {
+            BotEvent::Spike(event) => {
+                let analysis = self
+                    .market_analysis_data
+                    .entry(event.market_id.clone())
+                    .or_default();
+                analysis.recent_events.insert(0, event.clone());
+                analysis.recent_events.truncate(RECENT_EVENTS_LIMIT);
+                self.alert_spike(&event);
+            }
+            BotEvent::OrderPlaced(order_id) => {
+                self.last_order_id = Some(order_id.clone());
+                self.add_log(LogLevel::Info, &format!("Order ID: {}", order_id));
+            }
+            BotEvent::OrderFilled { order_id, price } => {
+                self.flash(
+                    LogLevel::Success,
+                    &format!("Order {} filled @ {:.3}", order_id, price),
+                );
+            }
+            BotEvent::OrderPartiallyFilled {
+                order_id,
+                filled_size,
+                size,
+            } => {
+                if let Some(order) = self
+                    .active_orders
+                    .iter_mut()
+                    .find(|o| o.order_id == order_id)
+                {
+                    order.filled_size = filled_size;
+                }
+                self.add_log(
+                    LogLevel::Info,
+                    &format!("Order {} filled {}/{}", order_id, filled_size, size),
+                );
+            }
+            BotEvent::Panic(cancelled) => {
+                self.is_paused = true;
+                self.flash(
+                    LogLevel::Error,
+                    &format!(
+                        "🚨 PANIC - cancelled {} order(s), trading paused",
+                        cancelled
+                    ),
+                );
+            }
+            BotEvent::OrderCancelled(order_id) => {
+                self.active_orders.retain(|o| o.order_id != order_id);
+                self.selected_order_index = self
+                    .selected_order_index
+                    .min(self.active_orders.len().saturating_sub(1));
+                self.add_log(LogLevel::Success, &format!("Cancelled order {}", order_id));
+            }
+            BotEvent::Paused => {
+                self.is_paused = true;
+                self.add_log(LogLevel::Warning, "Bot PAUSED - trading disabled");
+            }
+            BotEvent::Resumed => {
+                self.is_paused = false;
+                self.add_log(LogLevel::Success, "Bot RESUMED - trading enabled");
+            }
+        }
+    }
+
+    /// Velocity threshold for a market, its override if it has one
+    pub fn velocity_threshold_for(&self, market_id: &str) -> f64 {
+        self.market_thresholds
+            .get(market_id)
+            .and_then(|t| t.velocity)
+            .unwrap_or(self.velocity_threshold)
+    }
+
+    /// OBI threshold for a market, its override if it has one
+    pub fn obi_threshold_for(&self, market_id: &str) -> f64 {
+        self.market_thresholds
+            .get(market_id)
+            .and_then(|t| t.obi)
+            .unwrap_or(self.obi_threshold)
+    }
+
+    pub async fn handle_event(&mut self, event: AppEvent) -> Result<()> {
+        match event {
+            AppEvent::Key(key_event) => {
+                let was_user_initiated = std::mem::replace(&mut self.user_initiated, true);
+                let result = self.handle_key_event(key_event).await;
+                self.user_initiated = was_user_initiated;
+                result
+            }
+            AppEvent::ScrollUp => {
+                self.handle_scroll(true);
+                Ok(())
+            }
+            AppEvent::ScrollDown => {
+                self.handle_scroll(false);
+                Ok(())
+            }
+        }
+    }
+
+    /// Mouse wheel scrolling for the Logs and Docs tabs
+    fn handle_scroll(&mut self, up: bool) {
+        if self.input_mode != InputMode::Normal {
+            return;
+        }
+
+        match self.current_tab {
+            Tab::Logs => self.scroll_logs(if up { -1 } else { 1 }),
+            Tab::Docs if self.docs_viewing_content => {
+                if up {
+                    self.docs_scroll_up();
                 } else {
-                    let keyword = args.join(" ");
-                    self.search_markets(&keyword).await;
+                    self.docs_scroll_down();
                 }
             }
+            _ => {}
+        }
+    }
+
+    fn docs_scroll_up(&mut self) {
+        self.docs_scroll_offset = self.docs_scroll_offset.saturating_sub(1);
+    }
+
+    fn docs_scroll_down(&mut self) {
+        // Stop ~10 lines before the end so content stays visible
+        let max_scroll = doc_line_count(self.docs_selected_section).saturating_sub(10);
+        if self.docs_scroll_offset < max_scroll {
+            self.docs_scroll_offset = self.docs_scroll_offset.saturating_add(1);
+        }
+    }
+
+    async fn handle_key_event(&mut self, event: KeyEvent) -> Result<()> {
+        match self.input_mode {
+            InputMode::Command => self.handle_command_input(event).await,
+            InputMode::LogSearch => self.handle_log_search_input(event),
+            InputMode::QuitConfirmation => self.handle_quit_confirmation(event),
+            InputMode::LeaveMarketConfirmation => self.handle_leave_confirmation(event).await,
+            InputMode::OrderEntry => self.handle_order_entry(event),
+            InputMode::OrderConfirmation => self.handle_order_confirmation(event).await,
+            InputMode::SettingsEdit => self.handle_settings_input(event).await,
+            // Any key dismisses the help overlay
+            InputMode::HelpOverlay => {
+                self.input_mode = InputMode::Normal;
+                Ok(())
+            }
+            InputMode::Normal => self.handle_normal_input(event).await,
+        }
+    }
+
+    async fn handle_command_input(&mut self, event: KeyEvent) -> Result<()> {
+        match event.code {
+            KeyCode::Enter => {
+                let command = self.command_input.clone();
+                self.command_input.clear();
+                self.input_mode = InputMode::Normal;
+                self.execute_command(&command).await;
+            }
+            KeyCode::Esc => {
+                self.command_input.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.command_input.pop();
+            }
+            KeyCode::Char(c) => {
+                self.command_input.push(c);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    async fn handle_settings_input(&mut self, event: KeyEvent) -> Result<()> {
+        match event.code {
+            KeyCode::Enter => {
+                let field = self.selected_settings_field();
+                let input = std::mem::take(&mut self.settings_input);
+                self.input_mode = InputMode::Normal;
+                match input.trim().parse::<f64>() {
+                    Ok(value) => self.set_threshold(field.threshold_kind(), value).await,
+                    Err(_) => self.add_log(LogLevel::Error, &format!("Invalid value: {}", input)),
+                }
+            }
+            KeyCode::Esc => {
+                self.settings_input.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.settings_input.pop();
+            }
+            KeyCode::Char(c) if is_numeric_input_char(&self.settings_input, c, true) => {
+                self.settings_input.push(c);
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    pub fn selected_settings_field(&self) -> SettingsField {
+        let fields = SettingsField::all();
+        fields[self.settings_selected.min(fields.len() - 1)]
+    }
+
+    /// Current live value of an editable setting
+    pub fn settings_value(&self, field: SettingsField) -> f64 {
+        match field {
+            SettingsField::VelocityThreshold => self.velocity_threshold,
+            SettingsField::ObiThreshold => self.obi_threshold,
+        }
+    }
+
+    fn handle_log_search_input(&mut self, event: KeyEvent) -> Result<()> {
+        match event.code {
+            // Keep the filter applied
+            KeyCode::Enter => {
+                self.input_mode = InputMode::Normal;
+            }
+            // Clear the filter and restore the full list
+            KeyCode::Esc => {
+                self.log_search.clear();
+                self.input_mode = InputMode::Normal;
+            }
+            KeyCode::Backspace => {
+                self.log_search.pop();
+            }
+            KeyCode::Char(c) => {
+                self.log_search.push(c);
+            }
+            _ => {}
+        }
+        self.logs_scroll_offset = 0;
+        Ok(())
+    }
+
+    /// Run a `/command`. Its output is shown whatever the minimum log level.
+    pub async fn execute_command(&mut self, command: &str) {
+        let was_user_initiated = std::mem::replace(&mut self.user_initiated, true);
+        self.run_command(command).await;
+        self.user_initiated = was_user_initiated;
+    }
+
+    /// Run a command-bar command (also used by the REPL)
+    async fn run_command(&mut self, command: &str) {
+        let parts: Vec<&str> = command.split_whitespace().collect();
+        if parts.is_empty() {
+            return;
+        }
+
+        let cmd = parts[0].to_lowercase();
+        let args: Vec<&str> = parts[1..].to_vec();
+
+        match cmd.as_str() {
+            "/search" | "search" | "/s" | "s" => match parse_search_args(&args) {
+                Some((keyword, filter)) if !keyword.is_empty() => {
+                    self.search_markets(&keyword, filter).await;
+                }
+                _ => self.add_log(
+                    LogLevel::Warning,
+                    "Usage: /search <keyword> [--minvol <volume>] [--asc|--desc]",
+                ),
+            },
             "/joinmarket" | "joinmarket" | "/join" | "join" | "/j" | "j" => {
                 if args.is_empty() {
-                    self.add_log(LogLevel::Warning, "Usage: /joinmarket <market_id or index>");
+                    self.add_log(
+                        LogLevel::Warning,
+                        "Usage: /joinmarket <market_id> or /joinmarket #<index>",
+                    );
+                } else if let Some(number) = args[0].strip_prefix('#') {
+                    // Indexes are explicit so numeric market IDs are never misread
+                    match number.parse::<usize>() {
+                        Ok(n) if n > 0 => self.join_by_index(n - 1).await,
+                        _ => self.add_log(LogLevel::Error, &format!("Invalid index: {}", number)),
+                    }
                 } else {
-                    let market_ref = args[0];
-                    self.join_market(market_ref).await;
+                    self.join_by_id(args[0]).await;
                 }
             }
+            "/joinurl" | "joinurl" => match args.first() {
+                Some(url) => self.join_by_url(url).await,
+                None => self.add_log(LogLevel::Warning, "Usage: /joinurl <polymarket url>"),
+            },
             "/leavemarkt" | "leavemarket" | "/leave" | "leave" | "/l" => {
                 if args.is_empty() {
                     self.add_log(LogLevel::Warning, "Usage: /leavemarket <market_id>");
@@ -399,9 +1649,120 @@ impl App {
                     self.leave_market(args[0]).await;
                 }
             }
-            "/trending" | "trending" | "/t" | "t" => {
-                self.load_trending_markets().await;
+            "/unleave" | "unleave" => {
+                self.undo_leave().await;
+            }
+            "/watch" | "watch" | "/w" => {
+                if args.is_empty() {
+                    self.add_log(LogLevel::Warning, "Usage: /watch <keyword>");
+                } else {
+                    let keyword = args.join(" ");
+                    self.watch_keyword(&keyword).await;
+                }
             }
+            "/cancel" | "cancel" | "/c" => {
+                if args.is_empty() {
+                    self.add_log(LogLevel::Warning, "Usage: /cancel <order_id>");
+                } else {
+                    self.cancel_order(args[0]).await;
+                }
+            }
+            "/setthreshold" | "setthreshold" => match args.as_slice() {
+                [kind, value] => match value.parse::<f64>() {
+                    Ok(value) => self.set_threshold(kind, value).await,
+                    Err(_) => self.add_log(LogLevel::Error, &format!("Invalid value: {}", value)),
+                },
+                [market_id, kind, value] => match value.parse::<f64>() {
+                    Ok(value) => self.set_market_threshold(market_id, kind, value).await,
+                    Err(_) => self.add_log(LogLevel::Error, &format!("Invalid value: {}", value)),
+                },
+                _ => self.add_log(
+                    LogLevel::Warning,
+                    "Usage: /setthreshold [market_id] <velocity|obi> <value>",
+                ),
+            },
+            "/depth" | "depth" | "/d" => match args.as_slice() {
+                [market_id] => self.show_depth(market_id, DEPTH_LEVELS).await,
+                [market_id, levels] => match levels.parse::<usize>() {
+                    Ok(n) if n > 0 => self.show_depth(market_id, n).await,
+                    _ => self.add_log(LogLevel::Error, &format!("Invalid level count: {}", levels)),
+                },
+                _ => self.add_log(LogLevel::Warning, "Usage: /depth <market_id> [levels]"),
+            },
+            "/depthchart" | "depthchart" => match args.as_slice() {
+                [market_id] => self.show_depth_chart(market_id, DEPTH_CHART_BINS).await,
+                [market_id, bins] => match bins.parse::<usize>() {
+                    Ok(n) if n > 0 => self.show_depth_chart(market_id, n).await,
+                    _ => self.add_log(LogLevel::Error, &format!("Invalid bin count: {}", bins)),
+                },
+                _ => self.add_log(LogLevel::Warning, "Usage: /depthchart <market_id> [bins]"),
+            },
+            "/note" | "note" => match args.split_first() {
+                Some((market_id, note)) => self.set_note(market_id, &note.join(" ")).await,
+                None => self.add_log(LogLevel::Warning, "Usage: /note <market_id> [text]"),
+            },
+            "/compare" | "compare" => match args.as_slice() {
+                [first, second] => self.compare_markets(first, second).await,
+                _ => self.add_log(LogLevel::Warning, "Usage: /compare <market_id> <market_id>"),
+            },
+            "/resolve" | "resolve" => {
+                self.check_resolution().await;
+            }
+            "/connectivity" | "connectivity" => {
+                self.check_connectivity().await;
+            }
+            "/selftest" | "selftest" => {
+                self.run_self_test().await;
+            }
+            "/stats" | "stats" => {
+                self.show_stats().await;
+            }
+            "/markets" | "markets" => {
+                self.show_monitored_markets().await;
+            }
+            "/findarb" | "findarb" => {
+                self.find_arbitrage();
+            }
+            "/top" | "top" => match args.first() {
+                None => self.show_top_markets(TOP_MARKETS),
+                Some(n) => match n.parse::<usize>() {
+                    Ok(n) if n > 0 => self.show_top_markets(n),
+                    _ => self.add_log(LogLevel::Warning, "Usage: /top [n]"),
+                },
+            },
+            "/export" | "export" => {
+                let mut format = ExportFormat::Csv;
+                let mut dir = DEFAULT_EXPORT_DIR;
+                let mut args = args.iter();
+                while let Some(arg) = args.next() {
+                    if *arg == "--format" {
+                        match args.next().and_then(|f| ExportFormat::parse(f)) {
+                            Some(f) => format = f,
+                            None => {
+                                self.add_log(
+                                    LogLevel::Warning,
+                                    "Usage: /export [--format csv|json] [dir]",
+                                );
+                                return;
+                            }
+                        }
+                    } else {
+                        dir = arg;
+                    }
+                }
+                self.export_data(std::path::Path::new(dir), format).await;
+            }
+            "/trending" | "trending" | "/t" | "t" => match args.as_slice() {
+                [] => self.load_trending_markets(None).await,
+                [floor] => match floor.parse::<f64>() {
+                    Ok(n) if n >= 0.0 => self.load_trending_markets(Some(n)).await,
+                    _ => self.add_log(
+                        LogLevel::Error,
+                        &format!("Invalid minimum liquidity: {}", floor),
+                    ),
+                },
+                _ => self.add_log(LogLevel::Warning, "Usage: /trending [min_liquidity]"),
+            },
             "/help" | "help" | "/h" | "?" => {
                 self.show_command_help();
             }
@@ -412,144 +1773,1122 @@ impl App {
         }
     }
 
-    async fn search_markets(&mut self, keyword: &str) {
+    async fn cancel_order(&mut self, order_id: &str) {
+        match self.execution_engine.cancel_order(order_id).await {
+            // Logged when the cancellation comes back over the event bus
+            Ok(true) => self.drain_bot_events(),
+            Ok(false) => self.add_log(
+                LogLevel::Warning,
+                &format!("No open order with ID {}", order_id),
+            ),
+            Err(e) => self.add_log(LogLevel::Error, &format!("Cancel failed: {}", e)),
+        }
+    }
+
+    async fn search_markets(&mut self, keyword: &str, filter: SearchFilter) {
+        self.add_log(
+            LogLevel::Info,
+            &format!("Searching markets: '{}'...", keyword),
+        );
+        self.market_search_query = keyword.to_string();
+        self.is_loading_markets = true;
+        self.current_tab = Tab::Markets;
+
+        // An explicit volume order also becomes the Markets tab's sort
+        if let Some(ascending) = filter.volume_ascending {
+            self.market_sort = MarketSort::Volume;
+            self.market_sort_ascending = ascending;
+        }
+
+        match self
+            .market_service
+            .search_markets(keyword, 50, &filter)
+            .await
+        {
+            Ok(markets) => {
+                let count = markets.len();
+                self.available_markets = markets;
+                self.apply_market_sort();
+                self.selected_market_index = 0;
+                self.is_loading_markets = false;
+                self.add_log(LogLevel::Success, &format!("Found {} markets", count));
+            }
+            Err(e) => {
+                self.is_loading_markets = false;
+                self.log_market_error("Search failed", &e);
+            }
+        }
+    }
+
+    /// Search for `keyword` and join the top matches in one step, up to the
+    /// configured cap so a broad keyword can't flood the watchlist
+    async fn watch_keyword(&mut self, keyword: &str) {
+        let cap = self.execution_engine.config().watch_max_markets;
+        self.add_log(
+            LogLevel::Info,
+            &format!("Watching top {} markets for '{}'...", cap, keyword),
+        );
+
+        // search_markets only returns open, CLOB-enabled markets
+        let markets = match self
+            .market_service
+            .search_markets(keyword, 50, &SearchFilter::default())
+            .await
+        {
+            Ok(markets) => markets,
+            Err(e) => {
+                self.log_market_error("Search failed", &e);
+                return;
+            }
+        };
+
+        let mut added = 0;
+        let mut skipped = 0;
+        for market in markets {
+            if added >= cap {
+                break;
+            }
+            if self.joined_markets.contains(&market.id) {
+                skipped += 1;
+                continue;
+            }
+
+            if self.join(market).await {
+                added += 1;
+            }
+        }
+
+        self.add_log(
+            if added > 0 {
+                LogLevel::Success
+            } else {
+                LogLevel::Warning
+            },
+            &format!(
+                "Added {} market(s) for '{}' ({} already watched)",
+                added, keyword, skipped
+            ),
+        );
+    }
+
+    /// Load the top markets by volume that have at least `min_liquidity` USDC
+    /// of liquidity into the Markets tab, `MIN_TRENDING_LIQUIDITY` if not given
+    async fn load_trending_markets(&mut self, min_liquidity: Option<f64>) {
+        let min_liquidity =
+            min_liquidity.unwrap_or(self.execution_engine.config().min_trending_liquidity);
+        self.add_log(LogLevel::Info, "Loading trending markets...");
+        self.market_search_query = "Trending".to_string();
+        self.is_loading_markets = true;
+        self.current_tab = Tab::Markets;
+
+        match self
+            .market_service
+            .get_trending_markets(20, Some(min_liquidity).filter(|&floor| floor > 0.0))
+            .await
+        {
+            Ok(markets) => {
+                let count = markets.len();
+                self.available_markets = markets;
+                self.apply_market_sort();
+                self.selected_market_index = 0;
+                self.is_loading_markets = false;
+                self.add_log(
+                    LogLevel::Success,
+                    &format!(
+                        "Loaded {} trending markets (liquidity ≥ ${:.0})",
+                        count, min_liquidity
+                    ),
+                );
+            }
+            Err(e) => {
+                self.is_loading_markets = false;
+                self.log_market_error("Failed to load trending", &e);
+            }
+        }
+    }
+
+    /// Report a market API failure. Rate limits and timeouts are transient so
+    /// they're only warnings, while a parse failure means the API changed under us.
+    fn log_market_error(&mut self, context: &str, error: &MarketServiceError) {
+        match error {
+            MarketServiceError::RateLimited => self.add_log(
+                LogLevel::Warning,
+                &format!("{}: rate limited, try again in a few seconds", context),
+            ),
+            MarketServiceError::Timeout => self.add_log(
+                LogLevel::Warning,
+                &format!("{}: the Polymarket API didn't respond in time", context),
+            ),
+            MarketServiceError::Parse(e) => self.add_log(
+                LogLevel::Error,
+                &format!("{}: unexpected API response format ({})", context, e),
+            ),
+            e => self.add_log(LogLevel::Error, &format!("{}: {}", context, e)),
+        }
+    }
+
+    /// Copy the ID of the market under the cursor in the Markets or Market Detail tab
+    fn copy_selected_market_id(&mut self) {
+        let market = if self.current_tab == Tab::Markets {
+            self.available_markets.get(self.selected_market_index)
+        } else {
+            self.watched_markets_info
+                .get(self.selected_watched_market_index)
+        };
+
+        let Some(market_id) = market.map(|m| m.id.clone()) else {
+            self.add_log(LogLevel::Warning, "No market selected to copy");
+            return;
+        };
+
+        match copy_to_clipboard(&market_id) {
+            Ok(()) => self.add_log(
+                LogLevel::Success,
+                &format!("Copied market ID {} to clipboard", market_id),
+            ),
+            Err(e) => self.add_log(
+                LogLevel::Warning,
+                &format!("Clipboard unavailable ({}); market ID: {}", e, market_id),
+            ),
+        }
+    }
+
+    /// Select a sort key for the Markets tab; picking the active key again flips direction
+    fn set_market_sort(&mut self, sort: MarketSort) {
+        if self.market_sort == sort {
+            self.market_sort_ascending = !self.market_sort_ascending;
+        } else {
+            self.market_sort = sort;
+            // Alphabetical starts A→Z, numeric columns start highest first
+            self.market_sort_ascending = sort == MarketSort::Question;
+        }
+
+        // Keep the cursor on the same market after reordering
+        let selected_id = self
+            .available_markets
+            .get(self.selected_market_index)
+            .map(|m| m.id.clone());
+        self.apply_market_sort();
+        if let Some(id) = selected_id {
+            self.selected_market_index = self
+                .available_markets
+                .iter()
+                .position(|m| m.id == id)
+                .unwrap_or(0);
+        }
+    }
+
+    fn apply_market_sort(&mut self) {
+        let sort = self.market_sort;
+        let ascending = self.market_sort_ascending;
+        self.available_markets.sort_by(|a, b| {
+            let ordering = sort.compare(a, b);
+            if ascending {
+                ordering
+            } else {
+                ordering.reverse()
+            }
+        });
+    }
+
+    /// Join the market at a 0-based position in `available_markets`
+    async fn join_by_index(&mut self, index: usize) {
+        match self.available_markets.get(index).cloned() {
+            Some(market) => {
+                self.join(market).await;
+            }
+            None => self.add_log(
+                LogLevel::Error,
+                &format!(
+                    "Invalid index: {}. Use 1-{}",
+                    index + 1,
+                    self.available_markets.len()
+                ),
+            ),
+        }
+    }
+
+    /// Join a market by its ID, using the loaded market details when available
+    async fn join_by_id(&mut self, market_id: &str) {
+        if let Some(market) = self
+            .available_markets
+            .iter()
+            .find(|m| m.id == market_id)
+            .cloned()
+        {
+            self.join(market).await;
+            return;
+        }
+
+        // Unknown details: track the ID only, it isn't persisted without them
+        if self.joined_markets.iter().any(|id| id == market_id) {
+            self.add_log(LogLevel::Warning, "Already monitoring this market");
+            return;
+        }
+        self.joined_markets.push(market_id.to_string());
+        self.load_recent_events(market_id).await;
+        self.add_log(LogLevel::Success, &format!("Joined market: {}", market_id));
+    }
+
+    /// Join the market a polymarket.com event or market URL points to
+    async fn join_by_url(&mut self, url: &str) {
+        let Some(slug) = crate::markets::parse_market_url(url) else {
+            self.add_log(
+                LogLevel::Error,
+                &format!("Not a Polymarket market URL: {}", url),
+            );
+            return;
+        };
+
+        let market = match self.market_service.get_market_by_slug(&slug).await {
+            Ok(Some(market)) => market,
+            Ok(None) => {
+                self.add_log(
+                    LogLevel::Error,
+                    &format!(
+                        "No market found for '{}' (for events with several markets, use the market's own URL)",
+                        slug
+                    ),
+                );
+                return;
+            }
+            Err(e) => {
+                self.log_market_error("Failed to resolve URL", &e);
+                return;
+            }
+        };
+
+        if !market.order_book_enabled {
+            self.add_log(
+                LogLevel::Error,
+                &format!("{} isn't tradable on the CLOB", market.question),
+            );
+            return;
+        }
+        self.join(market).await;
+    }
+
+    /// Fetch the order book for a market's first outcome and summarize it.
+    /// The levels are kept in `depth` for interfaces that print the full book.
+    async fn show_depth(&mut self, market_id: &str, levels: usize) {
+        let Some((market, mut book)) = self.fetch_order_book(market_id).await else {
+            return;
+        };
+        book.bids.truncate(levels);
+        book.asks.truncate(levels);
+
+        let outcome = market
+            .outcomes
+            .first()
+            .cloned()
+            .unwrap_or_else(|| "Yes".to_string());
+        let fmt = |p: Option<f64>| p.map_or("-".to_string(), |p| format!("{:.3}", p));
+        self.add_log(
+            LogLevel::Info,
+            &format!(
+                "Order book for {} ({}): bid {} / ask {}, spread {}, mid {}",
+                market.question,
+                outcome,
+                fmt(book.best_bid()),
+                fmt(book.best_ask()),
+                fmt(book.spread()),
+                fmt(book.mid_price())
+            ),
+        );
+
+        self.depth = Some(DepthView {
+            question: market.question,
+            outcome,
+            book,
+        });
+    }
+
+    /// `/depthchart`: log a cumulative depth histogram of a market's first
+    /// outcome, to spot where the liquidity walls are
+    async fn show_depth_chart(&mut self, market_id: &str, bins: usize) {
+        let Some((market, book)) = self.fetch_order_book(market_id).await else {
+            return;
+        };
+
+        let chart = book.depth_chart(bins, DEPTH_CHART_WIDTH);
+        if chart.is_empty() {
+            self.add_log(
+                LogLevel::Warning,
+                &format!("Order book for {} is empty", market.question),
+            );
+            return;
+        }
+
+        self.add_log(
+            LogLevel::Info,
+            &format!("─── Cumulative depth: {} ───", market.question),
+        );
+        for line in chart {
+            self.add_log(LogLevel::Info, &line);
+        }
+    }
+
+    /// `/connectivity`: ping the Polymarket APIs and report each round trip,
+    /// to tell a dropped connection apart from an empty refresh
+    async fn check_connectivity(&mut self) {
+        self.add_log(LogLevel::Info, "Checking Polymarket API connectivity...");
+        for check in self.market_service.check_connectivity().await {
+            let latency_ms = check.latency.as_millis();
+            match check.result {
+                Ok(status) => self.add_log(
+                    LogLevel::Success,
+                    &format!(
+                        "✓ {}: {} ms (HTTP {}) - {}",
+                        check.name, latency_ms, status, check.url
+                    ),
+                ),
+                Err(e) => self.add_log(
+                    LogLevel::Error,
+                    &format!(
+                        "✗ {}: unreachable after {} ms ({}) - {}",
+                        check.name, latency_ms, e, check.url
+                    ),
+                ),
+            }
+        }
+    }
+
+    /// Exercise each subsystem and log a pass/fail line per check. Every check
+    /// runs whatever the others report, so one failure can't hide another.
+    /// Returns how many checks passed.
+    async fn run_self_test(&mut self) -> usize {
+        self.add_log(LogLevel::Info, "─── Self-test ───");
+        let checks = [
+            ("Database", self.self_test_database().await),
+            ("Gamma API", self.self_test_gamma().await),
+            ("Wallet configured", self.self_test_wallet()),
+            ("Spike detector", self.self_test_detector().await),
+        ];
+
+        let total = checks.len();
+        let mut passed = 0;
+        for (name, result) in checks {
+            match result {
+                Ok(detail) => {
+                    passed += 1;
+                    self.add_log(LogLevel::Success, &format!("✓ {}: {}", name, detail));
+                }
+                Err(reason) => self.add_log(LogLevel::Error, &format!("✗ {}: {}", name, reason)),
+            }
+        }
+
+        let level = if passed == total {
+            LogLevel::Success
+        } else {
+            LogLevel::Warning
+        };
+        self.add_log(
+            level,
+            &format!("Self-test: {}/{} checks passed", passed, total),
+        );
+        passed
+    }
+
+    async fn self_test_database(&self) -> Result<String, String> {
+        let tables = crate::database::count_tables(&self.db_pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        let version = crate::database::schema_version(&self.db_pool)
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(format!("{} tables, schema version {}", tables, version))
+    }
+
+    async fn self_test_gamma(&self) -> Result<String, String> {
+        let started = Instant::now();
+        let markets = self
+            .market_service
+            .search_markets(SELF_TEST_QUERY, 1, &SearchFilter::default())
+            .await
+            .map_err(|e| e.to_string())?;
+        Ok(format!(
+            "search returned {} markets in {} ms",
+            markets.len(),
+            started.elapsed().as_millis()
+        ))
+    }
+
+    /// Whether startup produced a wallet for trading. This makes no CLOB
+    /// call, so it can't tell whether the session is still valid.
+    fn self_test_wallet(&self) -> Result<String, String> {
+        match (&self.wallet_address, self.paper_trading) {
+            (Some(address), _) => Ok(address.clone()),
+            (None, true) => Ok("none, paper trading doesn't need one".to_string()),
+            (None, false) => Err("none - orders can't reach the CLOB".to_string()),
+        }
+    }
+
+    /// Run a synthetic spike, a calm sample and a lopsided book through the
+    /// detection math with the live thresholds
+    async fn self_test_detector(&self) -> Result<String, String> {
+        let threshold = self.spike_detector.lock().await.volume_velocity_threshold();
+        let spike = crate::spike_detection::detect_velocity_spike(
+            "selftest",
+            Some((1_000.0, 0)),
+            1_000.0 + 2.0 * threshold,
+            1_000,
+            threshold,
+        );
+        let calm = crate::spike_detection::detect_velocity_spike(
+            "selftest",
+            Some((1_000.0, 0)),
+            1_000.0,
+            1_000,
+            threshold,
+        );
+        let obi = crate::spike_detection::order_book_imbalance(900.0, 100.0);
+
+        match (spike, calm) {
+            (Some(event), None) if (obi - 0.8).abs() < 1e-9 => Ok(format!(
+                "synthetic spike at {:.0} vol/sec over threshold {:.0}, OBI {:+.2}",
+                event.velocity, threshold, obi
+            )),
+            (None, _) => Err("synthetic spike was not detected".to_string()),
+            (_, Some(_)) => Err("flat volume was reported as a spike".to_string()),
+            _ => Err(format!("order book imbalance {:+.2}, expected +0.80", obi)),
+        }
+    }
+
+    /// Resolve a market and fetch the order book of its first outcome,
+    /// logging why if either isn't available
+    async fn fetch_order_book(&mut self, market_id: &str) -> Option<(MarketInfo, OrderBook)> {
+        let market = self.resolve_market(market_id).await?;
+
+        let Some(token_id) = market.token_ids.first() else {
+            self.add_log(
+                LogLevel::Warning,
+                &format!("Market {} has no order book", market_id),
+            );
+            return None;
+        };
+
+        match self
+            .market_service
+            .get_order_book(market_id, token_id)
+            .await
+        {
+            Ok(book) => Some((market, book)),
+            Err(e) => {
+                self.log_market_error("Failed to load order book", &e);
+                None
+            }
+        }
+    }
+
+    /// A market with its token IDs, from the search results or watchlist when
+    /// they have them and the API otherwise. Logs why if it can't be found.
+    async fn resolve_market(&mut self, market_id: &str) -> Option<MarketInfo> {
+        // Search results and the watchlist don't always carry token IDs
+        let known = self
+            .available_markets
+            .iter()
+            .chain(self.watched_markets_info.iter())
+            .find(|m| m.id == market_id && !m.token_ids.is_empty())
+            .cloned();
+        if known.is_some() {
+            return known;
+        }
+
+        match self.market_service.get_market(market_id).await {
+            Ok(Some(market)) => Some(market),
+            Ok(None) => {
+                self.add_log(LogLevel::Error, &format!("Market not found: {}", market_id));
+                None
+            }
+            Err(e) => {
+                self.log_market_error("Failed to load market", &e);
+                None
+            }
+        }
+    }
+
+    /// `/compare`: log two markets side by side, marking the better value of
+    /// each metric where one is better
+    async fn compare_markets(&mut self, first_id: &str, second_id: &str) {
+        let Some(first) = self.comparison_side(first_id).await else {
+            return;
+        };
+        let Some(second) = self.comparison_side(second_id).await else {
+            return;
+        };
+
+        self.add_log(LogLevel::Info, "─── Market comparison ───");
+        for line in comparison_lines(&first, &second) {
+            self.add_log(LogLevel::Info, &line);
+        }
+    }
+
+    /// The numbers `/compare` shows for one market. A missing order book or
+    /// velocity is shown as "-" rather than failing the comparison.
+    async fn comparison_side(&mut self, market_id: &str) -> Option<ComparisonSide> {
+        let market = self.resolve_market(market_id).await?;
+
+        let book = match market.token_ids.first() {
+            Some(token_id) => match self
+                .market_service
+                .get_order_book(market_id, token_id)
+                .await
+            {
+                Ok(book) => Some(book),
+                Err(e) => {
+                    tracing::debug!("No order book for {} in /compare: {}", market_id, e);
+                    None
+                }
+            },
+            None => None,
+        };
+        let velocity = self
+            .market_analysis_data
+            .get(&market.id)
+            .and_then(|a| a.current_velocity);
+
+        Some(ComparisonSide {
+            question: market.question,
+            price: market.prices.first().copied(),
+            volume: parse_volume(&market.volume),
+            velocity,
+            spread: book.as_ref().and_then(|b| b.spread()),
+            mid: book.as_ref().and_then(|b| b.mid_price()),
+        })
+    }
+
+    /// Persist a market to the watchlist and start tracking it.
+    /// Returns false if it was already watched or couldn't be saved.
+    async fn join(&mut self, market: MarketInfo) -> bool {
+        // Enriching swaps a search result's ID for the conditionId the
+        // watchlist stores, so only check for duplicates afterwards
+        let market = if market.outcomes.is_empty() {
+            self.enrich_market(market).await
+        } else {
+            market
+        };
+
+        if self.joined_markets.contains(&market.id) {
+            self.add_log(LogLevel::Warning, "Already monitoring this market");
+            return false;
+        }
+
+        if let Err(e) = crate::markets::save_watched_market(&self.db_pool, &market).await {
+            self.add_log(LogLevel::Error, &format!("Failed to save market: {}", e));
+            return false;
+        }
+
+        let market_id = market.id.clone();
+        let question = market.question.clone();
+        self.joined_markets.push(market_id.clone());
+        self.execution_engine.add_monitored_market(&market_id).await;
+        self.watched_markets_info.push(market);
+        self.load_recent_events(&market_id).await;
+        self.add_log(LogLevel::Success, &format!("Joined market: {}", question));
+        self.add_log(LogLevel::Info, &format!("ID: {}", market_id));
+        true
+    }
+
+    /// Public-search results carry no outcomes, prices or token IDs; fetch the
+    /// full market by ID so the watchlist stores them. Falls back to the
+    /// partial data if the fetch fails.
+    async fn enrich_market(&mut self, market: MarketInfo) -> MarketInfo {
+        let reason = match self.market_service.get_market(&market.id).await {
+            Ok(Some(full)) => return full,
+            Ok(None) => "market not found".to_string(),
+            Err(e) => e.to_string(),
+        };
         self.add_log(
-            LogLevel::Info,
-            &format!("Searching markets: '{}'...", keyword),
+            LogLevel::Warning,
+            &format!(
+                "Couldn't load prices for {} ({}); saving without them",
+                market.id, reason
+            ),
         );
-        self.market_search_query = keyword.to_string();
-        self.is_loading_markets = true;
-        self.current_tab = Tab::Markets;
+        market
+    }
 
-        match self.market_service.search_markets(keyword, 50).await {
-            Ok(markets) => {
-                let count = markets.len();
-                self.available_markets = markets;
-                self.selected_market_index = 0;
-                self.is_loading_markets = false;
-                self.add_log(LogLevel::Success, &format!("Found {} markets", count));
+    async fn leave_market(&mut self, market_id: &str) {
+        if let Some(pos) = self.joined_markets.iter().position(|m| m == market_id) {
+            // Remove from database
+            if let Err(e) = crate::markets::remove_watched_market(&self.db_pool, market_id).await {
+                self.add_log(LogLevel::Error, &format!("Failed to remove from DB: {}", e));
+                return;
             }
-            Err(e) => {
-                self.is_loading_markets = false;
-                self.add_log(LogLevel::Error, &format!("Search failed: {}", e));
+
+            self.joined_markets.remove(pos);
+            self.execution_engine
+                .remove_monitored_market(market_id)
+                .await;
+            if let Some(index) = self
+                .watched_markets_info
+                .iter()
+                .position(|m| m.id == market_id)
+            {
+                self.last_left_market = Some(self.watched_markets_info.remove(index));
             }
+            self.add_log(LogLevel::Info, &format!("Left market: {}", market_id));
+            self.ensure_visible_watched_selection();
+        } else {
+            self.add_log(
+                LogLevel::Warning,
+                &format!("Not monitoring market: {}", market_id),
+            );
         }
     }
 
-    async fn load_trending_markets(&mut self) {
-        self.add_log(LogLevel::Info, "Loading trending markets...");
-        self.market_search_query = "Trending".to_string();
-        self.is_loading_markets = true;
-        self.current_tab = Tab::Markets;
+    /// Rejoin the last market left, from its saved row. The undo is used up
+    /// either way.
+    async fn undo_leave(&mut self) {
+        let Some(market) = self.last_left_market.take() else {
+            self.add_log(LogLevel::Warning, "No left market to restore");
+            return;
+        };
+        if self.joined_markets.contains(&market.id) {
+            self.add_log(
+                LogLevel::Warning,
+                &format!("Already monitoring {} again", market.question),
+            );
+            return;
+        }
 
-        match self.market_service.get_trending_markets(20).await {
-            Ok(markets) => {
-                let count = markets.len();
-                self.available_markets = markets;
-                self.selected_market_index = 0;
-                self.is_loading_markets = false;
+        match crate::markets::restore_watched_market(&self.db_pool, &market.id).await {
+            Ok(true) => {}
+            Ok(false) => {
                 self.add_log(
-                    LogLevel::Success,
-                    &format!("Loaded {} trending markets", count),
+                    LogLevel::Error,
+                    &format!("{} is no longer in the database", market.id),
                 );
+                return;
             }
             Err(e) => {
-                self.is_loading_markets = false;
-                self.add_log(LogLevel::Error, &format!("Failed to load trending: {}", e));
+                self.add_log(LogLevel::Error, &format!("Failed to restore market: {}", e));
+                return;
             }
         }
+
+        let market_id = market.id.clone();
+        let question = market.question.clone();
+        self.joined_markets.push(market_id.clone());
+        self.execution_engine.add_monitored_market(&market_id).await;
+        self.watched_markets_info.push(market);
+        self.sort_watchlist();
+        self.load_recent_events(&market_id).await;
+        self.add_log(LogLevel::Success, &format!("Restored market: {}", question));
     }
 
-    async fn join_market(&mut self, market_ref: &str) {
-        // Check if it's an index number
-        if let Ok(index) = market_ref.parse::<usize>() {
-            if index > 0 && index <= self.available_markets.len() {
-                let market = self.available_markets[index - 1].clone();
-                let market_id = market.id.clone();
-                let question = market.question.clone();
+    /// Save a research note on a market, or clear it when `note` is empty
+    async fn set_note(&mut self, market_id: &str, note: &str) {
+        if let Err(e) = crate::markets::save_market_note(&self.db_pool, market_id, note).await {
+            self.add_log(LogLevel::Error, &format!("Failed to save note: {}", e));
+            return;
+        }
 
-                if !self.joined_markets.contains(&market_id) {
-                    // Save to database
-                    if let Err(e) =
-                        crate::markets::save_watched_market(&self.db_pool, &market).await
-                    {
-                        self.add_log(LogLevel::Error, &format!("Failed to save market: {}", e));
-                        return;
-                    }
+        let note = note.trim();
+        if !note.is_empty() {
+            self.market_notes
+                .insert(market_id.to_string(), note.to_string());
+            self.add_log(
+                LogLevel::Success,
+                &format!("📝 Note saved on {}", market_id),
+            );
+        } else if self.market_notes.remove(market_id).is_some() {
+            self.add_log(LogLevel::Info, &format!("Cleared note on {}", market_id));
+        } else {
+            self.add_log(
+                LogLevel::Info,
+                &format!("No note on {} to clear", market_id),
+            );
+        }
+    }
 
-                    self.joined_markets.push(market_id.clone());
-                    self.watched_markets_info.push(market);
-                    self.add_log(LogLevel::Success, &format!("Joined market: {}", question));
-                    self.add_log(LogLevel::Info, &format!("ID: {}", market_id));
-                } else {
-                    self.add_log(LogLevel::Warning, "Already monitoring this market");
-                }
+    /// Update a live spike detection threshold, logging the old and new values
+    async fn set_threshold(&mut self, kind: &str, value: f64) {
+        let mut detector = self.spike_detector.lock().await;
+        let (name, old, result) = match kind.to_lowercase().as_str() {
+            "velocity" | "vel" => (
+                "Velocity",
+                detector.volume_velocity_threshold(),
+                detector.set_volume_velocity_threshold(value),
+            ),
+            "obi" => (
+                "OBI",
+                detector.obi_threshold(),
+                detector.set_obi_threshold(value),
+            ),
+            _ => {
+                drop(detector);
+                self.add_log(
+                    LogLevel::Warning,
+                    &format!("Unknown threshold '{}', use velocity or obi", kind),
+                );
                 return;
-            } else {
+            }
+        };
+        self.velocity_threshold = detector.volume_velocity_threshold();
+        self.obi_threshold = detector.obi_threshold();
+        drop(detector);
+
+        match result {
+            Ok(()) => self.add_log(
+                LogLevel::Success,
+                &format!("{} threshold changed: {} -> {}", name, old, value),
+            ),
+            Err(e) => self.add_log(LogLevel::Error, &format!("{}", e)),
+        }
+    }
+
+    /// `/setthreshold <market_id> <kind> <value>`: override one threshold for a
+    /// single watched market, saved with the market
+    async fn set_market_threshold(&mut self, market_id: &str, kind: &str, value: f64) {
+        let mut detector = self.spike_detector.lock().await;
+        let (name, old, result) = match kind.to_lowercase().as_str() {
+            "velocity" | "vel" => (
+                "Velocity",
+                detector.velocity_threshold_for(market_id),
+                detector
+                    .set_market_velocity_threshold(market_id, value)
+                    .await,
+            ),
+            "obi" => (
+                "OBI",
+                detector.obi_threshold_for(market_id),
+                detector.set_market_obi_threshold(market_id, value).await,
+            ),
+            _ => {
+                drop(detector);
                 self.add_log(
-                    LogLevel::Error,
-                    &format!(
-                        "Invalid index: {}. Use 1-{}",
-                        index,
-                        self.available_markets.len()
-                    ),
+                    LogLevel::Warning,
+                    &format!("Unknown threshold '{}', use velocity or obi", kind),
                 );
                 return;
             }
+        };
+        self.market_thresholds = detector.market_thresholds().clone();
+        drop(detector);
+
+        match result {
+            Ok(()) => self.add_log(
+                LogLevel::Success,
+                &format!(
+                    "{} threshold for {} changed: {} -> {}",
+                    name, market_id, old, value
+                ),
+            ),
+            Err(e) => self.add_log(LogLevel::Error, &format!("{}", e)),
+        }
+    }
+
+    /// Refetch every watched market and warn about any that have closed or
+    /// resolve within `resolve_warning_hours`
+    async fn check_resolution(&mut self) {
+        if self.watched_markets_info.is_empty() {
+            self.add_log(LogLevel::Info, "No watched markets to check");
+            return;
         }
 
-        // Otherwise treat as market ID - try to find in available markets
-        let market_id = market_ref.to_string();
-        if !self.joined_markets.contains(&market_id) {
-            // Try to find the market in available_markets
-            if let Some(market) = self
-                .available_markets
-                .iter()
-                .find(|m| m.id == market_id)
-                .cloned()
-            {
-                if let Err(e) = crate::markets::save_watched_market(&self.db_pool, &market).await {
-                    self.add_log(LogLevel::Error, &format!("Failed to save market: {}", e));
-                    return;
+        let window =
+            chrono::Duration::hours(self.execution_engine.config().resolve_warning_hours as i64);
+        let now = chrono::Utc::now();
+        let mut flagged = 0;
+
+        // IDs up front: updates re-sort the watchlist as we go
+        let market_ids: Vec<String> = self
+            .watched_markets_info
+            .iter()
+            .map(|m| m.id.clone())
+            .collect();
+        let results = self.market_service.get_markets(&market_ids).await;
+
+        for (market_id, result) in market_ids.into_iter().zip(results) {
+            let latest = match result {
+                Ok(Some(latest)) => latest,
+                Ok(None) => {
+                    self.add_log(
+                        LogLevel::Warning,
+                        &format!("Market not found: {}", market_id),
+                    );
+                    continue;
+                }
+                Err(e) => {
+                    self.log_market_error("Resolution check failed", &e);
+                    continue;
                 }
-                self.watched_markets_info.push(market.clone());
+            };
+
+            // Keep the watchlist's status current so closed markets are flagged
+            let question = latest.question.clone();
+            self.apply_market_update(&market_id, latest.clone()).await;
+
+            if !latest.active {
+                flagged += 1;
+                self.add_log(LogLevel::Error, &format!("CLOSED: {}", question));
+            } else if let Some(end) = latest.end_time().filter(|end| *end - now <= window) {
+                flagged += 1;
+                let hours = (end - now).num_hours().max(0);
+                self.add_log(
+                    LogLevel::Warning,
+                    &format!(
+                        "Ends in {}h ({}): {}",
+                        hours,
+                        end.format("%Y-%m-%d %H:%M UTC"),
+                        question
+                    ),
+                );
             }
+        }
 
-            self.joined_markets.push(market_id.clone());
-            self.add_log(LogLevel::Success, &format!("Joined market: {}", market_id));
-        } else {
-            self.add_log(LogLevel::Warning, "Already monitoring this market");
+        if flagged == 0 {
+            self.add_log(
+                LogLevel::Success,
+                "No watched markets closed or ending soon",
+            );
         }
     }
 
-    async fn leave_market(&mut self, market_id: &str) {
-        if let Some(pos) = self.joined_markets.iter().position(|m| m == market_id) {
-            // Remove from database
-            if let Err(e) = crate::markets::remove_watched_market(&self.db_pool, market_id).await {
-                self.add_log(LogLevel::Error, &format!("Failed to remove from DB: {}", e));
+    /// Write orders, trades and spike events to `dir` for offline analysis
+    async fn export_data(&mut self, dir: &std::path::Path, format: ExportFormat) {
+        let data = match load_export_data(&self.db_pool).await {
+            Ok(data) => data,
+            Err(e) => {
+                self.add_log(LogLevel::Error, &format!("Export failed: {}", e));
                 return;
             }
+        };
+        match write_export(&data, dir, format) {
+            Ok(_) => self.add_log(
+                LogLevel::Success,
+                &format!(
+                    "Exported {} orders, {} trades and {} events to {}",
+                    data.orders.len(),
+                    data.trades.len(),
+                    data.events.len(),
+                    dir.display()
+                ),
+            ),
+            Err(e) => self.add_log(LogLevel::Error, &format!("Export failed: {:#}", e)),
+        }
+    }
 
-            self.joined_markets.remove(pos);
-            self.watched_markets_info.retain(|m| m.id != market_id);
-            self.add_log(LogLevel::Info, &format!("Left market: {}", market_id));
-        } else {
+    /// List the `n` watched markets with the highest absolute velocity.
+    /// Ties go to the market with the most recent spike event.
+    fn show_top_markets(&mut self, n: usize) {
+        let mut ranked: Vec<(String, f64, Option<f64>, i64)> = self
+            .watched_markets_info
+            .iter()
+            .filter_map(|market| {
+                let analysis = self.market_analysis_data.get(&market.id)?;
+                let velocity = analysis.current_velocity?;
+                let last_event = analysis
+                    .recent_events
+                    .first()
+                    .map_or(i64::MIN, |e| e.timestamp);
+                Some((
+                    market.question.clone(),
+                    velocity,
+                    analysis.current_obi,
+                    last_event,
+                ))
+            })
+            .collect();
+
+        if ranked.is_empty() {
+            self.add_log(LogLevel::Info, "No velocity data for watched markets yet");
+            return;
+        }
+
+        ranked.sort_by(|a, b| b.1.abs().total_cmp(&a.1.abs()).then_with(|| b.3.cmp(&a.3)));
+
+        self.add_log(LogLevel::Info, "─── Top Markets by Velocity ───");
+        for (i, (question, velocity, obi, _)) in ranked.into_iter().take(n).enumerate() {
+            let obi = obi.map_or("-".to_string(), |o| format!("{:+.2}", o));
+            self.add_log(
+                LogLevel::Info,
+                &format!(
+                    "{}. {:>+9.1} vol/s  OBI {:>5}  {}",
+                    i + 1,
+                    velocity,
+                    obi,
+                    question
+                ),
+            );
+        }
+    }
+
+    /// `/findarb`: log watched binary markets whose YES + NO prices sum
+    /// outside 1 ± `ARB_FEE_BUFFER`
+    fn find_arbitrage(&mut self) {
+        let fee_buffer = self.execution_engine.config().arb_fee_buffer;
+        let signals = crate::markets::find_arbitrage(&self.watched_markets_info, fee_buffer);
+
+        if signals.is_empty() {
+            self.add_log(
+                LogLevel::Info,
+                &format!(
+                    "No mispriced binary markets (prices within 1 ± {})",
+                    fee_buffer
+                ),
+            );
+            return;
+        }
+
+        self.add_log(LogLevel::Info, "─── Complementary Mispricing ───");
+        for signal in signals {
+            let [yes, no] = signal.prices;
+            let action = if signal.edge > 0.0 {
+                "buy both"
+            } else {
+                "sell both"
+            };
             self.add_log(
                 LogLevel::Warning,
-                &format!("Not monitoring market: {}", market_id),
+                &format!(
+                    "{:.3} + {:.3} = {:.3}  edge {:+.1}% ({})  {}",
+                    yes,
+                    no,
+                    yes + no,
+                    signal.edge * 100.0,
+                    action,
+                    signal.question
+                ),
             );
         }
     }
 
+    /// List the markets the execution engine accepts orders on
+    async fn show_monitored_markets(&mut self) {
+        let monitored = self.execution_engine.monitored_markets().await;
+        if monitored.is_empty() {
+            self.add_log(LogLevel::Info, "No monitored markets - use /joinmarket");
+            return;
+        }
+
+        self.add_log(
+            LogLevel::Info,
+            &format!("─── Monitored Markets ({}) ───", monitored.len()),
+        );
+        for market_id in monitored {
+            let question = self
+                .watched_markets_info
+                .iter()
+                .find(|m| m.id == market_id)
+                .map(|m| m.question.clone())
+                .unwrap_or_default();
+            self.add_log(LogLevel::Info, &format!("{}  {}", market_id, question));
+        }
+    }
+
+    /// Log a summary of activity since launch
+    async fn show_stats(&mut self) {
+        let spikes = self.spike_detector.lock().await.spikes_detected();
+        let uptime = self.execution_engine.uptime().as_secs();
+
+        let stats = [
+            (
+                "Uptime",
+                format!(
+                    "{}h {:02}m {:02}s",
+                    uptime / 3600,
+                    uptime % 3600 / 60,
+                    uptime % 60
+                ),
+            ),
+            (
+                "Orders placed",
+                self.execution_engine.orders_placed().to_string(),
+            ),
+            ("Spikes detected", spikes.to_string()),
+            ("Watched markets", self.joined_markets.len().to_string()),
+            ("API calls", self.market_service.api_calls().to_string()),
+        ];
+
+        self.add_log(LogLevel::Info, "─── Session Stats ───");
+        for (label, value) in stats {
+            self.add_log(LogLevel::Info, &format!("{:<16} {}", label, value));
+        }
+    }
+
     fn show_command_help(&mut self) {
         self.add_log(LogLevel::Info, "─── Available Commands ───");
         self.add_log(LogLevel::Info, "/search <keyword>  - Search markets");
-        self.add_log(LogLevel::Info, "/trending          - Show trending markets");
         self.add_log(
             LogLevel::Info,
-            "/joinmarket <id|#> - Join market by ID or index",
+            "  --minvol <n> hides low-volume markets, --asc/--desc sort by volume",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/trending [min_liq] - Show liquid trending markets",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/joinmarket <id|#n> - Join market by ID or #index",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/joinurl <url>     - Join a market from its polymarket.com link",
         );
         self.add_log(LogLevel::Info, "/leavemarket <id>  - Leave a market");
+        self.add_log(
+            LogLevel::Info,
+            "/unleave           - Rejoin the last market left (Ctrl-Z)",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/watch <keyword>   - Join the top markets matching a keyword",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/note <id> [text]  - Annotate a market (no text clears the note)",
+        );
+        self.add_log(LogLevel::Info, "/cancel <order_id> - Cancel an open order");
+        self.add_log(
+            LogLevel::Info,
+            "/stats             - Show session statistics",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/markets           - List monitored market IDs",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/top [n]           - Rank watched markets by velocity",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/findarb           - Flag binary markets whose prices don't sum to 1",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/export [--format csv|json] [dir] - Export orders, trades and events",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/resolve           - Flag watched markets that closed or end soon",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/connectivity      - Ping the Polymarket APIs and show latency",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/selftest          - Check the database, API, wallet and detector",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/depth <id> [n]    - Show the top n order book levels",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/compare <id> <id> - Show two markets side by side",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/depthchart <id> [bins] - Chart cumulative bid/ask depth",
+        );
+        self.add_log(
+            LogLevel::Info,
+            "/setthreshold [market_id] <velocity|obi> <value> - Tune spike detection",
+        );
         self.add_log(LogLevel::Info, "/help              - Show this help");
     }
 
@@ -559,7 +2898,7 @@ impl App {
             match event.code {
                 KeyCode::Up | KeyCode::Char('k') => {
                     if self.docs_viewing_content {
-                        self.docs_scroll_offset = self.docs_scroll_offset.saturating_sub(1);
+                        self.docs_scroll_up();
                     } else if self.docs_selected_section > 0 {
                         self.docs_selected_section -= 1;
                     }
@@ -567,16 +2906,7 @@ impl App {
                 }
                 KeyCode::Down | KeyCode::Char('j') => {
                     if self.docs_viewing_content {
-                        // Line counts for each section (approximate, allows some scrolling past end)
-                        const DOC_LINE_COUNTS: [u16; 5] = [38, 37, 40, 35, 38];
-                        let max_scroll = DOC_LINE_COUNTS
-                            .get(self.docs_selected_section)
-                            .copied()
-                            .unwrap_or(30)
-                            .saturating_sub(10); // Stop ~10 lines before end so content stays visible
-                        if self.docs_scroll_offset < max_scroll {
-                            self.docs_scroll_offset = self.docs_scroll_offset.saturating_add(1);
-                        }
+                        self.docs_scroll_down();
                     } else if self.docs_selected_section < 4 {
                         // 5 sections (0-4)
                         self.docs_selected_section += 1;
@@ -608,8 +2938,11 @@ impl App {
                     return Ok(());
                 }
                 KeyCode::Char('q') | KeyCode::Char('Q') => {
-                    self.input_mode = InputMode::QuitConfirmation;
-                    self.quit_selection = QuitSelection::No;
+                    self.request_quit(event.code);
+                    return Ok(());
+                }
+                KeyCode::Char('?') => {
+                    self.input_mode = InputMode::HelpOverlay;
                     return Ok(());
                 }
                 KeyCode::Char('1') => {
@@ -636,6 +2969,10 @@ impl App {
                     self.current_tab = Tab::Docs;
                     return Ok(());
                 }
+                KeyCode::Char('7') => {
+                    self.current_tab = Tab::Settings;
+                    return Ok(());
+                }
                 KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
                     self.should_quit = true;
                     return Ok(());
@@ -660,49 +2997,139 @@ impl App {
 
             // Quick trending
             KeyCode::Char('t') | KeyCode::Char('T') => {
-                self.load_trending_markets().await;
+                self.load_trending_markets(None).await;
+            }
+
+            // Logs scrolling (newest entries are at the top)
+            KeyCode::Up | KeyCode::Char('k') if self.current_tab == Tab::Logs => {
+                self.scroll_logs(-1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.current_tab == Tab::Logs => {
+                self.scroll_logs(1);
+            }
+            KeyCode::PageUp if self.current_tab == Tab::Logs => {
+                self.scroll_logs(-(LOGS_PAGE_SIZE as isize));
+            }
+            KeyCode::PageDown if self.current_tab == Tab::Logs => {
+                self.scroll_logs(LOGS_PAGE_SIZE as isize);
+            }
+            KeyCode::Home if self.current_tab == Tab::Logs => {
+                self.logs_scroll_offset = 0;
+            }
+            KeyCode::End if self.current_tab == Tab::Logs => {
+                self.scroll_logs(isize::MAX);
+            }
+            KeyCode::Char('f') | KeyCode::Char('F') if self.current_tab == Tab::Logs => {
+                self.input_mode = InputMode::LogSearch;
+            }
+            KeyCode::Char('l') | KeyCode::Char('L') if self.current_tab == Tab::Logs => {
+                self.log_filter = self.log_filter.next();
+                self.logs_scroll_offset = 0;
+            }
+
+            KeyCode::Char('v') | KeyCode::Char('V') if self.current_tab == Tab::Markets => {
+                self.set_market_sort(MarketSort::Volume);
+            }
+            KeyCode::Char('$') if self.current_tab == Tab::Markets => {
+                self.set_market_sort(MarketSort::Price);
+            }
+            KeyCode::Char('%') if self.current_tab == Tab::Dashboard => {
+                self.pnl_as_percent = !self.pnl_as_percent;
+                let mode = if self.pnl_as_percent {
+                    "% of starting capital"
+                } else {
+                    "dollars"
+                };
+                self.add_log(LogLevel::Info, &format!("P&L shown in {}", mode));
+            }
+            KeyCode::Char('a') | KeyCode::Char('A') if self.current_tab == Tab::Markets => {
+                self.set_market_sort(MarketSort::Question);
+            }
+
+            KeyCode::Char('f') if self.current_tab == Tab::MarketDetail => {
+                self.toggle_favorite().await;
+            }
+            KeyCode::Char('F')
+                if matches!(self.current_tab, Tab::Dashboard | Tab::MarketDetail) =>
+            {
+                self.toggle_favorites_only();
+            }
+
+            KeyCode::Char('y') | KeyCode::Char('Y')
+                if matches!(self.current_tab, Tab::Markets | Tab::MarketDetail) =>
+            {
+                self.copy_selected_market_id();
             }
 
             // Market navigation (when in Markets or MarketDetail tab)
+            KeyCode::Char('x') | KeyCode::Char('X')
+                if self.current_tab == Tab::Orders && !self.active_orders.is_empty() =>
+            {
+                let order_id = self.active_orders[self.selected_order_index]
+                    .order_id
+                    .clone();
+                self.cancel_order(&order_id).await;
+            }
+
+            // Force a refresh (R resumes the bot outside Market Detail)
+            KeyCode::F(5) => self.refresh_requested = true,
+            KeyCode::Char('r') | KeyCode::Char('R') if self.current_tab == Tab::MarketDetail => {
+                self.refresh_requested = true;
+            }
+
+            // Settings editing
+            KeyCode::Up | KeyCode::Char('k') if self.current_tab == Tab::Settings => {
+                self.settings_selected = self.settings_selected.saturating_sub(1);
+            }
+            KeyCode::Down | KeyCode::Char('j') if self.current_tab == Tab::Settings => {
+                self.settings_selected =
+                    (self.settings_selected + 1).min(SettingsField::all().len() - 1);
+            }
+            KeyCode::Enter if self.current_tab == Tab::Settings => {
+                let value = self.settings_value(self.selected_settings_field());
+                self.settings_input = value.to_string();
+                self.input_mode = InputMode::SettingsEdit;
+            }
+
             KeyCode::Up | KeyCode::Char('k') => {
-                if self.current_tab == Tab::Markets && self.selected_market_index > 0 {
+                if self.current_tab == Tab::Orders && self.selected_order_index > 0 {
+                    self.selected_order_index -= 1;
+                } else if self.current_tab == Tab::Markets && self.selected_market_index > 0 {
                     self.selected_market_index -= 1;
-                } else if self.current_tab == Tab::MarketDetail
-                    && self.selected_watched_market_index > 0
-                {
-                    self.selected_watched_market_index -= 1;
+                } else if self.current_tab == Tab::MarketDetail {
+                    self.step_watched_selection(false);
                 }
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                if self.current_tab == Tab::Markets
+                if self.current_tab == Tab::Orders
+                    && self.selected_order_index < self.active_orders.len().saturating_sub(1)
+                {
+                    self.selected_order_index += 1;
+                } else if self.current_tab == Tab::Markets
                     && self.selected_market_index < self.available_markets.len().saturating_sub(1)
                 {
                     self.selected_market_index += 1;
-                } else if self.current_tab == Tab::MarketDetail
-                    && self.selected_watched_market_index
-                        < self.watched_markets_info.len().saturating_sub(1)
-                {
-                    self.selected_watched_market_index += 1;
+                } else if self.current_tab == Tab::MarketDetail {
+                    self.step_watched_selection(true);
                 }
             }
-            KeyCode::Enter => {
-                if self.current_tab == Tab::Markets && !self.available_markets.is_empty() {
-                    let index = (self.selected_market_index + 1).to_string();
-                    self.join_market(&index).await;
-                }
+            KeyCode::Enter
+                if self.current_tab == Tab::Markets && !self.available_markets.is_empty() =>
+            {
+                self.join_by_index(self.selected_market_index).await;
             }
             // Leave market - show confirmation modal (Delete or Backspace in MarketDetail tab)
-            KeyCode::Delete | KeyCode::Backspace => {
-                if self.current_tab == Tab::MarketDetail && !self.watched_markets_info.is_empty() {
-                    self.input_mode = InputMode::LeaveMarketConfirmation;
-                    self.leave_selection = LeaveSelection::No;
-                }
+            KeyCode::Delete | KeyCode::Backspace
+                if self.current_tab == Tab::MarketDetail
+                    && !self.watched_markets_info.is_empty() =>
+            {
+                self.input_mode = InputMode::LeaveMarketConfirmation;
+                self.leave_selection = LeaveSelection::No;
             }
 
-            // Quit - show confirmation modal
+            // Quit - show confirmation modal (Shift-Q skips it)
             KeyCode::Char('q') | KeyCode::Char('Q') => {
-                self.input_mode = InputMode::QuitConfirmation;
-                self.quit_selection = QuitSelection::No; // Default to No
+                self.request_quit(event.code);
             }
 
             // Tab navigation
@@ -720,17 +3147,17 @@ impl App {
             KeyCode::Char('4') => self.current_tab = Tab::MarketDetail,
             KeyCode::Char('5') => self.current_tab = Tab::Logs,
             KeyCode::Char('6') => self.current_tab = Tab::Docs,
+            KeyCode::Char('7') => self.current_tab = Tab::Settings,
 
             // Pause/Resume
+            // Logged when the change comes back over the event bus
             KeyCode::Char('p') | KeyCode::Char('P') => {
                 self.execution_engine.pause().await;
-                self.is_paused = true;
-                self.add_log(LogLevel::Warning, "Bot PAUSED - trading disabled");
+                self.drain_bot_events();
             }
             KeyCode::Char('r') | KeyCode::Char('R') => {
                 self.execution_engine.resume().await;
-                self.is_paused = false;
-                self.add_log(LogLevel::Success, "Bot RESUMED - trading enabled");
+                self.drain_bot_events();
             }
 
             // Panic mode
@@ -739,18 +3166,30 @@ impl App {
                 match self.execution_engine.cancel_all_orders().await {
                     Ok(count) => {
                         self.add_log(LogLevel::Error, &format!("Cancelled {} orders", count));
-                        self.add_log(LogLevel::Error, "Bot is now PAUSED");
                     }
                     Err(e) => {
                         self.add_log(LogLevel::Error, &format!("Panic error: {}", e));
                     }
                 }
-                self.is_paused = true;
+                self.drain_bot_events();
             }
 
-            // Export
+            // Export, like `/export` with no arguments
             KeyCode::Char('e') | KeyCode::Char('E') => {
-                self.add_log(LogLevel::Info, "Export feature coming soon...");
+                self.export_data(std::path::Path::new(DEFAULT_EXPORT_DIR), ExportFormat::Csv)
+                    .await;
+            }
+
+            // Order entry for the selected watched market
+            KeyCode::Char('o') | KeyCode::Char('O')
+                if matches!(self.current_tab, Tab::Orders | Tab::MarketDetail) =>
+            {
+                self.open_order_form();
+            }
+
+            // Keybindings for the current tab
+            KeyCode::Char('?') => {
+                self.input_mode = InputMode::HelpOverlay;
             }
 
             // Help
@@ -762,9 +3201,12 @@ impl App {
                 self.add_log(LogLevel::Info, "Tab/←/→  : Navigate tabs");
                 self.add_log(LogLevel::Info, "↑/↓      : Navigate markets list");
                 self.add_log(LogLevel::Info, "Enter    : Join selected market");
+                self.add_log(LogLevel::Info, "O        : New order (Orders/Detail)");
                 self.add_log(LogLevel::Info, "P        : Pause bot");
                 self.add_log(LogLevel::Info, "R        : Resume bot");
                 self.add_log(LogLevel::Info, "!        : PANIC mode");
+                self.add_log(LogLevel::Info, "E        : Export history as CSV");
+                self.add_log(LogLevel::Info, "?        : Keys for this tab");
                 self.add_log(LogLevel::Info, "Q        : Quit");
             }
 
@@ -772,6 +3214,10 @@ impl App {
             KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.should_quit = true;
             }
+            // Ctrl+Z undoes the last leave
+            KeyCode::Char('z') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.undo_leave().await;
+            }
 
             _ => {}
         }
@@ -779,8 +3225,30 @@ impl App {
         Ok(())
     }
 
+    /// `q` opens the quit confirmation, defaulting to No; Shift-Q quits at once
+    fn request_quit(&mut self, key: KeyCode) {
+        if key == KeyCode::Char('Q') {
+            self.should_quit = true;
+            return;
+        }
+        self.input_mode = InputMode::QuitConfirmation;
+        self.quit_selection = QuitSelection::No;
+        self.quit_requested_at = Some(Instant::now());
+    }
+
     fn handle_quit_confirmation(&mut self, event: KeyEvent) -> Result<()> {
         match event.code {
+            // Ctrl+C, or pressing q again right away, quits without confirming
+            KeyCode::Char('c') if event.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.should_quit = true;
+            }
+            KeyCode::Char('q') | KeyCode::Char('Q')
+                if self
+                    .quit_requested_at
+                    .is_some_and(|at| at.elapsed() <= FAST_QUIT_WINDOW) =>
+            {
+                self.should_quit = true;
+            }
             // Toggle selection with Left/Right or Tab
             KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab => {
                 self.quit_selection = match self.quit_selection {
@@ -842,4 +3310,972 @@ impl App {
         }
         Ok(())
     }
+
+    /// Open the order-entry form for the selected watched market
+    fn open_order_form(&mut self) {
+        let index = self
+            .selected_watched_market_index
+            .min(self.watched_markets_info.len().saturating_sub(1));
+        match self.watched_markets_info.get(index) {
+            Some(market) if !market.active => {
+                self.add_log(
+                    LogLevel::Error,
+                    "This market is closed - orders can't be placed",
+                );
+            }
+            Some(market) => {
+                self.order_form = Some(OrderForm::new(market));
+                self.input_mode = InputMode::OrderEntry;
+            }
+            None => {
+                self.add_log(
+                    LogLevel::Warning,
+                    "Join a market first - orders are placed on the selected watched market",
+                );
+            }
+        }
+    }
+
+    fn handle_order_entry(&mut self, event: KeyEvent) -> Result<()> {
+        let Some(form) = self.order_form.as_mut() else {
+            self.input_mode = InputMode::Normal;
+            return Ok(());
+        };
+
+        match (form.step, event.code) {
+            (_, KeyCode::Esc) => {
+                self.order_form = None;
+                self.input_mode = InputMode::Normal;
+                self.add_log(LogLevel::Info, "Order entry cancelled");
+                return Ok(());
+            }
+            // Side: toggle BUY/SELL
+            (
+                OrderFormStep::Side,
+                KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab,
+            ) => {
+                form.side = if form.side == "BUY" { "SELL" } else { "BUY" }.to_string();
+            }
+            (OrderFormStep::Side, KeyCode::Char('b') | KeyCode::Char('B')) => {
+                form.side = "BUY".to_string();
+            }
+            (OrderFormStep::Side, KeyCode::Char('s') | KeyCode::Char('S')) => {
+                form.side = "SELL".to_string();
+            }
+            (OrderFormStep::Side, KeyCode::Enter) => {
+                form.step = OrderFormStep::Size;
+            }
+            // Number keys fill the size from a preset and skip to the price
+            (OrderFormStep::Side, KeyCode::Char(c @ '1'..='9')) => {
+                let presets = &self.execution_engine.config().order_size_presets;
+                let index = c as usize - '1' as usize;
+                match presets.get(index) {
+                    Some(size) => {
+                        form.size_input = size.to_string();
+                        form.step = OrderFormStep::Price;
+                        form.error = None;
+                    }
+                    None => form.error = Some(format!("No size preset {}", c)),
+                }
+            }
+            // Size and price: digits and one decimal point, range-checked on Enter
+            (OrderFormStep::Size | OrderFormStep::Price, KeyCode::Char(c)) => {
+                let input = if form.step == OrderFormStep::Size {
+                    &mut form.size_input
+                } else {
+                    &mut form.price_input
+                };
+                if is_numeric_input_char(input, c, false) {
+                    input.push(c);
+                    form.error = None;
+                } else {
+                    form.error = Some(format!("'{}' ignored: digits and one '.' only", c));
+                }
+            }
+            // Backspace on an empty field steps back to the previous field
+            (OrderFormStep::Size, KeyCode::Backspace) if form.size_input.is_empty() => {
+                form.step = OrderFormStep::Side;
+            }
+            (OrderFormStep::Price, KeyCode::Backspace) if form.price_input.is_empty() => {
+                form.step = OrderFormStep::Size;
+            }
+            (OrderFormStep::Size, KeyCode::Backspace) => {
+                form.size_input.pop();
+            }
+            (OrderFormStep::Price, KeyCode::Backspace) => {
+                form.price_input.pop();
+            }
+            (OrderFormStep::Size, KeyCode::Enter) => {
+                let config = self.execution_engine.config();
+                form.error = match form.size_input.trim().parse::<f64>() {
+                    Ok(size) if size < config.min_order_size => {
+                        Some(format!("Size below minimum: {}", config.min_order_size))
+                    }
+                    Ok(size) if size > config.max_order_size => {
+                        Some(format!("Size exceeds maximum: {}", config.max_order_size))
+                    }
+                    Ok(_) => None,
+                    Err(_) => Some("Enter a numeric size".to_string()),
+                };
+                if form.error.is_none() {
+                    form.step = OrderFormStep::Price;
+                }
+            }
+            (OrderFormStep::Price, KeyCode::Enter) => {
+                // An empty price means a market order
+                let price = form.price_input.trim();
+                let price = if price.is_empty() {
+                    Ok(None)
+                } else {
+                    match price.parse::<f64>() {
+                        Ok(p) if (0.0..=1.0).contains(&p) => Ok(Some(p)),
+                        Ok(_) => Err("Price must be between 0.00 and 1.00".to_string()),
+                        Err(_) => Err("Enter a numeric price".to_string()),
+                    }
+                };
+
+                match price {
+                    Ok(price) => {
+                        let size = form.size_input.trim().parse::<f64>().unwrap_or_default();
+                        let order = PendingOrder {
+                            market_id: form.market_id.clone(),
+                            question: form.question.clone(),
+                            side: form.side.clone(),
+                            order_type: if price.is_some() {
+                                OrderType::Limit
+                            } else {
+                                OrderType::Market
+                            },
+                            size,
+                            price,
+                            outcome: 0,
+                        };
+                        self.order_form = None;
+                        self.request_order_confirmation(order);
+                    }
+                    Err(e) => form.error = Some(e),
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Show the order confirmation modal for an order
+    pub fn request_order_confirmation(&mut self, order: PendingOrder) {
+        self.pending_order = Some(order);
+        self.order_selection = OrderSelection::No;
+        self.input_mode = InputMode::OrderConfirmation;
+    }
+
+    async fn handle_order_confirmation(&mut self, event: KeyEvent) -> Result<()> {
+        match event.code {
+            // Toggle selection with Left/Right or Tab
+            KeyCode::Left | KeyCode::Right | KeyCode::Tab | KeyCode::BackTab => {
+                self.order_selection = match self.order_selection {
+                    OrderSelection::No => OrderSelection::Yes,
+                    OrderSelection::Yes => OrderSelection::No,
+                };
+            }
+            // Confirm selection with Enter
+            KeyCode::Enter => {
+                if let Some(order) = self.pending_order.take() {
+                    if self.order_selection == OrderSelection::Yes {
+                        self.submit_order(order).await;
+                    } else {
+                        self.add_log(LogLevel::Info, "Order cancelled");
+                    }
+                }
+                self.input_mode = InputMode::Normal;
+            }
+            // Cancel with Escape
+            KeyCode::Esc => {
+                self.pending_order = None;
+                self.input_mode = InputMode::Normal;
+                self.add_log(LogLevel::Info, "Order cancelled");
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Check the spread of the traded outcome's order book before submitting.
+    /// Returns false when the order should not go out. If the book can't be
+    /// found the check is skipped with a warning and the order proceeds.
+    async fn check_order_liquidity(&mut self, order: &PendingOrder) -> bool {
+        // Watchlist rows loaded from the database carry no token IDs
+        let token_id = self
+            .resolve_market(&order.market_id)
+            .await
+            .and_then(|m| m.token_ids.get(order.outcome).cloned());
+        let Some(token_id) = token_id else {
+            self.add_log(
+                LogLevel::Warning,
+                &format!(
+                    "Spread check skipped: no order book found for {}",
+                    order.market_id
+                ),
+            );
+            return true;
+        };
+
+        let book = match self
+            .market_service
+            .get_order_book(&order.market_id, &token_id)
+            .await
+        {
+            Ok(book) => book,
+            Err(e) => {
+                self.log_market_error("Spread check skipped", &e);
+                return true;
+            }
+        };
+
+        match self.execution_engine.check_spread(order.order_type, &book) {
+            Ok(None) => true,
+            Ok(Some(warning)) => {
+                self.add_log(LogLevel::Warning, &warning);
+                true
+            }
+            Err(e) => {
+                self.add_log(LogLevel::Error, &format!("Order rejected: {}", e));
+                false
+            }
+        }
+    }
+
+    async fn submit_order(&mut self, order: PendingOrder) {
+        if !self.check_order_liquidity(&order).await {
+            return;
+        }
+
+        match self
+            .execution_engine
+            .place_order(
+                &order.market_id,
+                &order.side,
+                order.order_type,
+                order.size,
+                order.price,
+            )
+            .await
+        {
+            Ok(_) => {
+                self.add_log(
+                    LogLevel::Success,
+                    &format!(
+                        "Placed {} {} order: {} @ {}",
+                        order.order_type,
+                        order.side,
+                        order.size,
+                        order
+                            .price
+                            .map(|p| format!("${:.2}", p))
+                            .unwrap_or_else(|| "market".to_string())
+                    ),
+                );
+                self.drain_bot_events();
+            }
+            Err(e) => {
+                self.add_log(LogLevel::Error, &format!("Order rejected: {}", e));
+            }
+        }
+    }
+}
+
+/// Split `/search` arguments into the keyword and its `--minvol`, `--asc` and
+/// `--desc` options. Returns `None` if an option is malformed.
+fn parse_search_args(args: &[&str]) -> Option<(String, SearchFilter)> {
+    let mut filter = SearchFilter::default();
+    let mut words = Vec::new();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match *arg {
+            "--minvol" => filter.min_volume = Some(args.next()?.parse().ok()?),
+            "--asc" => filter.volume_ascending = Some(true),
+            "--desc" => filter.volume_ascending = Some(false),
+            word => words.push(word),
+        }
+    }
+    Some((words.join(" "), filter))
+}
+
+/// Whether `c` may be typed after `input` in a numeric field: digits, a single
+/// decimal point and, for `signed` fields, a leading minus
+fn is_numeric_input_char(input: &str, c: char, signed: bool) -> bool {
+    match c {
+        '0'..='9' => true,
+        '.' => !input.contains('.'),
+        '-' => signed && input.is_empty(),
+        _ => false,
+    }
+}
+
+/// `/compare` rows for two markets. The better side of a metric is ticked:
+/// higher volume, faster absolute velocity and a tighter spread. Prices are
+/// shown without a verdict since neither side is better in itself.
+fn comparison_lines(first: &ComparisonSide, second: &ComparisonSide) -> Vec<String> {
+    const WIDTH: usize = 22;
+    let row = |label: &str, a: String, b: String, better: Option<std::cmp::Ordering>| {
+        let tick = |value: String, wins: bool| {
+            if wins {
+                format!("{} ✓", value)
+            } else {
+                value
+            }
+        };
+        let a = tick(a, better == Some(std::cmp::Ordering::Greater));
+        let b = tick(b, better == Some(std::cmp::Ordering::Less));
+        format!("{:<9}{:<WIDTH$}{}", label, a, b)
+    };
+    // Greater means the first market is better; a missing value always loses
+    let compare = |a: Option<f64>, b: Option<f64>, higher_is_better: bool| {
+        match (a, b) {
+            (Some(a), Some(b)) if higher_is_better => a.partial_cmp(&b),
+            (Some(a), Some(b)) => b.partial_cmp(&a),
+            (Some(_), None) => Some(std::cmp::Ordering::Greater),
+            (None, Some(_)) => Some(std::cmp::Ordering::Less),
+            (None, None) => None,
+        }
+        .filter(|o| o.is_ne())
+    };
+    let price = |p: Option<f64>| p.map_or("-".to_string(), |p| format!("{:.1}%", p * 100.0));
+    let number = |v: Option<f64>, decimals: usize| {
+        v.map_or("-".to_string(), |v| format!("{:.*}", decimals, v))
+    };
+    let question = |q: &str| {
+        if q.chars().count() > WIDTH - 2 {
+            format!("{}…", q.chars().take(WIDTH - 3).collect::<String>())
+        } else {
+            q.to_string()
+        }
+    };
+
+    vec![
+        row(
+            "Market",
+            question(&first.question),
+            question(&second.question),
+            None,
+        ),
+        row("Price", price(first.price), price(second.price), None),
+        row(
+            "Volume",
+            format!("${:.0}", first.volume),
+            format!("${:.0}", second.volume),
+            compare(Some(first.volume), Some(second.volume), true),
+        ),
+        row(
+            "Velocity",
+            number(first.velocity, 1),
+            number(second.velocity, 1),
+            compare(
+                first.velocity.map(f64::abs),
+                second.velocity.map(f64::abs),
+                true,
+            ),
+        ),
+        row(
+            "Spread",
+            number(first.spread, 3),
+            number(second.spread, 3),
+            compare(first.spread, second.spread, false),
+        ),
+        row("Mid", number(first.mid, 3), number(second.mid, 3), None),
+    ]
+}
+
+/// Substring match ignoring ASCII case; an empty needle matches everything
+pub fn contains_ignore_ascii_case(haystack: &str, needle: &str) -> bool {
+    find_ignore_ascii_case(haystack, needle).is_some() || needle.is_empty()
+}
+
+/// Byte offset of the first match of `needle` in `haystack`, ignoring ASCII case.
+/// ASCII lowercasing keeps byte offsets valid for the original string.
+pub fn find_ignore_ascii_case(haystack: &str, needle: &str) -> Option<usize> {
+    if needle.is_empty() {
+        return None;
+    }
+    haystack
+        .to_ascii_lowercase()
+        .find(&needle.to_ascii_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_numeric_input_filter() {
+        assert!(is_numeric_input_char("", '7', false));
+        assert!(is_numeric_input_char("12", '.', false));
+        assert!(!is_numeric_input_char("1.2", '.', false));
+        assert!(!is_numeric_input_char("12", 'a', false));
+        assert!(!is_numeric_input_char("", '-', false));
+        assert!(is_numeric_input_char("", '-', true));
+        assert!(!is_numeric_input_char("0.5", '-', true));
+    }
+
+    async fn test_app() -> (App, crate::database::DbPool, tempfile::TempDir) {
+        let dir = tempfile::tempdir().unwrap();
+        let db_path = dir.path().join("test.db");
+        let pool = crate::database::init_database(db_path.to_str().unwrap())
+            .await
+            .unwrap();
+        let config = crate::execution::tests::test_config();
+        let detector = SpikeDetector::new(
+            pool.clone(),
+            config.volume_velocity_threshold,
+            config.obi_threshold,
+        );
+        let engine = Arc::new(ExecutionEngine::new(config, pool.clone()));
+        let app = App::new(
+            pool.clone(),
+            engine,
+            Arc::new(Mutex::new(detector)),
+            MarketService::new(),
+        );
+        (app, pool, dir)
+    }
+
+    #[tokio::test]
+    async fn test_starting_capital_waits_for_a_funded_portfolio() {
+        let (mut app, pool, _dir) = test_app().await;
+
+        app.refresh_requested = true;
+        app.refresh_data().await;
+        assert!(app.portfolio.is_some());
+        assert_eq!(app.starting_capital, None);
+
+        crate::database::ensure_market(&pool, "market_1")
+            .await
+            .unwrap();
+        sqlx::query(
+            "INSERT INTO trades (market_id, trade_id, side, price, size, timestamp) VALUES (?, ?, ?, ?, ?, ?)",
+        )
+        .bind("market_1")
+        .bind("trade_1")
+        .bind("BUY")
+        .bind(0.40)
+        .bind(100.0)
+        .bind(1)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        app.refresh_requested = true;
+        app.refresh_data().await;
+        assert_eq!(app.starting_capital, Some(40.0));
+    }
+
+    #[tokio::test]
+    async fn test_watchlist_loads_in_background() {
+        let (mut app, pool, _dir) = test_app().await;
+        let market = MarketInfo {
+            id: "market_1".to_string(),
+            question: "Will it rain?".to_string(),
+            active: true,
+            order_book_enabled: true,
+            volume: "1000".to_string(),
+            outcomes: Vec::new(),
+            prices: Vec::new(),
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+        crate::markets::save_watched_market(&pool, &market)
+            .await
+            .unwrap();
+        crate::database::save_ui_state(&pool, UI_STATE_SELECTED_MARKET, "0")
+            .await
+            .unwrap();
+
+        app.start_loading_watched_markets();
+        app.restore_ui_state().await;
+        assert!(app.is_loading_watchlist);
+        assert!(app.watched_markets_info.is_empty());
+
+        app.finish_loading_watched_markets(true).await;
+        assert!(!app.is_loading_watchlist);
+        assert_eq!(app.joined_markets, vec!["market_1".to_string()]);
+        assert_eq!(app.watched_markets_info.len(), 1);
+        assert_eq!(app.pending_watched_selection, None);
+    }
+
+    #[tokio::test]
+    async fn test_self_test_reports_each_check() {
+        let (mut app, _pool, _dir) = test_app().await;
+        // Nothing listens on port 1, so only the Gamma check fails
+        let client = reqwest::Client::builder().no_proxy().build().unwrap();
+        app.market_service = MarketService::with_client(client)
+            .with_base_urls("http://127.0.0.1:1", "http://127.0.0.1:1");
+        app.paper_trading = true;
+
+        assert_eq!(app.run_self_test().await, 3);
+        let failed: Vec<&str> = app
+            .logs
+            .iter()
+            .filter(|l| l.level == LogLevel::Error)
+            .map(|l| l.message.as_str())
+            .collect();
+        assert_eq!(failed.len(), 1);
+        assert!(failed[0].starts_with("✗ Gamma API"), "{}", failed[0]);
+    }
+
+    #[tokio::test]
+    async fn test_unleave_restores_last_left_market_once() {
+        let (mut app, pool, _dir) = test_app().await;
+        let market = MarketInfo {
+            id: "market_1".to_string(),
+            question: "Will it rain?".to_string(),
+            active: true,
+            order_book_enabled: true,
+            volume: "1000".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            prices: vec![0.6, 0.4],
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+        assert!(app.join(market).await);
+        app.leave_market("market_1").await;
+        assert!(app.watched_markets_info.is_empty());
+
+        app.execute_command("/unleave").await;
+        assert_eq!(app.joined_markets, vec!["market_1".to_string()]);
+        assert_eq!(app.watched_markets_info[0].prices, vec![0.6, 0.4]);
+        let watched = crate::markets::load_watched_markets(&pool).await.unwrap();
+        assert_eq!(watched.len(), 1);
+
+        // The undo is used up
+        app.leave_market("market_1").await;
+        app.execute_command("/unleave").await;
+        app.execute_command("/unleave").await;
+        assert_eq!(app.joined_markets.len(), 1);
+        assert_eq!(
+            app.logs.last().unwrap().message,
+            "No left market to restore"
+        );
+    }
+
+    #[test]
+    fn test_comparison_ticks_the_better_side() {
+        let first = ComparisonSide {
+            question: "Will the first market resolve yes?".to_string(),
+            price: Some(0.62),
+            volume: 50_000.0,
+            velocity: Some(-300.0),
+            spread: Some(0.04),
+            mid: Some(0.62),
+        };
+        let second = ComparisonSide {
+            question: "Second?".to_string(),
+            price: Some(0.3),
+            volume: 10_000.0,
+            velocity: None,
+            spread: Some(0.01),
+            mid: Some(0.305),
+        };
+
+        let lines = comparison_lines(&first, &second);
+        assert_eq!(lines.len(), 6);
+        assert!(lines[0].contains("Will the first mark…"), "{}", lines[0]);
+        assert!(!lines[1].contains('✓'));
+        assert!(lines[2].contains("$50000 ✓"));
+        assert!(!lines[2].ends_with('✓'));
+        // A known velocity beats a missing one
+        assert!(lines[3].contains("-300.0 ✓"));
+        // The tighter spread wins
+        assert!(lines[4].ends_with("0.010 ✓"));
+        assert!(!lines[5].contains('✓'));
+    }
+
+    #[test]
+    fn test_obi_history_is_bounded() {
+        let mut analysis = MarketAnalysis::default();
+        for i in 0..OBI_HISTORY_LIMIT + 5 {
+            analysis.record_obi(i as f64 / 100.0);
+        }
+        assert_eq!(analysis.obi_history.len(), OBI_HISTORY_LIMIT);
+        // The oldest readings were dropped
+        assert_eq!(analysis.obi_history.front(), Some(&0.05));
+        assert_eq!(analysis.current_obi, analysis.obi_history.back().copied());
+    }
+
+    #[test]
+    fn test_poll_interval_follows_activity() {
+        let min = std::time::Duration::from_secs(5);
+        let max = std::time::Duration::from_secs(65);
+        let with_velocity = |velocity| MarketAnalysis {
+            current_velocity: velocity,
+            ..Default::default()
+        };
+
+        assert_eq!(with_velocity(None).poll_interval(1000.0, min, max), max);
+        assert_eq!(
+            with_velocity(Some(0.0)).poll_interval(1000.0, min, max),
+            max
+        );
+        assert_eq!(
+            with_velocity(Some(-500.0)).poll_interval(1000.0, min, max),
+            std::time::Duration::from_secs(35)
+        );
+        assert_eq!(
+            with_velocity(Some(3000.0)).poll_interval(1000.0, min, max),
+            min
+        );
+    }
+
+    #[tokio::test]
+    async fn test_market_update_matches_the_joined_id() {
+        let (mut app, _pool, _dir) = test_app().await;
+        let market = MarketInfo {
+            id: "12345".to_string(),
+            question: "Will it rain?".to_string(),
+            active: true,
+            order_book_enabled: true,
+            volume: "1000".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            prices: vec![0.6, 0.4],
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+        assert!(app.join(market.clone()).await);
+
+        // Gamma answers with the conditionId rather than the ID we joined with
+        app.apply_market_update(
+            "12345",
+            MarketInfo {
+                id: "0xabc".to_string(),
+                active: false,
+                volume: "2000".to_string(),
+                prices: vec![0.7, 0.3],
+                ..market
+            },
+        )
+        .await;
+
+        let watched = &app.watched_markets_info[0];
+        assert_eq!(watched.id, "12345");
+        assert_eq!(watched.prices, vec![0.7, 0.3]);
+        assert_eq!(watched.volume, "2000");
+        assert!(app.closed_since.contains_key("12345"));
+        assert!(app.market_analysis_data["12345"].last_updated.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_market_updates_run_the_spike_detector() {
+        let (mut app, pool, _dir) = test_app().await;
+        app.spike_detector = Arc::new(Mutex::new(
+            SpikeDetector::new(pool, 1000.0, 0.5).with_events(app.execution_engine.events()),
+        ));
+        let market = MarketInfo {
+            id: "market_1".to_string(),
+            question: "Will it rain?".to_string(),
+            active: true,
+            order_book_enabled: true,
+            volume: "1000".to_string(),
+            outcomes: vec!["Yes".to_string(), "No".to_string()],
+            prices: vec![0.6, 0.4],
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+        assert!(app.join(market.clone()).await);
+
+        // The first observation has nothing to compare against
+        app.apply_market_update("market_1", market.clone()).await;
+        let analysis = &app.market_analysis_data["market_1"];
+        assert_eq!(analysis.current_velocity, None);
+
+        tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        app.apply_market_update(
+            "market_1",
+            MarketInfo {
+                volume: "1000000".to_string(),
+                ..market
+            },
+        )
+        .await;
+        app.drain_bot_events();
+
+        let analysis = &app.market_analysis_data["market_1"];
+        assert!(analysis.current_velocity.unwrap() > 1000.0);
+        assert_eq!(analysis.recent_events.len(), 1);
+        assert_eq!(app.spike_detector.lock().await.spikes_detected(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_fixed_poll_interval_without_adaptive_polling() {
+        let (mut app, pool, _dir) = test_app().await;
+        app.market_analysis_data.insert(
+            "market_1".to_string(),
+            MarketAnalysis {
+                current_velocity: Some(5000.0),
+                ..Default::default()
+            },
+        );
+        assert_eq!(
+            app.poll_interval_for("market_1"),
+            std::time::Duration::from_secs(5)
+        );
+
+        let mut config = crate::execution::tests::test_config();
+        config.adaptive_polling = false;
+        app.execution_engine = Arc::new(ExecutionEngine::new(config, pool));
+        assert_eq!(
+            app.poll_interval_for("market_1"),
+            std::time::Duration::from_secs(60)
+        );
+    }
+
+    #[tokio::test]
+    async fn test_join_detects_duplicates_after_enrichment() {
+        let (mut app, _pool, _dir) = test_app().await;
+        let gamma_market = serde_json::json!([{
+            "id": "12345",
+            "conditionId": "0xabc",
+            "question": "Will it rain?",
+            "active": true,
+            "enableOrderBook": true,
+            "volume": "1000",
+            "outcomes": ["Yes", "No"],
+            "outcomePrices": ["0.5", "0.5"],
+            "clobTokenIds": ["tok_yes", "tok_no"],
+        }]);
+        app.market_service =
+            crate::markets::tests::mock_service(vec![("/markets", gamma_market.to_string())]).await;
+
+        // A public-search result: numeric ID and no outcomes yet
+        let search_result = MarketInfo {
+            id: "12345".to_string(),
+            question: "Will it rain?".to_string(),
+            active: true,
+            order_book_enabled: true,
+            volume: "1000".to_string(),
+            outcomes: Vec::new(),
+            prices: Vec::new(),
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+
+        assert!(app.join(search_result.clone()).await);
+        assert_eq!(app.joined_markets, vec!["0xabc".to_string()]);
+
+        assert!(!app.join(search_result).await);
+        assert_eq!(app.joined_markets.len(), 1);
+        assert_eq!(app.watched_markets_info.len(), 1);
+        assert_eq!(
+            app.logs.last().unwrap().message,
+            "Already monitoring this market"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_liquidity_check_uses_the_traded_outcome() {
+        let (mut app, _pool, _dir) = test_app().await;
+        let gamma_market = serde_json::json!([{
+            "id": "12345",
+            "question": "Will it rain?",
+            "active": true,
+            "enableOrderBook": true,
+            "volume": "1000",
+            "outcomes": ["Yes", "No"],
+            "outcomePrices": ["0.5", "0.5"],
+            "clobTokenIds": ["tok_yes", "tok_no"],
+        }]);
+        let book = |bid: &str, ask: &str| {
+            serde_json::json!({
+                "bids": [{"price": bid, "size": "10"}],
+                "asks": [{"price": ask, "size": "10"}],
+            })
+            .to_string()
+        };
+        app.market_service = crate::markets::tests::mock_service(vec![
+            ("/markets", gamma_market.to_string()),
+            ("/book?token_id=tok_yes", book("0.49", "0.51")),
+            ("/book?token_id=tok_no", book("0.10", "0.90")),
+        ])
+        .await;
+
+        // Loaded from the database, so no token IDs yet
+        let market = MarketInfo {
+            id: "12345".to_string(),
+            question: "Will it rain?".to_string(),
+            active: true,
+            order_book_enabled: true,
+            volume: "1000".to_string(),
+            outcomes: Vec::new(),
+            prices: Vec::new(),
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+        app.watched_markets_info.push(market);
+
+        let order = |outcome| PendingOrder {
+            market_id: "12345".to_string(),
+            question: "Will it rain?".to_string(),
+            side: "BUY".to_string(),
+            order_type: OrderType::Market,
+            size: 10.0,
+            price: None,
+            outcome,
+        };
+        assert!(app.check_order_liquidity(&order(0)).await);
+        assert!(!app.check_order_liquidity(&order(1)).await);
+        assert!(app.logs.last().unwrap().message.contains("Thin market"));
+
+        // An outcome without a book is let through, but not silently
+        assert!(app.check_order_liquidity(&order(2)).await);
+        assert!(app
+            .logs
+            .last()
+            .unwrap()
+            .message
+            .starts_with("Spread check skipped"));
+    }
+
+    #[tokio::test]
+    async fn test_order_form_size_presets() {
+        let (mut app, _pool, _dir) = test_app().await;
+        let market = MarketInfo {
+            id: "market_1".to_string(),
+            question: "Will it rain?".to_string(),
+            active: true,
+            order_book_enabled: true,
+            volume: "1000".to_string(),
+            outcomes: Vec::new(),
+            prices: Vec::new(),
+            token_ids: Vec::new(),
+            end_date: None,
+        };
+        app.order_form = Some(OrderForm::new(&market));
+        let press = |c| KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE);
+
+        // test_config has three presets
+        app.handle_order_entry(press('4')).unwrap();
+        let form = app.order_form.as_ref().unwrap();
+        assert_eq!(form.step, OrderFormStep::Side);
+        assert!(form.error.is_some());
+
+        app.handle_order_entry(press('2')).unwrap();
+        let form = app.order_form.as_ref().unwrap();
+        assert_eq!(form.step, OrderFormStep::Price);
+        assert_eq!(form.size_input, "25");
+        assert_eq!(form.error, None);
+    }
+
+    #[tokio::test]
+    async fn test_favorites_sort_first_filter_and_persist() {
+        let (mut app, pool, _dir) = test_app().await;
+        for id in ["market_1", "market_2", "market_3"] {
+            let market = MarketInfo {
+                id: id.to_string(),
+                question: format!("Question {}?", id),
+                active: true,
+                order_book_enabled: true,
+                volume: "1000".to_string(),
+                outcomes: Vec::new(),
+                prices: Vec::new(),
+                token_ids: Vec::new(),
+                end_date: None,
+            };
+            crate::markets::save_watched_market(&pool, &market)
+                .await
+                .unwrap();
+        }
+        app.init_watched_markets().await;
+
+        // Showing favorites only needs a favorite first
+        app.toggle_favorites_only();
+        assert!(!app.favorites_only);
+
+        let index = app
+            .watched_markets_info
+            .iter()
+            .position(|m| m.id == "market_2")
+            .unwrap();
+        app.selected_watched_market_index = index;
+        app.toggle_favorite().await;
+        assert_eq!(app.watched_markets_info[0].id, "market_2");
+        assert_eq!(app.selected_watched_market_index, 0);
+
+        app.toggle_favorites_only();
+        assert!(app.favorites_only);
+        app.step_watched_selection(true);
+        assert_eq!(app.selected_watched_market_index, 0);
+
+        // Favorites are restored from the database
+        app.favorite_markets.clear();
+        app.init_watched_markets().await;
+        assert!(app.favorite_markets.contains("market_2"));
+        assert_eq!(app.watched_markets_info[0].id, "market_2");
+
+        // Unfavoriting the last favorite shows every market again
+        app.selected_watched_market_index = 0;
+        app.toggle_favorite().await;
+        assert!(!app.favorites_only);
+        assert!(crate::markets::load_favorite_markets(&pool)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_min_log_level_gates_background_logs() {
+        assert_eq!(LogLevel::from_filter("info"), LogLevel::Info);
+        assert_eq!(LogLevel::from_filter("WARN"), LogLevel::Warning);
+        assert_eq!(
+            LogLevel::from_filter("error,polymarket_bot_summer=debug"),
+            LogLevel::Error
+        );
+        assert_eq!(
+            LogLevel::from_filter("polymarket_bot_summer=trace"),
+            LogLevel::Info
+        );
+
+        let (mut app, _pool, _dir) = test_app().await;
+        app.logs.clear();
+        app.min_log_level = LogLevel::Warning;
+
+        app.add_log(LogLevel::Info, "chatter");
+        app.add_log(LogLevel::Success, "more chatter");
+        app.add_log(LogLevel::Warning, "heads up");
+        assert_eq!(app.logs.len(), 1);
+        assert_eq!(app.logs[0].message, "heads up");
+
+        // Replies to commands are shown regardless
+        app.execute_command("/help").await;
+        assert!(app.logs.iter().any(|l| l.level == LogLevel::Info));
+        app.add_log(LogLevel::Info, "chatter");
+        assert!(!app.logs.iter().any(|l| l.message == "chatter"));
+    }
+
+    #[tokio::test]
+    async fn test_shutdown_writes_final_snapshot() {
+        let (mut app, pool, _dir) = test_app().await;
+        app.current_tab = Tab::Logs;
+
+        app.shutdown().await;
+
+        let (snapshots, usdc_balance): (i64, f64) =
+            sqlx::query_as("SELECT COUNT(*), MAX(usdc_balance) FROM portfolio_snapshots")
+                .fetch_one(&pool)
+                .await
+                .unwrap();
+        assert_eq!(snapshots, 1);
+        assert_eq!(
+            usdc_balance,
+            app.execution_engine
+                .get_portfolio()
+                .await
+                .unwrap()
+                .usdc_balance
+        );
+        assert_eq!(
+            crate::database::load_ui_state(&pool, UI_STATE_TAB)
+                .await
+                .unwrap()
+                .as_deref(),
+            Some(Tab::Logs.title())
+        );
+    }
 }
@@ -0,0 +1,40 @@
+use anyhow::{bail, Result};
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Clipboard utilities tried in order: macOS, Wayland, X11 (two flavours), Windows
+const CLIPBOARD_COMMANDS: [(&str, &[&str]); 5] = [
+    ("pbcopy", &[]),
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+    ("clip", &[]),
+];
+
+/// Copy text to the system clipboard by piping it into the first available
+/// clipboard utility. Fails on headless systems where none is installed.
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    for (program, args) in CLIPBOARD_COMMANDS {
+        let child = Command::new(program)
+            .args(args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn();
+
+        // Not installed, try the next one
+        let Ok(mut child) = child else {
+            continue;
+        };
+
+        if let Some(mut stdin) = child.stdin.take() {
+            stdin.write_all(text.as_bytes())?;
+        }
+
+        if child.wait()?.success() {
+            return Ok(());
+        }
+    }
+
+    bail!("no clipboard utility available (install xclip, xsel or wl-copy)")
+}
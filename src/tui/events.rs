@@ -1,7 +1,15 @@
-use crossterm::event::{self, Event, KeyEvent, KeyEventKind};
+use crossterm::event::{self, Event, KeyEvent, KeyEventKind, MouseEventKind};
 use std::time::Duration;
 
-/// Event handler for keyboard input
+/// Input events forwarded to the app
+#[derive(Debug, Clone, Copy)]
+pub enum AppEvent {
+    Key(KeyEvent),
+    ScrollUp,
+    ScrollDown,
+}
+
+/// Event handler for keyboard and mouse input
 pub struct EventHandler {
     tick_rate: Duration,
 }
@@ -13,14 +21,20 @@ impl EventHandler {
         }
     }
 
-    /// Poll for the next keyboard event (only Press events, ignoring Release/Repeat)
-    pub fn next(&mut self) -> std::io::Result<Option<KeyEvent>> {
+    /// Poll for the next input event (key Press events and mouse wheel scrolls)
+    pub fn next_event(&mut self) -> std::io::Result<Option<AppEvent>> {
         if event::poll(self.tick_rate)? {
-            if let Event::Key(key_event) = event::read()? {
+            match event::read()? {
                 // Only handle key Press events, ignore Release and Repeat
-                if key_event.kind == KeyEventKind::Press {
-                    return Ok(Some(key_event));
+                Event::Key(key_event) if key_event.kind == KeyEventKind::Press => {
+                    return Ok(Some(AppEvent::Key(key_event)));
                 }
+                Event::Mouse(mouse_event) => match mouse_event.kind {
+                    MouseEventKind::ScrollUp => return Ok(Some(AppEvent::ScrollUp)),
+                    MouseEventKind::ScrollDown => return Ok(Some(AppEvent::ScrollDown)),
+                    _ => {}
+                },
+                _ => {}
             }
         }
         Ok(None)
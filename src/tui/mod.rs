@@ -1,11 +1,16 @@
 mod app;
+mod clipboard;
 mod events;
+mod theme;
 mod ui;
 
-pub use app::App;
-pub use events::EventHandler;
+pub use app::{App, DepthView, LogLevel};
+pub use events::{AppEvent, EventHandler};
+pub use theme::Theme;
 
 use crate::execution::ExecutionEngine;
+use crate::markets::MarketService;
+use crate::spike_detection::SpikeDetector;
 use anyhow::Result;
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
@@ -15,11 +20,16 @@ use crossterm::{
 use ratatui::prelude::*;
 use std::io::stdout;
 use std::sync::Arc;
+use tokio::sync::{watch, Mutex};
 
 /// Initialize and run the TUI application
 pub async fn run_tui(
     db_pool: crate::database::DbPool,
     execution_engine: Arc<ExecutionEngine>,
+    spike_detector: Arc<Mutex<SpikeDetector>>,
+    market_service: MarketService,
+    wallet_address: Option<String>,
+    shutdown: watch::Receiver<bool>,
 ) -> Result<()> {
     // Setup terminal
     enable_raw_mode()?;
@@ -29,12 +39,15 @@ pub async fn run_tui(
     let mut terminal = Terminal::new(backend)?;
 
     // Create app state
-    let mut app = App::new(db_pool, execution_engine);
-    app.init_watched_markets().await;
+    let mut app = App::new(db_pool, execution_engine, spike_detector, market_service);
+    app.wallet_address = wallet_address;
+    app.start_loading_watched_markets();
+    app.restore_ui_state().await;
     let mut event_handler = EventHandler::new(100); // 100ms tick rate
 
     // Main loop
-    let result = run_app(&mut terminal, &mut app, &mut event_handler).await;
+    let result = run_app(&mut terminal, &mut app, &mut event_handler, shutdown).await;
+    app.shutdown().await;
 
     // Restore terminal
     disable_raw_mode()?;
@@ -52,18 +65,19 @@ async fn run_app<B: Backend>(
     terminal: &mut Terminal<B>,
     app: &mut App,
     event_handler: &mut EventHandler,
+    shutdown: watch::Receiver<bool>,
 ) -> Result<()> {
     loop {
         // Draw UI
         terminal.draw(|frame| ui::draw(frame, app))?;
 
         // Handle events
-        if let Some(event) = event_handler.next()? {
+        if let Some(event) = event_handler.next_event()? {
             app.handle_event(event).await?;
         }
 
-        // Check if we should quit
-        if app.should_quit {
+        // Check if we should quit (user request or external signal)
+        if app.should_quit || *shutdown.borrow() {
             break;
         }
 
@@ -0,0 +1,105 @@
+use ratatui::style::Color;
+
+/// Palette the TUI draws with, selected by `UI_THEME`.
+/// Fields are named by role so each theme can pick what reads well on its background.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Theme {
+    /// Primary text
+    pub text: Color,
+    /// Secondary text, hints and labels
+    pub muted: Color,
+    /// De-emphasised content such as closed markets
+    pub dim: Color,
+    /// Headings, selections and key bindings
+    pub accent: Color,
+    /// Secondary accent for borders and panels
+    pub info: Color,
+    /// Third accent, used for OBI and paper trading
+    pub highlight: Color,
+    pub success: Color,
+    pub warning: Color,
+    pub error: Color,
+    /// Text drawn on top of a coloured background
+    pub inverse: Color,
+}
+
+impl Theme {
+    /// Names accepted by `UI_THEME`
+    pub const NAMES: [&'static str; 3] = ["dark", "light", "high-contrast"];
+
+    /// The original palette, for dark terminal backgrounds
+    pub const DARK: Theme = Theme {
+        text: Color::White,
+        muted: Color::Gray,
+        dim: Color::DarkGray,
+        accent: Color::Cyan,
+        info: Color::Blue,
+        highlight: Color::Magenta,
+        success: Color::Green,
+        warning: Color::Yellow,
+        error: Color::Red,
+        inverse: Color::Black,
+    };
+
+    /// Darker tones that stay readable on light backgrounds
+    pub const LIGHT: Theme = Theme {
+        text: Color::Black,
+        muted: Color::DarkGray,
+        dim: Color::Gray,
+        accent: Color::Rgb(0, 110, 140),
+        info: Color::Blue,
+        highlight: Color::Rgb(140, 0, 140),
+        success: Color::Rgb(0, 120, 0),
+        warning: Color::Rgb(170, 100, 0),
+        error: Color::Rgb(190, 0, 0),
+        inverse: Color::White,
+    };
+
+    /// Bright, saturated colours with white secondary text
+    pub const HIGH_CONTRAST: Theme = Theme {
+        text: Color::White,
+        muted: Color::White,
+        dim: Color::Gray,
+        accent: Color::LightCyan,
+        info: Color::LightBlue,
+        highlight: Color::LightMagenta,
+        success: Color::LightGreen,
+        warning: Color::LightYellow,
+        error: Color::LightRed,
+        inverse: Color::Black,
+    };
+
+    /// Look a theme up by its `UI_THEME` name, ignoring case
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "dark" => Some(Self::DARK),
+            "light" => Some(Self::LIGHT),
+            "high-contrast" => Some(Self::HIGH_CONTRAST),
+            _ => None,
+        }
+    }
+}
+
+impl Default for Theme {
+    fn default() -> Self {
+        Self::DARK
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_every_theme_name_resolves() {
+        for name in Theme::NAMES {
+            assert!(Theme::from_name(name).is_some(), "{}", name);
+        }
+        assert_eq!(
+            Theme::from_name("High-Contrast"),
+            Some(Theme::HIGH_CONTRAST)
+        );
+        assert_eq!(Theme::from_name("solarized"), None);
+        assert_eq!(Theme::default(), Theme::DARK);
+    }
+}
@@ -1,15 +1,42 @@
-use crate::tui::app::{App, InputMode, LeaveSelection, LogLevel, QuitSelection, Tab};
+use crate::execution::trading_fee;
+use crate::markets::{parse_volume, ConnectionState};
+use crate::tui::app::{
+    find_ignore_ascii_case, App, Banner, InputMode, LeaveSelection, LogLevel, MarketSort,
+    OrderFormStep, OrderSelection, QuitSelection, SettingsField, Tab, STALE_DATA_AFTER,
+};
+use crate::tui::theme::Theme;
 use ratatui::{
     prelude::*,
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Tabs, Wrap},
+    widgets::{
+        Axis, Block, Borders, Chart, Clear, Dataset, GraphType, List, ListItem, Paragraph, Tabs,
+        Wrap,
+    },
 };
+use std::time::Duration;
+
+/// Narrowest the velocity and OBI bars get, so they stay readable
+const MIN_BAR_WIDTH: usize = 10;
+
+/// Velocity (vol/sec) at which the velocity bar fills the panel
+const VELOCITY_BAR_FULL_SCALE: f64 = 1500.0;
+
+/// Smallest terminal the full layout fits in; below it only a resize hint is drawn
+const MIN_TERMINAL_WIDTH: u16 = 80;
+const MIN_TERMINAL_HEIGHT: u16 = 24;
 
 /// Draw the complete TUI
 pub fn draw(frame: &mut Frame, app: &App) {
     let area = frame.area();
 
+    // Redrawn every tick, so the full layout comes back as soon as the window grows
+    if area.width < MIN_TERMINAL_WIDTH || area.height < MIN_TERMINAL_HEIGHT {
+        draw_too_small(frame, area, &app.theme);
+        return;
+    }
+
     // Main layout: Header, Tabs, Content, Command Input (if active), Footer
-    let constraints = if app.input_mode == InputMode::Command {
+    let has_input_bar = matches!(app.input_mode, InputMode::Command | InputMode::LogSearch);
+    let constraints = if has_input_bar {
         vec![
             Constraint::Length(3), // Header
             Constraint::Length(3), // Tabs
@@ -32,12 +59,18 @@ pub fn draw(frame: &mut Frame, app: &App) {
         .split(area);
 
     draw_header(frame, layout[0], app);
+    if let Some(ref banner) = app.banner {
+        draw_banner(frame, layout[0], banner, &app.theme);
+    }
     draw_tabs(frame, layout[1], app);
     draw_content(frame, layout[2], app);
 
     if app.input_mode == InputMode::Command {
         draw_command_input(frame, layout[3], app);
         draw_footer(frame, layout[4], app);
+    } else if app.input_mode == InputMode::LogSearch {
+        draw_log_search_input(frame, layout[3], app);
+        draw_footer(frame, layout[4], app);
     } else {
         draw_footer(frame, layout[3], app);
     }
@@ -51,45 +84,128 @@ pub fn draw(frame: &mut Frame, app: &App) {
     if app.input_mode == InputMode::LeaveMarketConfirmation {
         draw_leave_confirmation_modal(frame, area, app);
     }
+
+    // Draw order entry form on top if active
+    if app.input_mode == InputMode::OrderEntry {
+        draw_order_entry_modal(frame, area, app);
+    }
+
+    // Draw order confirmation modal on top if active
+    if app.input_mode == InputMode::OrderConfirmation {
+        draw_order_confirmation_modal(frame, area, app);
+    }
+
+    // Draw keybinding help on top if active
+    if app.input_mode == InputMode::HelpOverlay {
+        draw_help_overlay(frame, area, app);
+    }
+}
+
+/// Resize hint shown instead of the layout on terminals that are too small
+fn draw_too_small(frame: &mut Frame, area: Rect, theme: &Theme) {
+    let lines = vec![
+        Line::styled(
+            "Terminal too small — please resize",
+            Style::default().fg(theme.warning).bold(),
+        ),
+        Line::styled(
+            format!(
+                "need at least {}x{}, have {}x{}",
+                MIN_TERMINAL_WIDTH, MIN_TERMINAL_HEIGHT, area.width, area.height
+            ),
+            Style::default().fg(theme.muted),
+        ),
+    ];
+
+    // Vertically centre the hint when there's room for it
+    let top = area.height.saturating_sub(lines.len() as u16) / 2;
+    let hint_area = Rect {
+        y: area.y + top,
+        height: area.height - top,
+        ..area
+    };
+
+    frame.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: true }),
+        hint_area,
+    );
+}
+
+/// Flash notification drawn over the header, colored by severity
+fn draw_banner(frame: &mut Frame, area: Rect, banner: &Banner, theme: &Theme) {
+    let color = match banner.level {
+        LogLevel::Error => theme.error,
+        LogLevel::Warning => theme.warning,
+        LogLevel::Success => theme.success,
+        LogLevel::Info => theme.info,
+    };
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(Line::styled(
+            banner.message.as_str(),
+            Style::default().fg(color).bold(),
+        ))
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(color)),
+        )
+        .alignment(Alignment::Center),
+        area,
+    );
 }
 
 fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let status = if app.is_paused {
         Span::styled(
             " PAUSED ",
-            Style::default().bg(Color::Red).fg(Color::White).bold(),
+            Style::default().bg(theme.error).fg(theme.text).bold(),
         )
     } else {
         Span::styled(
             " ACTIVE ",
-            Style::default().bg(Color::Green).fg(Color::Black).bold(),
+            Style::default().bg(theme.success).fg(theme.inverse).bold(),
+        )
+    };
+
+    let mode = if app.paper_trading {
+        Span::styled(
+            " PAPER ",
+            Style::default().bg(theme.highlight).fg(theme.text).bold(),
         )
+    } else {
+        Span::raw("")
     };
 
     let markets_count = app.joined_markets.len();
     let markets_info = if markets_count > 0 {
         Span::styled(
             format!(" [{} markets] ", markets_count),
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.warning),
         )
     } else {
         Span::raw("")
     };
 
     let header = Paragraph::new(Line::from(vec![
-        Span::styled("🟢 ", Style::default().fg(Color::Green)),
+        Span::styled("🟢 ", Style::default().fg(theme.success)),
         Span::styled(
             "Polymarket Bot Summer",
-            Style::default().fg(Color::Cyan).bold(),
+            Style::default().fg(theme.accent).bold(),
         ),
         Span::raw(" - "),
         status,
+        mode,
         markets_info,
     ]))
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Cyan))
+            .border_style(Style::default().fg(theme.accent))
             .title(" Bot Status "),
     );
 
@@ -97,14 +213,15 @@ fn draw_header(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_tabs(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let titles: Vec<Line> = Tab::all()
         .iter()
         .enumerate()
         .map(|(i, t)| {
             let style = if *t == app.current_tab {
-                Style::default().fg(Color::Yellow).bold()
+                Style::default().fg(theme.warning).bold()
             } else {
-                Style::default().fg(Color::Gray)
+                Style::default().fg(theme.muted)
             };
             Line::from(format!(" [{}] {} ", i + 1, t.title())).style(style)
         })
@@ -112,7 +229,7 @@ fn draw_tabs(frame: &mut Frame, area: Rect, app: &App) {
 
     let tabs = Tabs::new(titles)
         .block(Block::default().borders(Borders::ALL).title(" Navigation "))
-        .highlight_style(Style::default().fg(Color::Yellow).bold())
+        .highlight_style(Style::default().fg(theme.warning).bold())
         .select(app.current_tab as usize);
 
     frame.render_widget(tabs, area);
@@ -126,26 +243,46 @@ fn draw_content(frame: &mut Frame, area: Rect, app: &App) {
         Tab::MarketDetail => draw_market_detail(frame, area, app),
         Tab::Logs => draw_logs(frame, area, app),
         Tab::Docs => draw_docs(frame, area, app),
+        Tab::Settings => draw_settings(frame, area, app),
     }
 }
 
 fn draw_command_input(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let input = Paragraph::new(Line::from(vec![
-        Span::styled("Command: ", Style::default().fg(Color::Cyan).bold()),
-        Span::styled(&app.command_input, Style::default().fg(Color::White)),
-        Span::styled("▌", Style::default().fg(Color::Yellow)),
+        Span::styled("Command: ", Style::default().fg(theme.accent).bold()),
+        Span::styled(&app.command_input, Style::default().fg(theme.text)),
+        Span::styled("▌", Style::default().fg(theme.warning)),
     ]))
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::Yellow))
+            .border_style(Style::default().fg(theme.warning))
             .title(" 📝 Command Mode (ESC to cancel) "),
     );
 
     frame.render_widget(input, area);
 }
 
+fn draw_log_search_input(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let input = Paragraph::new(Line::from(vec![
+        Span::styled("Filter: ", Style::default().fg(theme.accent).bold()),
+        Span::styled(&app.log_search, Style::default().fg(theme.text)),
+        Span::styled("▌", Style::default().fg(theme.warning)),
+    ]))
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.warning))
+            .title(" 🔎 Filter Logs (Enter to apply, ESC to clear) "),
+    );
+
+    frame.render_widget(input, area);
+}
+
 fn draw_dashboard(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Split into two columns
     let columns = Layout::default()
         .direction(Direction::Horizontal)
@@ -159,39 +296,48 @@ fn draw_dashboard(frame: &mut Frame, area: Rect, app: &App) {
         .split(columns[0]);
 
     // Portfolio
+    let percent_of = app.starting_capital.filter(|_| app.pnl_as_percent);
     let portfolio_text = if let Some(ref p) = app.portfolio {
         vec![
             Line::from(vec![
                 Span::raw("  USDC Balance: "),
                 Span::styled(
                     format!("${:.2}", p.usdc_balance),
-                    Style::default().fg(Color::Green),
+                    Style::default().fg(theme.success),
                 ),
             ]),
             Line::from(vec![
                 Span::raw("  Total Value:  "),
                 Span::styled(
                     format!("${:.2}", p.total_value),
-                    Style::default().fg(Color::Cyan),
+                    Style::default().fg(theme.accent),
                 ),
             ]),
             Line::raw(""),
             Line::from(vec![
                 Span::raw("  P&L: "),
                 Span::styled(
-                    format!("{:+.2}", p.realized_pnl + p.unrealized_pnl),
+                    pnl_text(p.realized_pnl + p.unrealized_pnl, percent_of),
                     if p.realized_pnl + p.unrealized_pnl >= 0.0 {
-                        Style::default().fg(Color::Green)
+                        Style::default().fg(theme.success)
                     } else {
-                        Style::default().fg(Color::Red)
+                        Style::default().fg(theme.error)
                     },
                 ),
+                Span::styled(
+                    format!(
+                        "  ({} gross, {:.2} fees)",
+                        pnl_text(p.realized_pnl + p.fees_paid + p.unrealized_pnl, percent_of),
+                        p.fees_paid
+                    ),
+                    Style::default().fg(theme.muted),
+                ),
             ]),
         ]
     } else {
         vec![Line::styled(
             "  Loading...",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.warning),
         )]
     };
 
@@ -200,41 +346,54 @@ fn draw_dashboard(frame: &mut Frame, area: Rect, app: &App) {
             Block::default()
                 .borders(Borders::ALL)
                 .title(" 💰 Portfolio ")
-                .border_style(Style::default().fg(Color::Green)),
+                .border_style(Style::default().fg(theme.success)),
         )
         .wrap(Wrap { trim: true });
 
     frame.render_widget(portfolio_widget, left_layout[0]);
 
     // Joined Markets
-    let joined_text: Vec<Line> = if app.joined_markets.is_empty() {
+    let joined_text: Vec<Line> = if app.is_loading_watchlist {
+        vec![loading_line("Loading watched markets...", theme)]
+    } else if app.joined_markets.is_empty() {
         vec![
-            Line::styled("  No markets joined", Style::default().fg(Color::Yellow)),
+            Line::styled("  No markets joined", Style::default().fg(theme.warning)),
             Line::raw(""),
             Line::styled(
                 "  Press 'S' to search markets",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ),
         ]
     } else {
-        app.joined_markets
-            .iter()
+        monitored_market_ids(app)
+            .into_iter()
             .enumerate()
             .map(|(i, m)| {
+                let marker = if app.favorite_markets.contains(m) {
+                    Span::styled("★ ", Style::default().fg(theme.warning))
+                } else {
+                    Span::raw("  ")
+                };
                 Line::from(vec![
-                    Span::styled(format!("  {}. ", i + 1), Style::default().fg(Color::Gray)),
-                    Span::styled(&m[..16.min(m.len())], Style::default().fg(Color::Cyan)),
+                    Span::styled(format!("  {}. ", i + 1), Style::default().fg(theme.muted)),
+                    marker,
+                    Span::styled(truncate_str(m, 16), Style::default().fg(theme.accent)),
                     Span::raw("..."),
                 ])
             })
             .collect()
     };
 
+    let joined_title = if app.favorites_only {
+        format!(" 🎯 Monitoring (★ {}) ", monitored_market_ids(app).len())
+    } else {
+        format!(" 🎯 Monitoring ({}) ", app.joined_markets.len())
+    };
     let joined_widget = Paragraph::new(joined_text).block(
         Block::default()
             .borders(Borders::ALL)
-            .title(format!(" 🎯 Monitoring ({}) ", app.joined_markets.len()))
-            .border_style(Style::default().fg(Color::Magenta)),
+            .title(joined_title)
+            .border_style(Style::default().fg(theme.highlight)),
     );
 
     frame.render_widget(joined_widget, left_layout[1]);
@@ -244,27 +403,66 @@ fn draw_dashboard(frame: &mut Frame, area: Rect, app: &App) {
         Line::from(vec![
             Span::raw("  Trading:   "),
             if app.is_paused {
-                Span::styled("PAUSED", Style::default().fg(Color::Red).bold())
+                Span::styled("PAUSED", Style::default().fg(theme.error).bold())
             } else {
-                Span::styled("ACTIVE", Style::default().fg(Color::Green).bold())
+                Span::styled("ACTIVE", Style::default().fg(theme.success).bold())
+            },
+        ]),
+        Line::from(vec![
+            Span::raw("  Wallet:    "),
+            match app.wallet_address {
+                Some(ref address) if address.len() > 14 => Span::styled(
+                    format!(
+                        "{}…{}",
+                        truncate_str(address, 6),
+                        &address[address.len() - 4..]
+                    ),
+                    Style::default().fg(theme.warning),
+                ),
+                Some(ref address) => Span::styled(address, Style::default().fg(theme.warning)),
+                None => Span::styled("Offline (demo)", Style::default().fg(theme.muted)),
             },
         ]),
         Line::raw(""),
         Line::from(vec![
-            Span::raw("  WebSocket: "),
-            Span::styled("Connected", Style::default().fg(Color::Green)),
+            Span::raw("  API:       "),
+            match app.connection_status.state {
+                ConnectionState::Idle => Span::styled("Idle", Style::default().fg(theme.muted)),
+                ConnectionState::Connected => {
+                    Span::styled("Connected", Style::default().fg(theme.success))
+                }
+                ConnectionState::Reconnecting => {
+                    Span::styled("Reconnecting", Style::default().fg(theme.warning))
+                }
+                ConnectionState::Disconnected => {
+                    Span::styled("Disconnected", Style::default().fg(theme.error).bold())
+                }
+            },
         ]),
         Line::from(vec![
             Span::raw("  Latency:   "),
-            Span::styled("42ms", Style::default().fg(Color::Yellow)),
+            match app.connection_status.latency {
+                Some(latency) => {
+                    let ms = latency.as_millis();
+                    let color = if ms < 300 {
+                        theme.success
+                    } else if ms < 1000 {
+                        theme.warning
+                    } else {
+                        theme.error
+                    };
+                    Span::styled(format!("{}ms", ms), Style::default().fg(color))
+                }
+                None => Span::styled("—", Style::default().fg(theme.muted)),
+            },
         ]),
         Line::raw(""),
         Line::from(vec![
             Span::raw("  Last Order: "),
             if let Some(ref id) = app.last_order_id {
-                Span::styled(&id[..12.min(id.len())], Style::default().fg(Color::Cyan))
+                Span::styled(truncate_str(id, 12), Style::default().fg(theme.accent))
             } else {
-                Span::styled("None", Style::default().fg(Color::Gray))
+                Span::styled("None", Style::default().fg(theme.muted))
             },
         ]),
     ];
@@ -273,38 +471,147 @@ fn draw_dashboard(frame: &mut Frame, area: Rect, app: &App) {
         Block::default()
             .borders(Borders::ALL)
             .title(" 📊 System Status ")
-            .border_style(Style::default().fg(Color::Blue)),
+            .border_style(Style::default().fg(theme.info)),
     );
 
-    frame.render_widget(status_widget, columns[1]);
+    // Right column: System Status + Equity Curve
+    let right_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(9), Constraint::Min(6)])
+        .split(columns[1]);
+
+    frame.render_widget(status_widget, right_layout[0]);
+    draw_equity_curve(frame, right_layout[1], app);
+}
+
+fn draw_equity_curve(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(" 📈 Equity Curve ")
+        .border_style(Style::default().fg(theme.accent));
+
+    if app.equity_curve.len() < 2 {
+        let msg = Paragraph::new(vec![
+            Line::raw(""),
+            Line::styled(
+                "  Waiting for portfolio snapshots...",
+                Style::default().fg(theme.muted),
+            ),
+        ])
+        .block(block);
+        frame.render_widget(msg, area);
+        return;
+    }
+
+    let points: Vec<(f64, f64)> = app
+        .equity_curve
+        .iter()
+        .map(|(ts, value)| (*ts as f64, *value))
+        .collect();
+
+    let first_ts = points[0].0;
+    let last_ts = points[points.len() - 1].0;
+    let low = points.iter().map(|p| p.1).fold(f64::INFINITY, f64::min);
+    let high = points.iter().map(|p| p.1).fold(f64::NEG_INFINITY, f64::max);
+    // Keep a flat line visible by padding the y range
+    let (y_min, y_max) = if (high - low).abs() < f64::EPSILON {
+        (low - 1.0, high + 1.0)
+    } else {
+        (low, high)
+    };
+
+    let session_up = points[points.len() - 1].1 >= points[0].1;
+    let line_color = if session_up {
+        theme.success
+    } else {
+        theme.error
+    };
+
+    let time_label = |ts: f64| {
+        chrono::DateTime::from_timestamp(ts as i64, 0)
+            .map(|dt| dt.with_timezone(&chrono::Local).format("%H:%M").to_string())
+            .unwrap_or_default()
+    };
+
+    let datasets = vec![Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(line_color))
+        .data(&points)];
+
+    let chart = Chart::new(datasets)
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .bounds([first_ts, last_ts])
+                .labels(vec![
+                    Span::raw(time_label(first_ts)),
+                    Span::raw(time_label(last_ts)),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(theme.muted))
+                .bounds([y_min, y_max])
+                .labels(vec![
+                    Span::styled(format!("${:.2}", low), Style::default().fg(theme.error)),
+                    Span::styled(format!("${:.2}", high), Style::default().fg(theme.success)),
+                ]),
+        );
+
+    frame.render_widget(chart, area);
 }
 
 fn draw_orders(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let items: Vec<ListItem> = if app.active_orders.is_empty() {
         vec![ListItem::new(Line::styled(
             "  No active orders",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.warning),
         ))]
     } else {
         app.active_orders
             .iter()
-            .map(|order| {
+            .enumerate()
+            .map(|(i, order)| {
                 let side_style = if order.side == "BUY" {
-                    Style::default().fg(Color::Green)
+                    Style::default().fg(theme.success)
+                } else {
+                    Style::default().fg(theme.error)
+                };
+
+                let prefix = if i == app.selected_order_index {
+                    "▶ "
                 } else {
-                    Style::default().fg(Color::Red)
+                    "  "
                 };
 
                 ListItem::new(Line::from(vec![
-                    Span::raw("  "),
+                    Span::styled(prefix, Style::default().fg(theme.warning).bold()),
                     Span::styled(
-                        &order.order_id[..12.min(order.order_id.len())],
-                        Style::default().fg(Color::Cyan),
+                        truncate_str(&order.order_id, 20),
+                        Style::default().fg(theme.accent),
                     ),
                     Span::raw(" | "),
                     Span::styled(&order.side, side_style),
                     Span::raw(" | "),
                     Span::raw(format!("{} @ ${:.2}", order.size, order.price)),
+                    Span::raw(" | "),
+                    Span::styled(
+                        format!(
+                            "filled {}/{} ({:.0}%)",
+                            order.filled_size,
+                            order.size,
+                            order.filled_size / order.size * 100.0
+                        ),
+                        Style::default().fg(if order.filled_size > 0.0 {
+                            theme.warning
+                        } else {
+                            theme.muted
+                        }),
+                    ),
                 ]))
             })
             .collect()
@@ -314,13 +621,14 @@ fn draw_orders(frame: &mut Frame, area: Rect, app: &App) {
         Block::default()
             .borders(Borders::ALL)
             .title(format!(" 📋 Active Orders ({}) ", app.active_orders.len()))
-            .border_style(Style::default().fg(Color::Blue)),
+            .border_style(Style::default().fg(theme.info)),
     );
 
     frame.render_widget(orders_list, area);
 }
 
 fn draw_markets(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Split: Search info + Market list
     let layout = Layout::default()
         .direction(Direction::Vertical)
@@ -331,33 +639,33 @@ fn draw_markets(frame: &mut Frame, area: Rect, app: &App) {
     let search_info = if app.is_loading_markets {
         Line::from(vec![Span::styled(
             "  Loading markets...",
-            Style::default().fg(Color::Yellow),
+            Style::default().fg(theme.warning),
         )])
     } else if !app.market_search_query.is_empty() {
         Line::from(vec![
             Span::raw("  Search: "),
             Span::styled(
                 &app.market_search_query,
-                Style::default().fg(Color::Cyan).bold(),
+                Style::default().fg(theme.accent).bold(),
             ),
             Span::raw(" | "),
             Span::styled(
                 format!("{} results", app.available_markets.len()),
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.success),
             ),
             Span::raw(" | "),
             Span::styled(
                 "↑↓ Navigate, Enter to join",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ),
         ])
     } else {
         Line::from(vec![
-            Span::styled("  Press ", Style::default().fg(Color::Gray)),
-            Span::styled("S", Style::default().fg(Color::Yellow).bold()),
-            Span::styled(" to search or ", Style::default().fg(Color::Gray)),
-            Span::styled("T", Style::default().fg(Color::Yellow).bold()),
-            Span::styled(" for trending markets", Style::default().fg(Color::Gray)),
+            Span::styled("  Press ", Style::default().fg(theme.muted)),
+            Span::styled("S", Style::default().fg(theme.warning).bold()),
+            Span::styled(" to search or ", Style::default().fg(theme.muted)),
+            Span::styled("T", Style::default().fg(theme.warning).bold()),
+            Span::styled(" for trending markets", Style::default().fg(theme.muted)),
         ])
     };
 
@@ -365,7 +673,7 @@ fn draw_markets(frame: &mut Frame, area: Rect, app: &App) {
         Block::default()
             .borders(Borders::ALL)
             .title(" 🔍 Market Search ")
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(Style::default().fg(theme.accent)),
     );
 
     frame.render_widget(search_widget, layout[0]);
@@ -376,24 +684,24 @@ fn draw_markets(frame: &mut Frame, area: Rect, app: &App) {
             ListItem::new(Line::raw("")),
             ListItem::new(Line::styled(
                 "  No markets loaded",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme.warning),
             )),
             ListItem::new(Line::raw("")),
             ListItem::new(Line::styled(
                 "  Commands:",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             )),
             ListItem::new(Line::styled(
                 "    /search <keyword>  - Search markets",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             )),
             ListItem::new(Line::styled(
                 "    /trending          - Show trending",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             )),
             ListItem::new(Line::styled(
-                "    /joinmarket <#>    - Join by index",
-                Style::default().fg(Color::Gray),
+                "    /joinmarket #<n>   - Join by index",
+                Style::default().fg(theme.muted),
             )),
         ]
     } else {
@@ -406,25 +714,27 @@ fn draw_markets(frame: &mut Frame, area: Rect, app: &App) {
 
                 let prefix = if is_selected { "▶ " } else { "  " };
                 let index_style = if is_selected {
-                    Style::default().fg(Color::Yellow).bold()
+                    Style::default().fg(theme.warning).bold()
                 } else {
-                    Style::default().fg(Color::Gray)
+                    Style::default().fg(theme.muted)
                 };
 
-                let question_style = if is_joined {
-                    Style::default().fg(Color::Green)
+                let question_style = if !market.active {
+                    Style::default().fg(theme.dim)
+                } else if is_joined {
+                    Style::default().fg(theme.success)
                 } else if is_selected {
-                    Style::default().fg(Color::White).bold()
+                    Style::default().fg(theme.text).bold()
                 } else {
-                    Style::default().fg(Color::White)
+                    Style::default().fg(theme.text)
                 };
 
                 let joined_marker = if is_joined { " ✓" } else { "" };
 
                 // Truncate question to fit
                 let max_len = 60;
-                let question = if market.question.len() > max_len {
-                    format!("{}...", &market.question[..max_len])
+                let question = if market.question.chars().count() > max_len {
+                    format!("{}...", truncate_str(&market.question, max_len))
                 } else {
                     market.question.clone()
                 };
@@ -432,12 +742,12 @@ fn draw_markets(frame: &mut Frame, area: Rect, app: &App) {
                 // Price display
                 let price_info = if market.prices.len() >= 2 {
                     format!(
-                        " [{:.0}%/{:.0}%]",
+                        "[{:.0}%/{:.0}%]",
                         market.prices[0] * 100.0,
                         market.prices[1] * 100.0
                     )
                 } else if !market.prices.is_empty() {
-                    format!(" [{:.0}%]", market.prices[0] * 100.0)
+                    format!("[{:.0}%]", market.prices[0] * 100.0)
                 } else {
                     String::new()
                 };
@@ -445,49 +755,317 @@ fn draw_markets(frame: &mut Frame, area: Rect, app: &App) {
                 ListItem::new(Line::from(vec![
                     Span::raw(prefix),
                     Span::styled(format!("{:2}. ", i + 1), index_style),
-                    Span::styled(question, question_style),
-                    Span::styled(price_info, Style::default().fg(Color::Cyan)),
-                    Span::styled(joined_marker, Style::default().fg(Color::Green)),
+                    Span::styled(format!("{:<63}", question), question_style),
+                    Span::styled(
+                        format!(" {:>10}", price_info),
+                        Style::default().fg(theme.accent),
+                    ),
+                    Span::styled(
+                        format!(" {:>10}", format_volume(&market.volume)),
+                        Style::default().fg(theme.highlight),
+                    ),
+                    Span::styled(joined_marker, Style::default().fg(theme.success)),
                 ]))
             })
             .collect()
     };
 
+    // Column headers, marking the active sort column
+    let items = if app.available_markets.is_empty() {
+        items
+    } else {
+        let header_style = Style::default().fg(theme.muted).bold();
+        let header = ListItem::new(Line::from(vec![
+            Span::styled(
+                format!(
+                    "      {:<63}",
+                    sort_header(app, MarketSort::Question, "Question")
+                ),
+                header_style,
+            ),
+            Span::styled(
+                format!(" {:>10}", sort_header(app, MarketSort::Price, "Price")),
+                header_style,
+            ),
+            Span::styled(
+                format!(" {:>10}", sort_header(app, MarketSort::Volume, "Volume")),
+                header_style,
+            ),
+        ]));
+        std::iter::once(header).chain(items).collect()
+    };
+
+    let sort_indicator = if app.market_sort == MarketSort::None {
+        String::new()
+    } else {
+        format!(
+            " · {} {}",
+            app.market_sort.title(),
+            if app.market_sort_ascending {
+                "▲"
+            } else {
+                "▼"
+            }
+        )
+    };
+
     let markets_list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .title(format!(" 📈 Markets ({}) ", app.available_markets.len()))
-            .border_style(Style::default().fg(Color::Yellow)),
+            .title(format!(
+                " 📈 Markets ({}){} ",
+                app.available_markets.len(),
+                sort_indicator
+            ))
+            .border_style(Style::default().fg(theme.warning)),
     );
 
     frame.render_widget(markets_list, layout[1]);
 }
 
+/// Column label with a direction arrow when it is the active sort
+fn sort_header(app: &App, sort: MarketSort, label: &str) -> String {
+    if app.market_sort != sort {
+        label.to_string()
+    } else if app.market_sort_ascending {
+        format!("{} ▲", label)
+    } else {
+        format!("{} ▼", label)
+    }
+}
+
+/// Braille spinner frames, one per 100ms
+const SPINNER_FRAMES: [&str; 10] = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+
+/// Monitored market IDs for the Dashboard, favorites first and only those
+/// when the favorites filter is on
+fn monitored_market_ids(app: &App) -> Vec<&String> {
+    let (mut favorites, others): (Vec<_>, Vec<_>) = app
+        .joined_markets
+        .iter()
+        .partition(|id| app.favorite_markets.contains(*id));
+    if !app.favorites_only {
+        favorites.extend(others);
+    }
+    favorites
+}
+
+/// `message` behind a spinner that advances with the wall clock, so it moves
+/// at the redraw rate without any state
+fn loading_line(message: &str, theme: &Theme) -> Line<'static> {
+    let millis = std::time::UNIX_EPOCH
+        .elapsed()
+        .unwrap_or_default()
+        .as_millis();
+    let frame = SPINNER_FRAMES[(millis / 100) as usize % SPINNER_FRAMES.len()];
+    Line::styled(
+        format!("  {} {}", frame, message),
+        Style::default().fg(theme.warning),
+    )
+}
+
+/// A P&L figure in dollars, or as a percentage of `percent_of` when set.
+/// Without positive starting capital there is nothing to take a percentage of.
+fn pnl_text(pnl: f64, percent_of: Option<f64>) -> String {
+    match percent_of {
+        None => format!("{:+.2}", pnl),
+        Some(capital) if capital > 0.0 => format!("{:+.2}%", pnl / capital * 100.0),
+        Some(_) => "n/a %".to_string(),
+    }
+}
+
+/// One bar per value, scaled between the lowest and highest value so small
+/// moves stay visible. Equal values sit in the middle.
+fn sparkline(values: &[f64]) -> Vec<char> {
+    const BARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+    let low = values.iter().copied().fold(f64::INFINITY, f64::min);
+    let high = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    values
+        .iter()
+        .map(|value| {
+            if high - low <= f64::EPSILON {
+                return BARS[BARS.len() / 2];
+            }
+            let level = (value - low) / (high - low) * (BARS.len() - 1) as f64;
+            BARS[level.round() as usize]
+        })
+        .collect()
+}
+
+/// OBI history as a sparkline, each bar green when buy pressure grew since the
+/// previous reading and red when it shrank, followed by an arrow for the move
+/// over the whole window
+fn obi_trend_spans(history: &[f64], theme: &Theme) -> Vec<Span<'static>> {
+    let direction_color = |change: f64| {
+        if change > f64::EPSILON {
+            theme.success
+        } else if change < -f64::EPSILON {
+            theme.error
+        } else {
+            theme.muted
+        }
+    };
+
+    let mut spans: Vec<Span> = sparkline(history)
+        .into_iter()
+        .enumerate()
+        .map(|(i, bar)| {
+            let change = i.checked_sub(1).map_or(0.0, |p| history[i] - history[p]);
+            Span::styled(
+                bar.to_string(),
+                Style::default().fg(direction_color(change)),
+            )
+        })
+        .collect();
+
+    let net = match (history.first(), history.last()) {
+        (Some(first), Some(last)) => last - first,
+        _ => 0.0,
+    };
+    let arrow = if net > f64::EPSILON {
+        " ▲"
+    } else if net < -f64::EPSILON {
+        " ▼"
+    } else {
+        " ─"
+    };
+    spans.push(Span::styled(
+        arrow,
+        Style::default().fg(direction_color(net)).bold(),
+    ));
+    spans
+}
+
+/// Age of a market's data: plain while fresh, yellow once stale and red at
+/// twice the stale threshold
+fn freshness_span(age: Option<Duration>, theme: &Theme) -> Span<'static> {
+    let Some(age) = age else {
+        return Span::styled("waiting for data", Style::default().fg(theme.muted));
+    };
+    let secs = age.as_secs();
+    if age >= STALE_DATA_AFTER * 2 {
+        Span::styled(
+            format!("stale ({}s ago)", secs),
+            Style::default().fg(theme.error).bold(),
+        )
+    } else if age >= STALE_DATA_AFTER {
+        Span::styled(
+            format!("stale ({}s ago)", secs),
+            Style::default().fg(theme.warning),
+        )
+    } else {
+        Span::styled(format!("{}s ago", secs), Style::default().fg(theme.success))
+    }
+}
+
+/// Compact dollar volume, e.g. "$1.2M"
+fn format_volume(volume: &str) -> String {
+    let value = parse_volume(volume);
+    if value >= 1_000_000.0 {
+        format!("${:.1}M", value / 1_000_000.0)
+    } else if value >= 1_000.0 {
+        format!("${:.1}K", value / 1_000.0)
+    } else {
+        format!("${:.0}", value)
+    }
+}
+
+/// First `max_chars` characters of `s`, cut on a char boundary so multi-byte
+/// text (emoji, accents) never panics
+pub fn truncate_str(s: &str, max_chars: usize) -> &str {
+    match s.char_indices().nth(max_chars) {
+        Some((idx, _)) => &s[..idx],
+        None => s,
+    }
+}
+
+/// `s` cut to at most `max_chars` characters, ending in "..." when shortened
+fn ellipsize(s: &str, max_chars: usize) -> String {
+    if s.chars().count() <= max_chars {
+        return s.to_string();
+    }
+    format!("{}...", truncate_str(s, max_chars.saturating_sub(3)))
+}
+
+/// Split `text` into lines of at most `width` characters, breaking between
+/// words. Words longer than a line are split across lines.
+fn wrap_words(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let mut word = word;
+        loop {
+            let used = current.chars().count();
+            let needed = word.chars().count() + usize::from(used > 0);
+            if used + needed <= width {
+                if used > 0 {
+                    current.push(' ');
+                }
+                current.push_str(word);
+                break;
+            }
+            if used > 0 {
+                lines.push(std::mem::take(&mut current));
+                continue;
+            }
+            // Alone on the line and still too long: hard-split it
+            let head = truncate_str(word, width);
+            lines.push(head.to_string());
+            word = &word[head.len()..];
+            if word.is_empty() {
+                break;
+            }
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+    lines
+}
+
 fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    if app.is_loading_watchlist {
+        let msg = Paragraph::new(vec![
+            Line::raw(""),
+            loading_line("Loading watched markets...", theme),
+        ])
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(" 📊 Market Detail ")
+                .border_style(Style::default().fg(theme.muted)),
+        );
+        frame.render_widget(msg, area);
+        return;
+    }
+
     // If no watched markets, show message
     if app.watched_markets_info.is_empty() {
         let msg = Paragraph::new(vec![
             Line::raw(""),
             Line::styled(
                 "  No markets being watched",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme.warning),
             ),
             Line::raw(""),
             Line::styled(
                 "  Join a market from the ",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ),
             Line::from(vec![
-                Span::styled("  ", Style::default().fg(Color::Gray)),
-                Span::styled("Markets", Style::default().fg(Color::Cyan).bold()),
-                Span::styled(" tab to view details", Style::default().fg(Color::Gray)),
+                Span::styled("  ", Style::default().fg(theme.muted)),
+                Span::styled("Markets", Style::default().fg(theme.accent).bold()),
+                Span::styled(" tab to view details", Style::default().fg(theme.muted)),
             ]),
         ])
         .block(
             Block::default()
                 .borders(Borders::ALL)
                 .title(" 📊 Market Detail ")
-                .border_style(Style::default().fg(Color::Yellow)),
+                .border_style(Style::default().fg(theme.warning)),
         );
         frame.render_widget(msg, area);
         return;
@@ -509,85 +1087,112 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
         ])
         .split(area);
 
-    // COLUMN 1: Market List
+    // COLUMN 1: Market List, names cut to fit inside the borders and "> " prefix
+    let name_width = (columns[0].width as usize).saturating_sub(4);
     let items: Vec<ListItem> = app
         .watched_markets_info
         .iter()
         .enumerate()
+        .filter(|(_, m)| app.is_watched_market_shown(m))
         .map(|(i, m)| {
             let is_selected = i == app.selected_watched_market_index;
             let style = if is_selected {
-                Style::default().fg(Color::Yellow).bold()
+                Style::default().fg(theme.warning).bold()
             } else {
-                Style::default().fg(Color::Gray)
+                Style::default().fg(theme.muted)
             };
-
-            // Show simple name or ID
-            let name = if m.question.len() > 18 {
-                format!("{}...", &m.question[..18])
+            let style = if m.active {
+                style
             } else {
-                m.question.clone()
+                style.fg(theme.dim).crossed_out()
             };
 
             let prefix = if is_selected { "> " } else { "  " };
+            let mut spans = vec![Span::styled(prefix, style)];
+            let mut width = name_width;
+            if app.favorite_markets.contains(&m.id) {
+                spans.push(Span::styled("★", Style::default().fg(theme.warning)));
+                width = width.saturating_sub(1);
+            }
+            spans.push(Span::styled(ellipsize(&m.question, width), style));
 
-            ListItem::new(Line::from(vec![
-                Span::styled(prefix, style),
-                Span::styled(name, style),
-            ]))
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
+    let list_title = if app.favorites_only {
+        " ★ Favorites "
+    } else {
+        " Markets "
+    };
     let list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .title(" Markets ")
-            .border_style(Style::default().fg(Color::Blue)),
+            .title(list_title)
+            .border_style(Style::default().fg(theme.info)),
     );
     frame.render_widget(list, columns[0]);
 
-    // COLUMN 2: Market Information
-    let mut info_lines = vec![
-        Line::from(vec![Span::styled(
-            "  Question: ",
-            Style::default().fg(Color::Cyan).bold(),
-        )]),
-        Line::from(vec![
+    // COLUMN 2: Market Information, with the full question wrapped and indented
+    let question_width = (columns[1].width as usize).saturating_sub(4);
+    let mut info_lines = vec![Line::from(vec![Span::styled(
+        "  Question: ",
+        Style::default().fg(theme.accent).bold(),
+    )])];
+    for line in wrap_words(&market.question, question_width) {
+        info_lines.push(Line::from(vec![
             Span::raw("  "),
-            Span::styled(&market.question, Style::default().fg(Color::White)),
-        ]),
+            Span::styled(line, Style::default().fg(theme.text)),
+        ]));
+    }
+    info_lines.extend([
         Line::raw(""),
         Line::from(vec![
-            Span::styled("  Market ID: ", Style::default().fg(Color::Gray)),
+            Span::styled("  Market ID: ", Style::default().fg(theme.muted)),
             Span::styled(
-                &market.id[..16.min(market.id.len())],
-                Style::default().fg(Color::Yellow),
+                truncate_str(&market.id, 16),
+                Style::default().fg(theme.warning),
             ),
             Span::raw("..."),
         ]),
         Line::raw(""),
-    ];
+    ]);
+
+    // Research note from /note
+    if let Some(note) = app.market_notes.get(&market.id) {
+        info_lines.push(Line::styled(
+            "  📝 Note:",
+            Style::default().fg(theme.accent).bold(),
+        ));
+        for line in wrap_words(note, question_width) {
+            info_lines.push(Line::from(vec![
+                Span::raw("  "),
+                Span::styled(line, Style::default().fg(theme.text).italic()),
+            ]));
+        }
+        info_lines.push(Line::raw(""));
+    }
 
     // Outcomes and prices
     if !market.outcomes.is_empty() {
         info_lines.push(Line::styled(
             "  Outcomes & Prices:",
-            Style::default().fg(Color::Cyan).bold(),
+            Style::default().fg(theme.accent).bold(),
         ));
         for (i, outcome) in market.outcomes.iter().enumerate() {
             let price = market.prices.get(i).unwrap_or(&0.0);
             let price_pct = price * 100.0;
             let color = if price_pct > 60.0 {
-                Color::Green
+                theme.success
             } else if price_pct > 40.0 {
-                Color::Yellow
+                theme.warning
             } else {
-                Color::Red
+                theme.error
             };
 
             info_lines.push(Line::from(vec![
                 Span::raw("    "),
-                Span::styled(outcome, Style::default().fg(Color::White)),
+                Span::styled(outcome, Style::default().fg(theme.text)),
                 Span::raw(": "),
                 Span::styled(
                     format!("{:.1}%", price_pct),
@@ -600,26 +1205,76 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
 
     // Volume
     info_lines.push(Line::from(vec![
-        Span::styled("  Volume: ", Style::default().fg(Color::Gray)),
-        Span::styled(&market.volume, Style::default().fg(Color::Cyan)),
+        Span::styled("  Volume: ", Style::default().fg(theme.muted)),
+        Span::styled(&market.volume, Style::default().fg(theme.accent)),
     ]));
 
     // Status
     info_lines.push(Line::from(vec![
-        Span::styled("  Status: ", Style::default().fg(Color::Gray)),
+        Span::styled("  Status: ", Style::default().fg(theme.muted)),
         if market.active {
-            Span::styled("Active", Style::default().fg(Color::Green).bold())
+            Span::styled("Active", Style::default().fg(theme.success).bold())
         } else {
-            Span::styled("Closed", Style::default().fg(Color::Red))
+            Span::styled("Closed", Style::default().fg(theme.error))
         },
     ]));
 
+    // How much to trust the numbers on this screen
+    let data_age = app
+        .market_analysis_data
+        .get(&market.id)
+        .and_then(|a| a.data_age());
+    info_lines.push(Line::from(vec![
+        Span::styled("  Updated: ", Style::default().fg(theme.muted)),
+        freshness_span(data_age, theme),
+    ]));
+
+    // Top of book
+    let book = app
+        .market_analysis_data
+        .get(&market.id)
+        .and_then(|a| a.order_book.as_ref());
+    let price_or_dash = |p: Option<f64>| p.map_or("-".to_string(), |p| format!("{:.3}", p));
+    info_lines.push(Line::raw(""));
+    info_lines.push(Line::from(vec![
+        Span::styled("  Best Bid: ", Style::default().fg(theme.muted)),
+        Span::styled(
+            price_or_dash(book.and_then(|b| b.best_bid())),
+            Style::default().fg(theme.success),
+        ),
+    ]));
+    info_lines.push(Line::from(vec![
+        Span::styled("  Best Ask: ", Style::default().fg(theme.muted)),
+        Span::styled(
+            price_or_dash(book.and_then(|b| b.best_ask())),
+            Style::default().fg(theme.error),
+        ),
+    ]));
+    let spread = book.and_then(|b| b.spread());
+    let spread_color = match spread {
+        Some(s) if s <= 0.01 => theme.success,
+        Some(s) if s <= 0.05 => theme.warning,
+        Some(_) => theme.error,
+        None => theme.muted,
+    };
+    info_lines.push(Line::from(vec![
+        Span::styled("  Spread:   ", Style::default().fg(theme.muted)),
+        Span::styled(
+            price_or_dash(spread),
+            Style::default().fg(spread_color).bold(),
+        ),
+    ]));
+
     let info_widget = Paragraph::new(info_lines)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" 📋 Market Info ")
-                .border_style(Style::default().fg(Color::Cyan)),
+                .title(if app.refresh_requested {
+                    " 📋 Market Info (refreshing...) "
+                } else {
+                    " 📋 Market Info "
+                })
+                .border_style(Style::default().fg(theme.accent)),
         )
         .wrap(Wrap { trim: true });
 
@@ -630,19 +1285,28 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(10), // Volume velocity graph
-            Constraint::Length(6),  // OBI visualization
+            Constraint::Length(9),  // OBI visualization and trend
             Constraint::Min(5),     // Recent events
         ])
         .split(columns[2]);
 
     // Get analysis data for this market (if available)
     let analysis = app.market_analysis_data.get(&market.id);
+    let velocity_threshold = app.velocity_threshold_for(&market.id);
+    let obi_threshold = app.obi_threshold_for(&market.id);
+    // Mark thresholds that come from a per-market override
+    let overrides = app
+        .market_thresholds
+        .get(&market.id)
+        .copied()
+        .unwrap_or_default();
+    let source = |overridden: bool| if overridden { " (market)" } else { "" };
 
     // Volume Velocity Graph (ASCII)
     let mut velocity_lines = vec![
         Line::styled(
             "  Volume Velocity (V_v)",
-            Style::default().fg(Color::Yellow).bold(),
+            Style::default().fg(theme.warning).bold(),
         ),
         Line::raw(""),
     ];
@@ -650,12 +1314,12 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
     if let Some(analysis) = analysis {
         if let Some(velocity) = analysis.current_velocity {
             let velocity_str = format!("{:+.2}", velocity);
-            let velocity_color = if velocity.abs() > 1000.0 {
-                Color::Red
-            } else if velocity.abs() > 500.0 {
-                Color::Yellow
+            let velocity_color = if velocity.abs() > velocity_threshold {
+                theme.error
+            } else if velocity.abs() > velocity_threshold / 2.0 {
+                theme.warning
             } else {
-                Color::Green
+                theme.success
             };
 
             velocity_lines.push(Line::from(vec![
@@ -665,7 +1329,9 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
             ]));
 
             // Simple ASCII bar
-            let bar_length = (velocity.abs() / 50.0).min(30.0) as usize;
+            let width = bar_width(analysis_layout[0]);
+            let bar_length = (velocity.abs() / VELOCITY_BAR_FULL_SCALE * width as f64)
+                .min(width as f64) as usize;
             let bar = "█".repeat(bar_length);
             velocity_lines.push(Line::from(vec![
                 Span::raw("  "),
@@ -674,27 +1340,31 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
         } else {
             velocity_lines.push(Line::styled(
                 "  No data yet",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ));
         }
     } else {
         velocity_lines.push(Line::styled(
             "  Collecting data...",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme.muted),
         ));
     }
 
     velocity_lines.push(Line::raw(""));
     velocity_lines.push(Line::styled(
-        "  Threshold: 1000.0 vol/sec",
-        Style::default().fg(Color::Gray),
+        format!(
+            "  Threshold: {:.1} vol/sec{}",
+            velocity_threshold,
+            source(overrides.velocity.is_some())
+        ),
+        Style::default().fg(theme.muted),
     ));
 
     let velocity_widget = Paragraph::new(velocity_lines).block(
         Block::default()
             .borders(Borders::ALL)
             .title(" 📈 Velocity ")
-            .border_style(Style::default().fg(Color::Yellow)),
+            .border_style(Style::default().fg(theme.warning)),
     );
 
     frame.render_widget(velocity_widget, analysis_layout[0]);
@@ -703,7 +1373,7 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
     let mut obi_lines = vec![
         Line::styled(
             "  Order Book Imbalance",
-            Style::default().fg(Color::Magenta).bold(),
+            Style::default().fg(theme.highlight).bold(),
         ),
         Line::raw(""),
     ];
@@ -711,10 +1381,10 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
     if let Some(analysis) = analysis {
         if let Some(obi) = analysis.current_obi {
             let obi_pct = obi * 100.0;
-            let obi_color = if obi.abs() > 0.3 {
-                Color::Red
+            let obi_color = if obi.abs() > obi_threshold {
+                theme.error
             } else {
-                Color::Green
+                theme.success
             };
 
             obi_lines.push(Line::from(vec![
@@ -725,34 +1395,54 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
                 ),
             ]));
 
-            // Visual bar from -100% to +100%
-            let bar_pos = ((obi + 1.0) / 2.0 * 30.0) as usize;
-            let left = "─".repeat(bar_pos.min(30));
-            let right = "─".repeat((30 - bar_pos).max(0));
+            // Visual bar from -100% to +100%, leaving a column for the marker
+            let width = bar_width(analysis_layout[1]).saturating_sub(1);
+            let bar_pos = ((obi + 1.0) / 2.0 * width as f64) as usize;
+            let left = "─".repeat(bar_pos.min(width));
+            let right = "─".repeat(width.saturating_sub(bar_pos));
             obi_lines.push(Line::from(vec![
                 Span::raw("  "),
-                Span::styled(left, Style::default().fg(Color::Red)),
+                Span::styled(left, Style::default().fg(theme.error)),
                 Span::styled("●", Style::default().fg(obi_color).bold()),
-                Span::styled(right, Style::default().fg(Color::Green)),
+                Span::styled(right, Style::default().fg(theme.success)),
             ]));
+
+            let readings = bar_width(analysis_layout[1]).saturating_sub(9);
+            let history: Vec<f64> = analysis.obi_history.iter().copied().collect();
+            let recent = &history[history.len().saturating_sub(readings)..];
+            if recent.len() >= 2 {
+                let mut trend = vec![Span::raw("  Trend: ")];
+                trend.extend(obi_trend_spans(recent, theme));
+                obi_lines.push(Line::from(trend));
+            }
         } else {
             obi_lines.push(Line::styled(
                 "  No data yet",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ));
         }
     } else {
         obi_lines.push(Line::styled(
             "  Collecting data...",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme.muted),
         ));
     }
 
+    obi_lines.push(Line::raw(""));
+    obi_lines.push(Line::styled(
+        format!(
+            "  Threshold: ±{:.2}{}",
+            obi_threshold,
+            source(overrides.obi.is_some())
+        ),
+        Style::default().fg(theme.muted),
+    ));
+
     let obi_widget = Paragraph::new(obi_lines).block(
         Block::default()
             .borders(Borders::ALL)
             .title(" ⚖️  OBI ")
-            .border_style(Style::default().fg(Color::Magenta)),
+            .border_style(Style::default().fg(theme.highlight)),
     );
 
     frame.render_widget(obi_widget, analysis_layout[1]);
@@ -764,7 +1454,7 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
         if analysis.recent_events.is_empty() {
             events_lines.push(Line::styled(
                 "  No spike events detected yet",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ));
         } else {
             for event in analysis.recent_events.iter().take(5) {
@@ -773,11 +1463,11 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
                     .unwrap_or_else(|| "Unknown".to_string());
 
                 events_lines.push(Line::from(vec![
-                    Span::styled(format!("  [{}] ", time), Style::default().fg(Color::Gray)),
-                    Span::styled("Velocity: ", Style::default().fg(Color::Yellow)),
+                    Span::styled(format!("  [{}] ", time), Style::default().fg(theme.muted)),
+                    Span::styled("Velocity: ", Style::default().fg(theme.warning)),
                     Span::styled(
                         format!("{:+.1}", event.velocity),
-                        Style::default().fg(Color::Red).bold(),
+                        Style::default().fg(theme.error).bold(),
                     ),
                 ]));
             }
@@ -785,7 +1475,7 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
     } else {
         events_lines.push(Line::styled(
             "  Initializing detector...",
-            Style::default().fg(Color::Gray),
+            Style::default().fg(theme.muted),
         ));
     }
 
@@ -793,48 +1483,180 @@ fn draw_market_detail(frame: &mut Frame, area: Rect, app: &App) {
         Block::default()
             .borders(Borders::ALL)
             .title(" 🔔 Recent Events ")
-            .border_style(Style::default().fg(Color::Red)),
+            .border_style(Style::default().fg(theme.error)),
     );
 
     frame.render_widget(events_widget, analysis_layout[2]);
 }
 
+/// Width available for a bar inside a bordered panel with a two-space indent
+fn bar_width(area: Rect) -> usize {
+    (area.width as usize).saturating_sub(4).max(MIN_BAR_WIDTH)
+}
+
+fn draw_settings(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let config = app.execution_engine.config();
+    let label_style = Style::default().fg(theme.muted);
+
+    let mut lines = vec![
+        Line::raw(""),
+        Line::styled(
+            "  SPIKE DETECTION",
+            Style::default().fg(theme.success).bold(),
+        ),
+    ];
+
+    for (i, field) in SettingsField::all().into_iter().enumerate() {
+        let selected = i == app.settings_selected;
+        let editing = selected && app.input_mode == InputMode::SettingsEdit;
+        let value = if editing {
+            Span::styled(
+                format!("{}█", app.settings_input),
+                Style::default().fg(theme.warning).bold(),
+            )
+        } else {
+            Span::styled(
+                app.settings_value(field).to_string(),
+                Style::default().fg(theme.accent),
+            )
+        };
+        let marker = if selected { "▶ " } else { "  " };
+        lines.push(Line::from(vec![
+            Span::styled(format!("  {}", marker), Style::default().fg(theme.warning)),
+            Span::styled(format!("{:<22}", field.label()), label_style),
+            value,
+        ]));
+    }
+
+    let setting = |label: &str, value: String| {
+        Line::from(vec![
+            Span::styled(format!("    {:<22}", label), label_style),
+            Span::raw(value),
+        ])
+    };
+    let mode = if config.paper_trading {
+        "Paper (simulated)"
+    } else {
+        "Live"
+    };
+
+    lines.extend([
+        Line::raw(""),
+        Line::styled("  TRADING", Style::default().fg(theme.success).bold()),
+        setting("Mode", mode.to_string()),
+        setting("Min order size", format!("{} USDC", config.min_order_size)),
+        setting("Max order size", format!("{} USDC", config.max_order_size)),
+        setting("Cancel on exit", config.cancel_on_exit.to_string()),
+        Line::raw(""),
+        Line::styled("  SYSTEM", Style::default().fg(theme.success).bold()),
+        // Never rendered, not even partially
+        setting("Private key", "•••••••• (hidden)".to_string()),
+        setting("Database", config.database_path.clone()),
+        setting(
+            "Data retention",
+            format!("{} days", config.data_retention_days),
+        ),
+        setting(
+            "API rate limit",
+            format!("{} req/s", config.api_requests_per_second),
+        ),
+        setting(
+            "Request timeout",
+            format!("{}s", config.request_timeout_secs),
+        ),
+        Line::raw(""),
+        Line::styled(
+            "  Edited values apply to this session only",
+            Style::default().fg(theme.dim),
+        ),
+    ]);
+
+    let settings = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title(" ⚙ Settings ")
+            .border_style(Style::default().fg(theme.accent)),
+    );
+    frame.render_widget(settings, area);
+}
+
 fn draw_logs(frame: &mut Frame, area: Rect, app: &App) {
-    let items: Vec<ListItem> = app
-        .logs
+    let theme = &app.theme;
+    let visible_rows = area.height.saturating_sub(2) as usize;
+    let logs = app.filtered_logs();
+    let total = logs.len();
+    let first = app.logs_scroll_offset.min(total);
+    let last = (first + visible_rows).min(total);
+
+    let items: Vec<ListItem> = logs
         .iter()
         .rev() // Show newest first
-        .take(50)
+        .skip(first)
+        .take(visible_rows)
         .map(|log| {
             let (prefix, style) = match log.level {
-                LogLevel::Info => ("ℹ️ ", Style::default().fg(Color::Cyan)),
-                LogLevel::Warning => ("⚠️ ", Style::default().fg(Color::Yellow)),
-                LogLevel::Error => ("❌", Style::default().fg(Color::Red)),
-                LogLevel::Success => ("✅", Style::default().fg(Color::Green)),
+                LogLevel::Info => ("ℹ️ ", Style::default().fg(theme.accent)),
+                LogLevel::Warning => ("⚠️ ", Style::default().fg(theme.warning)),
+                LogLevel::Error => ("❌", Style::default().fg(theme.error)),
+                LogLevel::Success => ("✅", Style::default().fg(theme.success)),
             };
 
-            ListItem::new(Line::from(vec![
+            let mut spans = vec![
                 Span::styled(
                     format!("[{}] ", log.timestamp),
-                    Style::default().fg(Color::Gray),
+                    Style::default().fg(theme.muted),
                 ),
                 Span::raw(prefix),
-                Span::styled(&log.message, style),
-            ]))
+            ];
+
+            // Highlight the search match within the message
+            match find_ignore_ascii_case(&log.message, &app.log_search) {
+                Some(start) => {
+                    let end = start + app.log_search.len();
+                    spans.push(Span::styled(&log.message[..start], style));
+                    spans.push(Span::styled(
+                        &log.message[start..end],
+                        Style::default().bg(theme.warning).fg(theme.inverse).bold(),
+                    ));
+                    spans.push(Span::styled(&log.message[end..], style));
+                }
+                None => spans.push(Span::styled(&log.message, style)),
+            }
+
+            ListItem::new(Line::from(spans))
         })
         .collect();
 
+    let search = if app.log_search.is_empty() {
+        String::new()
+    } else {
+        format!(" \"{}\"", app.log_search)
+    };
+
     let logs_list = List::new(items).block(
         Block::default()
             .borders(Borders::ALL)
-            .title(format!(" 📝 Logs ({}) ", app.logs.len()))
-            .border_style(Style::default().fg(Color::Gray)),
+            .title(if total == 0 {
+                format!(" 📝 Logs (0) [{}]{} ", app.log_filter.title(), search)
+            } else {
+                format!(
+                    " 📝 Logs (showing {}–{} of {}) [{}]{} ",
+                    first + 1,
+                    last,
+                    total,
+                    app.log_filter.title(),
+                    search
+                )
+            })
+            .border_style(Style::default().fg(theme.muted)),
     );
 
     frame.render_widget(logs_list, area);
 }
 
 fn draw_docs(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     const DOC_SECTIONS: [&str; 5] = [
         "📖 How to Use This Bot",
         "🎯 What is Polymarket?",
@@ -857,9 +1679,9 @@ fn draw_docs(frame: &mut Frame, area: Rect, app: &App) {
             let is_selected = i == app.docs_selected_section;
             let is_viewing = app.docs_viewing_content && is_selected;
             let style = if is_selected {
-                Style::default().fg(Color::Yellow).bold()
+                Style::default().fg(theme.warning).bold()
             } else {
-                Style::default().fg(Color::White)
+                Style::default().fg(theme.text)
             };
             let prefix = if is_viewing {
                 "● "
@@ -885,14 +1707,14 @@ fn draw_docs(frame: &mut Frame, area: Rect, app: &App) {
         Block::default()
             .borders(Borders::ALL)
             .title(list_title)
-            .border_style(Style::default().fg(Color::Cyan)),
+            .border_style(Style::default().fg(theme.accent)),
     );
     frame.render_widget(list, layout[0]);
 
     // Right: Content or Preview
     if app.docs_viewing_content {
         // Show full content with scroll
-        let content = get_doc_content(app.docs_selected_section);
+        let content = get_doc_content(app.docs_selected_section, theme);
         let scroll = app.docs_scroll_offset;
 
         let content_widget = Paragraph::new(content)
@@ -900,32 +1722,32 @@ fn draw_docs(frame: &mut Frame, area: Rect, app: &App) {
                 Block::default()
                     .borders(Borders::ALL)
                     .title(format!(" {} ", DOC_SECTIONS[app.docs_selected_section]))
-                    .border_style(Style::default().fg(Color::Yellow)),
+                    .border_style(Style::default().fg(theme.warning)),
             )
             .wrap(Wrap { trim: false })
             .scroll((scroll, 0));
         frame.render_widget(content_widget, layout[1]);
     } else {
         // Show preview
-        let preview = get_doc_preview(app.docs_selected_section);
+        let preview = get_doc_preview(app.docs_selected_section, theme);
         let preview_widget = Paragraph::new(preview)
             .block(
                 Block::default()
                     .borders(Borders::ALL)
                     .title(" Preview ")
-                    .border_style(Style::default().fg(Color::Gray)),
+                    .border_style(Style::default().fg(theme.muted)),
             )
             .wrap(Wrap { trim: false });
         frame.render_widget(preview_widget, layout[1]);
     }
 }
 
-fn get_doc_preview(section: usize) -> Vec<Line<'static>> {
+fn get_doc_preview(section: usize, theme: &Theme) -> Vec<Line<'static>> {
     match section {
         0 => vec![
             Line::styled(
                 "  How to Use This Bot",
-                Style::default().fg(Color::Yellow).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
             Line::raw(""),
             Line::raw("  Learn the basics of navigating and"),
@@ -933,13 +1755,13 @@ fn get_doc_preview(section: usize) -> Vec<Line<'static>> {
             Line::raw(""),
             Line::styled(
                 "  Press Enter to read more...",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ),
         ],
         1 => vec![
             Line::styled(
                 "  What is Polymarket?",
-                Style::default().fg(Color::Yellow).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
             Line::raw(""),
             Line::raw("  Polymarket is a decentralized"),
@@ -948,13 +1770,13 @@ fn get_doc_preview(section: usize) -> Vec<Line<'static>> {
             Line::raw(""),
             Line::styled(
                 "  Press Enter to read more...",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ),
         ],
         2 => vec![
             Line::styled(
                 "  Trading Mechanics",
-                Style::default().fg(Color::Yellow).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
             Line::raw(""),
             Line::raw("  Understanding shares, prices,"),
@@ -962,13 +1784,13 @@ fn get_doc_preview(section: usize) -> Vec<Line<'static>> {
             Line::raw(""),
             Line::styled(
                 "  Press Enter to read more...",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ),
         ],
         3 => vec![
             Line::styled(
                 "  Spike Detection",
-                Style::default().fg(Color::Yellow).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
             Line::raw(""),
             Line::raw("  How this bot detects volume"),
@@ -976,48 +1798,53 @@ fn get_doc_preview(section: usize) -> Vec<Line<'static>> {
             Line::raw(""),
             Line::styled(
                 "  Press Enter to read more...",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ),
         ],
         4 => vec![
-            Line::styled("  References", Style::default().fg(Color::Yellow).bold()),
+            Line::styled("  References", Style::default().fg(theme.warning).bold()),
             Line::raw(""),
             Line::raw("  Sources and links to learn"),
             Line::raw("  more about prediction markets."),
             Line::raw(""),
             Line::styled(
                 "  Press Enter to read more...",
-                Style::default().fg(Color::Gray),
+                Style::default().fg(theme.muted),
             ),
         ],
         _ => vec![],
     }
 }
 
-fn get_doc_content(section: usize) -> Vec<Line<'static>> {
+/// Number of lines in a Docs section before wrapping, used to bound scrolling
+pub fn doc_line_count(section: usize) -> u16 {
+    get_doc_content(section, &Theme::default()).len() as u16
+}
+
+fn get_doc_content(section: usize, theme: &Theme) -> Vec<Line<'static>> {
     match section {
         0 => vec![
             // HOW TO USE THIS BOT
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::styled(
                 "  HOW TO USE THIS BOT",
-                Style::default().fg(Color::Yellow).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::raw(""),
-            Line::styled("  NAVIGATION", Style::default().fg(Color::Green).bold()),
+            Line::styled("  NAVIGATION", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • Use Tab or ←/→ arrow keys to switch between tabs"),
-            Line::raw("  • Press 1-6 to jump directly to a specific tab"),
+            Line::raw("  • Press 1-7 to jump directly to a specific tab"),
             Line::raw("  • Use ↑/↓ arrow keys to navigate lists"),
             Line::raw(""),
-            Line::styled("  TABS OVERVIEW", Style::default().fg(Color::Green).bold()),
+            Line::styled("  TABS OVERVIEW", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  [1] Dashboard  - View portfolio and system status"),
             Line::raw("  [2] Orders     - See your active orders"),
@@ -1025,57 +1852,63 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw("  [4] Detail     - Detailed view of watched markets"),
             Line::raw("  [5] Logs       - View application logs and events"),
             Line::raw("  [6] Docs       - This documentation"),
+            Line::raw("  [7] Settings   - View config and tune thresholds"),
             Line::raw(""),
             Line::styled(
                 "  SEARCHING MARKETS",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • Press 'S' for quick search"),
             Line::raw("  • Press 'T' for trending markets"),
+            Line::raw("  • Press V, $ or A to sort by volume, price or question"),
             Line::raw("  • Use ':' or '/' to enter command mode"),
             Line::raw("  • Commands: /search <keyword>, /trending, /help"),
             Line::raw(""),
             Line::styled(
                 "  JOINING MARKETS",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • In Markets tab, use ↑/↓ to select a market"),
             Line::raw("  • Press Enter to join the selected market"),
-            Line::raw("  • Or use /joinmarket <number> command"),
+            Line::raw("  • Or use /joinmarket #<number> or /joinmarket <id>"),
+            Line::raw("  • Press Y to copy the selected market ID"),
             Line::raw(""),
-            Line::styled("  BOT CONTROLS", Style::default().fg(Color::Green).bold()),
+            Line::styled("  BOT CONTROLS", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • P - Pause the bot (stops trading)"),
             Line::raw("  • R - Resume the bot (enable trading)"),
+            Line::raw("  • O - New order on the selected watched market"),
+            Line::raw("  • X - Cancel the selected order (Orders tab)"),
             Line::raw("  • ! - PANIC MODE (cancel all orders immediately)"),
-            Line::raw("  • Q - Quit the application"),
+            Line::raw("  • ? - Keybindings for the current tab"),
+            Line::raw("  • Q - Quit (Shift-Q or Ctrl-C quits instantly)"),
             Line::raw(""),
         ],
         1 => vec![
             // WHAT IS POLYMARKET
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::styled(
                 "  WHAT IS POLYMARKET?",
-                Style::default().fg(Color::Yellow).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::raw(""),
-            Line::styled("  OVERVIEW", Style::default().fg(Color::Green).bold()),
+            Line::styled("  OVERVIEW", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  Polymarket is a decentralized prediction market"),
             Line::raw("  platform where users can bet on the outcomes of"),
             Line::raw("  real-world events across politics, sports, crypto,"),
             Line::raw("  and current affairs."),
             Line::raw(""),
-            Line::styled("  HOW IT WORKS", Style::default().fg(Color::Green).bold()),
+            Line::styled("  HOW IT WORKS", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • Events are presented as YES/NO questions"),
             Line::raw("  • Users buy 'shares' representing potential outcomes"),
@@ -1087,7 +1920,7 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw(""),
             Line::styled(
                 "  KEY CONCEPT: COLLATERALIZATION",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  Each pair of YES + NO shares = $1.00 USDC"),
@@ -1096,7 +1929,7 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw(""),
             Line::styled(
                 "  PEER-TO-PEER TRADING",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  Unlike traditional betting:"),
@@ -1104,7 +1937,7 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw("  • No bookmaker setting arbitrary odds"),
             Line::raw("  • No limits on successful traders"),
             Line::raw(""),
-            Line::styled("  RESOLUTION", Style::default().fg(Color::Green).bold()),
+            Line::styled("  RESOLUTION", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  When an event concludes:"),
             Line::raw("  • The correct outcome shares pay $1.00 each"),
@@ -1116,20 +1949,20 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             // TRADING MECHANICS
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::styled(
                 "  TRADING MECHANICS",
-                Style::default().fg(Color::Yellow).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::raw(""),
             Line::styled(
                 "  UNDERSTANDING SHARES",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • Buy YES if you think an event is MORE likely"),
@@ -1140,7 +1973,7 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw(""),
             Line::styled(
                 "  ORDER BOOK (CLOB)",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  Polymarket uses a 'hybrid-decentralized CLOB':"),
@@ -1148,27 +1981,27 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw("  • Orders are matched off-chain (fast)"),
             Line::raw("  • Settlement happens on-chain (secure)"),
             Line::raw(""),
-            Line::styled("  ORDER TYPES", Style::default().fg(Color::Green).bold()),
+            Line::styled("  ORDER TYPES", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • Market Order: Buy/sell immediately at best price"),
             Line::raw("  • Limit Order: Set your own price, wait for match"),
             Line::raw(""),
-            Line::styled("  LIQUIDITY", Style::default().fg(Color::Green).bold()),
+            Line::styled("  LIQUIDITY", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • Liquidity = how easily you can buy/sell"),
             Line::raw("  • High liquidity = small price impact"),
             Line::raw("  • Low liquidity = larger price swings"),
             Line::raw("  • Market makers provide liquidity by posting orders"),
             Line::raw(""),
-            Line::styled("  SPREAD", Style::default().fg(Color::Green).bold()),
+            Line::styled("  SPREAD", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • Spread = difference between best buy and sell price"),
             Line::raw("  • Tight spread = efficient market"),
             Line::raw("  • Wide spread = hidden cost for impatient trades"),
             Line::raw(""),
-            Line::styled("  FEES", Style::default().fg(Color::Green).bold()),
+            Line::styled("  FEES", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
-            Line::raw("  • Trading fee: ~4% on transactions"),
+            Line::raw("  • Trading fee: ~4% (TRADING_FEE_PCT), netted from P&L"),
             Line::raw("  • No fees for deposits or withdrawals"),
             Line::raw("  • USDC is the native currency"),
             Line::raw(""),
@@ -1177,20 +2010,20 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             // SPIKE DETECTION
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::styled(
                 "  SPIKE DETECTION",
-                Style::default().fg(Color::Yellow).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::raw(""),
             Line::styled(
                 "  WHAT IS VOLUME VELOCITY?",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  Volume Velocity (V_v) measures how fast trading"),
@@ -1203,25 +2036,25 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw("  • Large institutional trades"),
             Line::raw("  • Market manipulation attempts"),
             Line::raw(""),
-            Line::styled("  THRESHOLDS", Style::default().fg(Color::Green).bold()),
+            Line::styled("  THRESHOLDS", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  Velocity levels:"),
             Line::styled(
                 "    Normal: <500 vol/sec",
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.success),
             ),
             Line::styled(
                 "    Elevated: 500-1000 vol/sec",
-                Style::default().fg(Color::Yellow),
+                Style::default().fg(theme.warning),
             ),
             Line::styled(
                 "    Spike Alert: >1000 vol/sec",
-                Style::default().fg(Color::Red),
+                Style::default().fg(theme.error),
             ),
             Line::raw(""),
             Line::styled(
                 "  ORDER BOOK IMBALANCE (OBI)",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  OBI measures the difference between buy and sell"),
@@ -1232,13 +2065,13 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw("  Range: -1.0 (all sells) to +1.0 (all buys)"),
             Line::styled(
                 "    Balanced: -0.3 to +0.3",
-                Style::default().fg(Color::Green),
+                Style::default().fg(theme.success),
             ),
-            Line::styled("    Imbalanced: >|0.3|", Style::default().fg(Color::Red)),
+            Line::styled("    Imbalanced: >|0.3|", Style::default().fg(theme.error)),
             Line::raw(""),
             Line::styled(
                 "  HOW THE BOT USES THIS",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  The bot monitors these metrics in real-time:"),
@@ -1252,20 +2085,20 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             // REFERENCES
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::styled(
                 "  REFERENCES & RESOURCES",
-                Style::default().fg(Color::Yellow).bold(),
+                Style::default().fg(theme.warning).bold(),
             ),
             Line::styled(
                 "  ═══════════════════════════════════════════════",
-                Style::default().fg(Color::Cyan),
+                Style::default().fg(theme.accent),
             ),
             Line::raw(""),
             Line::styled(
                 "  OFFICIAL DOCUMENTATION",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • Polymarket Learn: polymarket.com/learn"),
@@ -1274,7 +2107,7 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw(""),
             Line::styled(
                 "  ARTICLES & GUIDES",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • 'A Beginner's Guide to Prediction Markets'"),
@@ -1286,7 +2119,7 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw("  • 'Trading Strategies for Prediction Markets'"),
             Line::raw("    Source: medium.com/@polymarket"),
             Line::raw(""),
-            Line::styled("  KEY CONCEPTS", Style::default().fg(Color::Green).bold()),
+            Line::styled("  KEY CONCEPTS", Style::default().fg(theme.success).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • USDC: USD Coin, the stablecoin used for trading"),
             Line::raw("  • CLOB: Central Limit Order Book"),
@@ -1296,7 +2129,7 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw(""),
             Line::styled(
                 "  RESEARCH PAPERS",
-                Style::default().fg(Color::Green).bold(),
+                Style::default().fg(theme.success).bold(),
             ),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  • 'Prediction Markets: Theory & Practice'"),
@@ -1305,7 +2138,7 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
             Line::raw("  • 'The Wisdom of Crowds in Markets'"),
             Line::raw("    Surowiecki, Anchor Books (2005)"),
             Line::raw(""),
-            Line::styled("  DISCLAIMER", Style::default().fg(Color::Red).bold()),
+            Line::styled("  DISCLAIMER", Style::default().fg(theme.error).bold()),
             Line::raw("  ─────────────────────────────────────────────────"),
             Line::raw("  This bot is for educational purposes only."),
             Line::raw("  Trading involves risk. Never invest more than"),
@@ -1317,71 +2150,134 @@ fn get_doc_content(section: usize) -> Vec<Line<'static>> {
 }
 
 fn draw_footer(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     let shortcuts = if app.current_tab == Tab::Markets {
         Line::from(vec![
-            Span::styled(" [S]", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" [S]", Style::default().fg(theme.warning).bold()),
             Span::raw("earch  "),
-            Span::styled("[T]", Style::default().fg(Color::Cyan).bold()),
+            Span::styled("[T]", Style::default().fg(theme.accent).bold()),
             Span::raw("rending  "),
-            Span::styled("[:]", Style::default().fg(Color::Magenta).bold()),
+            Span::styled("[:]", Style::default().fg(theme.highlight).bold()),
             Span::raw("Command  "),
-            Span::styled("[↑↓]", Style::default().fg(Color::Blue).bold()),
+            Span::styled("[↑↓]", Style::default().fg(theme.info).bold()),
             Span::raw("Nav  "),
-            Span::styled("[Enter]", Style::default().fg(Color::Green).bold()),
+            Span::styled("[Enter]", Style::default().fg(theme.success).bold()),
             Span::raw("Join  "),
-            Span::styled("[Q]", Style::default().fg(Color::Red).bold()),
+            Span::styled("[V/$/A]", Style::default().fg(theme.accent).bold()),
+            Span::raw("Sort  "),
+            Span::styled("[Y]", Style::default().fg(theme.accent).bold()),
+            Span::raw("Copy ID  "),
+            Span::styled("[Q]", Style::default().fg(theme.error).bold()),
             Span::raw("uit"),
         ])
     } else if app.current_tab == Tab::MarketDetail {
         Line::from(vec![
-            Span::styled(" [↑↓]", Style::default().fg(Color::Blue).bold()),
+            Span::styled(" [↑↓]", Style::default().fg(theme.info).bold()),
             Span::raw("Navigate  "),
-            Span::styled("[Del/⌫]", Style::default().fg(Color::Red).bold()),
+            Span::styled("[Del/⌫]", Style::default().fg(theme.error).bold()),
             Span::raw("Leave  "),
-            Span::styled("[S]", Style::default().fg(Color::Cyan).bold()),
+            Span::styled("[O]", Style::default().fg(theme.success).bold()),
+            Span::raw("rder  "),
+            Span::styled("[Y]", Style::default().fg(theme.accent).bold()),
+            Span::raw("Copy ID  "),
+            Span::styled("[R]", Style::default().fg(theme.success).bold()),
+            Span::raw("efresh  "),
+            Span::styled("[S]", Style::default().fg(theme.accent).bold()),
             Span::raw("earch  "),
-            Span::styled("[:]", Style::default().fg(Color::Magenta).bold()),
+            Span::styled("[:]", Style::default().fg(theme.highlight).bold()),
+            Span::raw("Cmd  "),
+            Span::styled("[Q]", Style::default().fg(theme.error).bold()),
+            Span::raw("uit"),
+        ])
+    } else if app.current_tab == Tab::Logs {
+        Line::from(vec![
+            Span::styled(" [↑↓]", Style::default().fg(theme.info).bold()),
+            Span::raw("Scroll  "),
+            Span::styled("[PgUp/PgDn]", Style::default().fg(theme.info).bold()),
+            Span::raw("Page  "),
+            Span::styled("[Home/End]", Style::default().fg(theme.accent).bold()),
+            Span::raw("Newest/Oldest  "),
+            Span::styled("[L]", Style::default().fg(theme.warning).bold()),
+            Span::raw("evel  "),
+            Span::styled("[F]", Style::default().fg(theme.warning).bold()),
+            Span::raw("ilter  "),
+            Span::styled("[:]", Style::default().fg(theme.highlight).bold()),
+            Span::raw("Cmd  "),
+            Span::styled("[Q]", Style::default().fg(theme.error).bold()),
+            Span::raw("uit"),
+        ])
+    } else if app.current_tab == Tab::Orders {
+        Line::from(vec![
+            Span::styled(" [↑↓]", Style::default().fg(theme.info).bold()),
+            Span::raw("Select  "),
+            Span::styled("[O]", Style::default().fg(theme.success).bold()),
+            Span::raw("rder  "),
+            Span::styled("[X]", Style::default().fg(theme.error).bold()),
+            Span::raw("Cancel  "),
+            Span::styled("[P]", Style::default().fg(theme.warning).bold()),
+            Span::raw("ause  "),
+            Span::styled("[R]", Style::default().fg(theme.success).bold()),
+            Span::raw("esume  "),
+            Span::styled("[:]", Style::default().fg(theme.highlight).bold()),
+            Span::raw("Cmd  "),
+            Span::styled("[Q]", Style::default().fg(theme.error).bold()),
+            Span::raw("uit"),
+        ])
+    } else if app.input_mode == InputMode::SettingsEdit {
+        Line::from(vec![
+            Span::styled(" [Enter]", Style::default().fg(theme.success).bold()),
+            Span::raw("Apply  "),
+            Span::styled("[Esc]", Style::default().fg(theme.error).bold()),
+            Span::raw("Cancel"),
+        ])
+    } else if app.current_tab == Tab::Settings {
+        Line::from(vec![
+            Span::styled(" [↑↓]", Style::default().fg(theme.info).bold()),
+            Span::raw("Select  "),
+            Span::styled("[Enter]", Style::default().fg(theme.success).bold()),
+            Span::raw("Edit  "),
+            Span::styled("[:]", Style::default().fg(theme.highlight).bold()),
             Span::raw("Cmd  "),
-            Span::styled("[Q]", Style::default().fg(Color::Red).bold()),
+            Span::styled("[Q]", Style::default().fg(theme.error).bold()),
             Span::raw("uit"),
         ])
     } else if app.current_tab == Tab::Docs {
         if app.docs_viewing_content {
             Line::from(vec![
-                Span::styled(" [↑↓]", Style::default().fg(Color::Blue).bold()),
+                Span::styled(" [↑↓]", Style::default().fg(theme.info).bold()),
                 Span::raw("Scroll  "),
-                Span::styled("[⌫/←]", Style::default().fg(Color::Yellow).bold()),
+                Span::styled("[⌫/←]", Style::default().fg(theme.warning).bold()),
                 Span::raw("Back  "),
-                Span::styled("[1-6]", Style::default().fg(Color::Cyan).bold()),
+                Span::styled("[1-7]", Style::default().fg(theme.accent).bold()),
                 Span::raw("Tabs  "),
-                Span::styled("[Q]", Style::default().fg(Color::Red).bold()),
+                Span::styled("[Q]", Style::default().fg(theme.error).bold()),
                 Span::raw("uit"),
             ])
         } else {
             Line::from(vec![
-                Span::styled(" [↑↓]", Style::default().fg(Color::Blue).bold()),
+                Span::styled(" [↑↓]", Style::default().fg(theme.info).bold()),
                 Span::raw("Select  "),
-                Span::styled("[Enter]", Style::default().fg(Color::Green).bold()),
+                Span::styled("[Enter]", Style::default().fg(theme.success).bold()),
                 Span::raw("View  "),
-                Span::styled("[←→]", Style::default().fg(Color::Yellow).bold()),
+                Span::styled("[←→]", Style::default().fg(theme.warning).bold()),
                 Span::raw("Tabs  "),
-                Span::styled("[Q]", Style::default().fg(Color::Red).bold()),
+                Span::styled("[Q]", Style::default().fg(theme.error).bold()),
                 Span::raw("uit"),
             ])
         }
     } else {
         Line::from(vec![
-            Span::styled(" [P]", Style::default().fg(Color::Yellow).bold()),
+            Span::styled(" [P]", Style::default().fg(theme.warning).bold()),
             Span::raw("ause  "),
-            Span::styled("[R]", Style::default().fg(Color::Green).bold()),
+            Span::styled("[R]", Style::default().fg(theme.success).bold()),
             Span::raw("esume  "),
-            Span::styled("[S]", Style::default().fg(Color::Cyan).bold()),
+            Span::styled("[S]", Style::default().fg(theme.accent).bold()),
             Span::raw("earch  "),
-            Span::styled("[:]", Style::default().fg(Color::Magenta).bold()),
+            Span::styled("[:]", Style::default().fg(theme.highlight).bold()),
             Span::raw("Cmd  "),
-            Span::styled("[H]", Style::default().fg(Color::Blue).bold()),
+            Span::styled("[H]", Style::default().fg(theme.info).bold()),
             Span::raw("elp  "),
-            Span::styled("[Q]", Style::default().fg(Color::Red).bold()),
+            Span::styled("[Q]", Style::default().fg(theme.error).bold()),
             Span::raw("uit"),
         ])
     };
@@ -1394,9 +2290,10 @@ fn draw_footer(frame: &mut Frame, area: Rect, app: &App) {
 }
 
 fn draw_quit_confirmation_modal(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Create centered modal area
     let modal_width = 50;
-    let modal_height = 7;
+    let modal_height = 8;
 
     let modal_area = Rect {
         x: (area.width.saturating_sub(modal_width)) / 2,
@@ -1410,22 +2307,22 @@ fn draw_quit_confirmation_modal(frame: &mut Frame, area: Rect, app: &App) {
 
     // Modal content
     let yes_style = if app.quit_selection == QuitSelection::Yes {
-        Style::default().bg(Color::Red).fg(Color::White).bold()
+        Style::default().bg(theme.error).fg(theme.text).bold()
     } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(theme.muted)
     };
 
     let no_style = if app.quit_selection == QuitSelection::No {
-        Style::default().bg(Color::Green).fg(Color::Black).bold()
+        Style::default().bg(theme.success).fg(theme.inverse).bold()
     } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(theme.muted)
     };
 
     let modal_content = vec![
         Line::raw(""),
         Line::from(vec![Span::styled(
             "  Are you sure you want to quit?",
-            Style::default().fg(Color::Yellow).bold(),
+            Style::default().fg(theme.warning).bold(),
         )]),
         Line::raw(""),
         Line::from(vec![
@@ -1435,13 +2332,17 @@ fn draw_quit_confirmation_modal(frame: &mut Frame, area: Rect, app: &App) {
             Span::styled("  No  ", no_style),
         ]),
         Line::raw(""),
+        Line::styled(
+            "Q again or Ctrl-C to quit instantly",
+            Style::default().fg(theme.dim),
+        ),
     ];
 
     let modal = Paragraph::new(modal_content)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Red))
+                .border_style(Style::default().fg(theme.error))
                 .title(" ⚠️  Confirm Quit "),
         )
         .alignment(Alignment::Center);
@@ -1449,7 +2350,66 @@ fn draw_quit_confirmation_modal(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(modal, modal_area);
 }
 
+/// Lines listing the keybindings for `tab`, key column padded to line up
+fn help_overlay_lines(tab: Tab, theme: &Theme) -> Vec<Line<'static>> {
+    let bindings = tab.keybindings();
+    let key_width = bindings
+        .iter()
+        .map(|(key, _)| key.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    bindings
+        .into_iter()
+        .map(|(key, action)| {
+            let padding = " ".repeat(key_width - key.chars().count());
+            Line::from(vec![
+                Span::styled(
+                    format!("  {}{}  ", key, padding),
+                    Style::default().fg(theme.highlight).bold(),
+                ),
+                Span::styled(action, Style::default().fg(theme.text)),
+            ])
+        })
+        .collect()
+}
+
+fn draw_help_overlay(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let mut content = vec![Line::raw("")];
+    content.extend(help_overlay_lines(app.current_tab, theme));
+    content.push(Line::raw(""));
+    content.push(Line::styled(
+        "  Press any key to close",
+        Style::default().fg(theme.dim),
+    ));
+
+    // Create centered modal area, sized to the list
+    let modal_width = 52;
+    let modal_height = content.len() as u16 + 2;
+
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width.min(area.width),
+        height: modal_height.min(area.height),
+    };
+
+    // Clear background
+    frame.render_widget(Clear, modal_area);
+
+    let modal = Paragraph::new(content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.info))
+            .title(format!(" ⌨️  {} Keys ", app.current_tab.title())),
+    );
+
+    frame.render_widget(modal, modal_area);
+}
+
 fn draw_leave_confirmation_modal(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
     // Create centered modal area
     let modal_width = 60;
     let modal_height = 9;
@@ -1469,8 +2429,8 @@ fn draw_leave_confirmation_modal(frame: &mut Frame, area: Rect, app: &App) {
         .watched_markets_info
         .get(app.selected_watched_market_index)
         .map(|m| {
-            if m.question.len() > 40 {
-                format!("{}...", &m.question[..40])
+            if m.question.chars().count() > 40 {
+                format!("{}...", truncate_str(&m.question, 40))
             } else {
                 m.question.clone()
             }
@@ -1479,27 +2439,27 @@ fn draw_leave_confirmation_modal(frame: &mut Frame, area: Rect, app: &App) {
 
     // Modal content
     let yes_style = if app.leave_selection == LeaveSelection::Yes {
-        Style::default().bg(Color::Red).fg(Color::White).bold()
+        Style::default().bg(theme.error).fg(theme.text).bold()
     } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(theme.muted)
     };
 
     let no_style = if app.leave_selection == LeaveSelection::No {
-        Style::default().bg(Color::Green).fg(Color::Black).bold()
+        Style::default().bg(theme.success).fg(theme.inverse).bold()
     } else {
-        Style::default().fg(Color::Gray)
+        Style::default().fg(theme.muted)
     };
 
     let modal_content = vec![
         Line::raw(""),
         Line::from(vec![Span::styled(
             "  Leave this market?",
-            Style::default().fg(Color::Yellow).bold(),
+            Style::default().fg(theme.warning).bold(),
         )]),
         Line::raw(""),
         Line::from(vec![Span::styled(
             format!("  {}", market_name),
-            Style::default().fg(Color::Cyan),
+            Style::default().fg(theme.accent),
         )]),
         Line::raw(""),
         Line::from(vec![
@@ -1515,10 +2475,381 @@ fn draw_leave_confirmation_modal(frame: &mut Frame, area: Rect, app: &App) {
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .border_style(Style::default().fg(Color::Yellow))
+                .border_style(Style::default().fg(theme.warning))
                 .title(" 🚪 Leave Market "),
         )
         .alignment(Alignment::Center);
 
     frame.render_widget(modal, modal_area);
 }
+
+fn draw_order_entry_modal(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let Some(form) = app.order_form.as_ref() else {
+        return;
+    };
+
+    // Create centered modal area
+    let modal_width = 60;
+    let modal_height = 14;
+
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width.min(area.width),
+        height: modal_height.min(area.height),
+    };
+
+    // Clear background
+    frame.render_widget(Clear, modal_area);
+
+    let market_name = if form.question.chars().count() > 40 {
+        format!("{}...", truncate_str(&form.question, 40))
+    } else {
+        form.question.clone()
+    };
+
+    let label_style = |step: OrderFormStep| {
+        if form.step == step {
+            Style::default().fg(theme.warning).bold()
+        } else {
+            Style::default().fg(theme.muted)
+        }
+    };
+    let cursor = |step: OrderFormStep| {
+        if form.step == step {
+            Span::styled("▌", Style::default().fg(theme.warning))
+        } else {
+            Span::raw("")
+        }
+    };
+
+    let buy_style = if form.side == "BUY" {
+        Style::default().bg(theme.success).fg(theme.inverse).bold()
+    } else {
+        Style::default().fg(theme.muted)
+    };
+    let sell_style = if form.side == "SELL" {
+        Style::default().bg(theme.error).fg(theme.text).bold()
+    } else {
+        Style::default().fg(theme.muted)
+    };
+
+    let config = app.execution_engine.config();
+    let hint = match form.step {
+        OrderFormStep::Side => "←/→ or B/S to choose, Enter to continue".to_string(),
+        OrderFormStep::Size => format!(
+            "Size {}-{} USDC, Enter to continue",
+            config.min_order_size, config.max_order_size
+        ),
+        OrderFormStep::Price => "0.00-1.00, leave empty for a market order".to_string(),
+    };
+
+    let mut modal_content = vec![
+        Line::raw(""),
+        Line::from(vec![Span::styled(
+            format!("  {}", market_name),
+            Style::default().fg(theme.accent),
+        )]),
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled("  Side:  ", label_style(OrderFormStep::Side)),
+            Span::styled("  BUY  ", buy_style),
+            Span::raw("  "),
+            Span::styled("  SELL  ", sell_style),
+        ]),
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled("  Size:  ", label_style(OrderFormStep::Size)),
+            Span::styled(&form.size_input, Style::default().fg(theme.text)),
+            cursor(OrderFormStep::Size),
+        ]),
+        Line::from(vec![
+            Span::styled("  Price: ", label_style(OrderFormStep::Price)),
+            Span::styled(&form.price_input, Style::default().fg(theme.text)),
+            cursor(OrderFormStep::Price),
+        ]),
+        Line::raw(""),
+        Line::styled(format!("  {}", hint), Style::default().fg(theme.muted)),
+    ];
+
+    if form.step == OrderFormStep::Side && !config.order_size_presets.is_empty() {
+        let presets: Vec<String> = config
+            .order_size_presets
+            .iter()
+            .enumerate()
+            .map(|(i, size)| format!("{}={}", i + 1, size))
+            .collect();
+        modal_content.push(Line::styled(
+            format!("  Size presets: {}", presets.join("  ")),
+            Style::default().fg(theme.muted),
+        ));
+    }
+
+    if let Some(ref error) = form.error {
+        modal_content.push(Line::styled(
+            format!("  {}", error),
+            Style::default().fg(theme.error).bold(),
+        ));
+    }
+
+    let modal = Paragraph::new(modal_content).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.success))
+            .title(" 🧾 New Order (ESC to cancel) "),
+    );
+
+    frame.render_widget(modal, modal_area);
+}
+
+/// Gross and net profit of a limit buy if its outcome resolves to $1.
+/// Resolution pays out without a fill, so only the entry is charged a fee.
+fn limit_buy_max_profit(price: f64, size: f64, fee_pct: f64) -> (f64, f64) {
+    let gross = (1.0 - price) * size;
+    (gross, gross - trading_fee(price, size, fee_pct))
+}
+
+fn draw_order_confirmation_modal(frame: &mut Frame, area: Rect, app: &App) {
+    let theme = &app.theme;
+    let Some(order) = app.pending_order.as_ref() else {
+        return;
+    };
+
+    // Create centered modal area
+    let modal_width = 60;
+    let modal_height = 12;
+
+    let modal_area = Rect {
+        x: (area.width.saturating_sub(modal_width)) / 2,
+        y: (area.height.saturating_sub(modal_height)) / 2,
+        width: modal_width.min(area.width),
+        height: modal_height.min(area.height),
+    };
+
+    // Clear background
+    frame.render_widget(Clear, modal_area);
+
+    let market_name = if order.question.chars().count() > 40 {
+        format!("{}...", truncate_str(&order.question, 40))
+    } else {
+        order.question.clone()
+    };
+
+    let side_style = if order.side == "BUY" {
+        Style::default().fg(theme.success).bold()
+    } else {
+        Style::default().fg(theme.error).bold()
+    };
+
+    let price_text = order
+        .price
+        .map(|p| format!("${:.2}", p))
+        .unwrap_or_else(|| "Market".to_string());
+
+    // Modal content
+    let yes_style = if app.order_selection == OrderSelection::Yes {
+        Style::default().bg(theme.error).fg(theme.text).bold()
+    } else {
+        Style::default().fg(theme.muted)
+    };
+
+    let no_style = if app.order_selection == OrderSelection::No {
+        Style::default().bg(theme.success).fg(theme.inverse).bold()
+    } else {
+        Style::default().fg(theme.muted)
+    };
+
+    let mut modal_content = vec![
+        Line::raw(""),
+        Line::from(vec![Span::styled(
+            "  Place this order?",
+            Style::default().fg(theme.warning).bold(),
+        )]),
+        Line::raw(""),
+        Line::from(vec![Span::styled(
+            format!("  {}", market_name),
+            Style::default().fg(theme.accent),
+        )]),
+        Line::raw(""),
+        Line::from(vec![
+            Span::styled(format!("{} ", order.side), side_style),
+            Span::styled(
+                format!("{} ", order.order_type),
+                Style::default().fg(theme.muted),
+            ),
+            Span::styled(
+                format!("{} @ {}", order.size, price_text),
+                Style::default().fg(theme.text).bold(),
+            ),
+        ]),
+    ];
+
+    if let (true, Some(price)) = (order.side == "BUY", order.price) {
+        let fee_pct = app.execution_engine.config().trading_fee_pct;
+        let (gross, net) = limit_buy_max_profit(price, order.size, fee_pct);
+        modal_content.push(Line::styled(
+            format!("Max profit: ${:.2} gross, ${:.2} net of fees", gross, net),
+            Style::default().fg(theme.muted),
+        ));
+    }
+
+    modal_content.extend([
+        Line::raw(""),
+        Line::from(vec![
+            Span::raw("      "),
+            Span::styled("  Yes  ", yes_style),
+            Span::raw("    "),
+            Span::styled("  No  ", no_style),
+        ]),
+        Line::raw(""),
+    ]);
+
+    let modal = Paragraph::new(modal_content)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .border_style(Style::default().fg(theme.warning))
+                .title(" 💸 Confirm Order "),
+        )
+        .alignment(Alignment::Center);
+
+    frame.render_widget(modal, modal_area);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_pnl_text_dollars_and_percent() {
+        assert_eq!(pnl_text(12.5, None), "+12.50");
+        assert_eq!(pnl_text(-3.0, None), "-3.00");
+        assert_eq!(pnl_text(12.5, Some(250.0)), "+5.00%");
+        assert_eq!(pnl_text(12.5, Some(0.0)), "n/a %");
+    }
+
+    #[test]
+    fn test_truncate_str_multibyte() {
+        // "é" and the emoji are multi-byte; byte slicing at 2 or 5 would panic
+        let question = "Jé 🚀 will it moon?";
+        assert_eq!(truncate_str(question, 2), "Jé");
+        assert_eq!(truncate_str(question, 4), "Jé 🚀");
+        assert_eq!(truncate_str(question, 100), question);
+        assert_eq!(truncate_str("", 5), "");
+    }
+
+    #[test]
+    fn test_ellipsize() {
+        assert_eq!(ellipsize("short", 10), "short");
+        assert_eq!(ellipsize("exactly10!", 10), "exactly10!");
+        assert_eq!(ellipsize("Will 🚀 moon this year?", 10), "Will 🚀 ...");
+    }
+
+    #[test]
+    fn test_max_profit_charges_only_the_entry_fee() {
+        // 100 @ 0.40 pays 60.00 at resolution; 4% of the 40.00 entry is 1.60
+        let (gross, net) = limit_buy_max_profit(0.40, 100.0, 0.04);
+        assert!((gross - 60.0).abs() < 1e-9);
+        assert!((net - 58.4).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_wrap_words() {
+        assert_eq!(
+            wrap_words("Will the Fed cut rates in March?", 12),
+            vec!["Will the Fed", "cut rates in", "March?"]
+        );
+        // Over-long words are split; multi-byte characters stay intact
+        assert_eq!(wrap_words("ééééé ab", 3), vec!["ééé", "éé", "ab"]);
+        assert!(wrap_words("", 10).is_empty());
+    }
+
+    #[test]
+    fn test_bar_width_scales_with_panel() {
+        assert_eq!(bar_width(Rect::new(0, 0, 44, 10)), 40);
+        assert_eq!(bar_width(Rect::new(0, 0, 12, 10)), MIN_BAR_WIDTH);
+        assert_eq!(bar_width(Rect::new(0, 0, 0, 0)), MIN_BAR_WIDTH);
+    }
+
+    #[test]
+    fn test_doc_line_count_matches_content() {
+        for section in 0..5 {
+            let lines = get_doc_content(section, &Theme::default());
+            assert!(!lines.is_empty());
+            assert_eq!(doc_line_count(section) as usize, lines.len());
+        }
+        assert_eq!(doc_line_count(99), 0);
+    }
+
+    #[test]
+    fn test_help_overlay_fits_modal() {
+        for tab in Tab::all() {
+            let lines = help_overlay_lines(tab, &Theme::default());
+            assert!(lines
+                .iter()
+                .any(|line| line.to_string().contains("This help")));
+            // Modal is 52 wide including borders
+            assert!(lines.iter().all(|line| line.width() <= 50), "{:?}", tab);
+        }
+    }
+
+    #[test]
+    fn test_obi_trend_colors_each_move() {
+        assert_eq!(sparkline(&[-0.5, 0.0, 0.5]), vec!['▁', '▅', '█']);
+        assert_eq!(sparkline(&[0.2, 0.2]), vec!['▅', '▅']);
+        assert!(sparkline(&[]).is_empty());
+
+        let theme = Theme::default();
+        let spans = obi_trend_spans(&[0.1, 0.3, 0.2, 0.2], &theme);
+        let colors: Vec<_> = spans.iter().map(|s| s.style.fg).collect();
+        assert_eq!(
+            colors,
+            vec![
+                Some(theme.muted),
+                Some(theme.success),
+                Some(theme.error),
+                Some(theme.muted),
+                Some(theme.success),
+            ]
+        );
+        assert_eq!(spans.last().unwrap().content, " ▲");
+        assert_eq!(obi_trend_spans(&[0.4, -0.1], &theme)[2].content, " ▼");
+    }
+
+    #[test]
+    fn test_freshness_span_flags_stale_data() {
+        let theme = Theme::default();
+        let fresh = freshness_span(Some(Duration::from_secs(2)), &theme);
+        assert_eq!(fresh.content, "2s ago");
+        assert_eq!(fresh.style.fg, Some(theme.success));
+
+        let stale = freshness_span(Some(STALE_DATA_AFTER), &theme);
+        assert_eq!(stale.style.fg, Some(theme.warning));
+        assert!(stale.content.starts_with("stale ("));
+
+        let very_stale = freshness_span(Some(STALE_DATA_AFTER * 2), &theme);
+        assert_eq!(very_stale.style.fg, Some(theme.error));
+
+        assert_eq!(freshness_span(None, &theme).content, "waiting for data");
+    }
+
+    #[test]
+    fn test_too_small_terminal_shows_resize_hint() {
+        let backend = ratatui::backend::TestBackend::new(40, 10);
+        let mut terminal = Terminal::new(backend).unwrap();
+        terminal
+            .draw(|frame| draw_too_small(frame, frame.area(), &Theme::default()))
+            .unwrap();
+
+        let text: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .iter()
+            .map(|cell| cell.symbol())
+            .collect();
+        assert!(text.contains("Terminal too small"));
+        assert!(text.contains("have 40x10"));
+    }
+}
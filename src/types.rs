@@ -1,30 +1,58 @@
 use serde::{Deserialize, Serialize};
 
 /// Represents the current state of the trading bot
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default)]
 pub struct BotState {
     pub is_paused: bool,
     pub last_order_id: Option<String>,
     pub monitored_markets: Vec<String>,
 }
 
-impl Default for BotState {
-    fn default() -> Self {
-        Self {
-            is_paused: false,
-            last_order_id: None,
-            monitored_markets: Vec::new(),
-        }
-    }
-}
-
 /// Portfolio information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Portfolio {
     pub usdc_balance: f64,
     pub total_value: f64,
+    /// Net of trading fees
     pub realized_pnl: f64,
     pub unrealized_pnl: f64,
+    /// Fees deducted from `realized_pnl`
+    pub fees_paid: f64,
+}
+
+/// Order type accepted by the execution engine
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OrderType {
+    /// Fill immediately; the price is the worst acceptable fill
+    Market,
+    /// Rest on the book at the given price until matched
+    Limit,
+}
+
+impl OrderType {
+    /// Value stored in the `orders.order_type` column
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OrderType::Market => "MARKET",
+            OrderType::Limit => "LIMIT",
+        }
+    }
+
+    /// Parse a stored `order_type` value.
+    /// Unknown or legacy values are treated as market orders so older rows still load.
+    pub fn from_db(value: &str) -> Self {
+        if value.eq_ignore_ascii_case("LIMIT") {
+            OrderType::Limit
+        } else {
+            OrderType::Market
+        }
+    }
+}
+
+impl std::fmt::Display for OrderType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Order information
@@ -40,6 +68,17 @@ pub struct OrderInfo {
     pub created_at: i64,
 }
 
+/// A fill recorded in the `trades` table
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TradeRecord {
+    pub market_id: String,
+    pub trade_id: String,
+    pub side: String,
+    pub price: f64,
+    pub size: f64,
+    pub timestamp: i64,
+}
+
 /// Market information
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MarketInfo {
@@ -49,7 +88,7 @@ pub struct MarketInfo {
 }
 
 /// Volume velocity spike event
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct VolumeVelocityEvent {
     pub market_id: String,
     pub velocity: f64,
@@ -59,7 +98,7 @@ pub struct VolumeVelocityEvent {
 }
 
 /// Order book imbalance data
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OrderBookImbalance {
     pub market_id: String,
     pub obi: f64, // (V_bids - V_asks) / (V_bids + V_asks)
@@ -67,3 +106,42 @@ pub struct OrderBookImbalance {
     pub asks_volume: f64,
     pub timestamp: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detection_events_serde_round_trip() {
+        let event = VolumeVelocityEvent {
+            market_id: "market_1".to_string(),
+            velocity: 1500.0,
+            volume_delta: 3000.0,
+            time_delta: 2.0,
+            timestamp: 1_700_000_000,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        let back: VolumeVelocityEvent = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.market_id, event.market_id);
+        assert_eq!(back.velocity, event.velocity);
+        assert_eq!(back.volume_delta, event.volume_delta);
+        assert_eq!(back.time_delta, event.time_delta);
+        assert_eq!(back.timestamp, event.timestamp);
+
+        let imbalance = OrderBookImbalance {
+            market_id: "market_1".to_string(),
+            obi: -0.25,
+            bids_volume: 300.0,
+            asks_volume: 500.0,
+            timestamp: 1_700_000_000,
+        };
+        let json = serde_json::to_string(&imbalance).unwrap();
+        assert!(json.contains("\"obi\":-0.25"));
+        let back: OrderBookImbalance = serde_json::from_str(&json).unwrap();
+        assert_eq!(back.market_id, imbalance.market_id);
+        assert_eq!(back.obi, imbalance.obi);
+        assert_eq!(back.bids_volume, imbalance.bids_volume);
+        assert_eq!(back.asks_volume, imbalance.asks_volume);
+        assert_eq!(back.timestamp, imbalance.timestamp);
+    }
+}